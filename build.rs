@@ -1,9 +1,12 @@
 fn main() {
     tonic_build::configure()
-        .build_client(false)
+        .build_client(true)
         .build_server(true)
         .compile(&[
+            "proto/admin.proto",
             "proto/auth.proto",
+            "proto/common.proto",
+            "proto/contacts.proto",
             "proto/profile.proto",
             "proto/registries.proto",
             "proto/transactions.proto",
@@ -15,4 +18,4 @@ fn main() {
 
     //tonic_build::compile_protos("./proto/*.proto")
     //    .unwrap_or_else(|e| panic!("Failed to compile protos {:?}", e));
-}
+}
\ No newline at end of file
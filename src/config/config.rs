@@ -2,13 +2,14 @@ use std::{env, fs::File, error::Error};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{storage::ScyllaConfig, domain::ServicesConfig, grpc::ServerConfig};
+use crate::{storage::StorageConfig, domain::ServicesConfig, grpc::ServerConfig, workers::ReconcilerConfig};
 
 #[derive(Serialize, Deserialize)]
 pub struct Config {
     pub server: ServerConfig,
-    pub scylla: ScyllaConfig,
+    pub storage: StorageConfig,
     pub services: ServicesConfig,
+    pub reconciler: ReconcilerConfig,
 }
 
 impl Config {
@@ -0,0 +1,5 @@
+pub enum CodeDeliveryState {
+    Delivered,
+    Rejected,
+    Unavailable,
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::CodeDeliveryState;
+
+#[async_trait]
+pub trait CodeSender: fmt::Debug {
+    async fn send(&self, destination: &str, code: i64) -> Result<CodeDeliveryState, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,23 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CodeSenderConfig {
+    Webhook {
+        url: String,
+        timeout: i64,
+    },
+    Smtp {
+        host: String,
+        port: u16,
+        username: String,
+        #[serde(skip_serializing)]
+        password: String,
+        from: String,
+        subject: String,
+    },
+    File {
+        path: String,
+    },
+    Log,
+}
\ No newline at end of file
@@ -0,0 +1,47 @@
+use std::{sync::Arc, error::Error};
+
+use crate::logging::Logger;
+
+use super::{CodeSender, CodeSenderConfig, WebhookCodeSender, SmtpCodeSender, FileCodeSender, LogCodeSender};
+
+#[derive(Debug)]
+pub struct CodeSenderFactory {
+    phone_code_sender: Arc<dyn CodeSender + Sync + Send>,
+    email_code_sender: Arc<dyn CodeSender + Sync + Send>,
+}
+
+impl CodeSenderFactory {
+    pub fn new(phone_config: &CodeSenderConfig, email_config: &CodeSenderConfig, logger: &Arc<Logger>) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            phone_code_sender: create_code_sender(phone_config, logger)?,
+            email_code_sender: create_code_sender(email_config, logger)?,
+        })
+    }
+
+    pub fn phone(&self) -> Arc<dyn CodeSender + Sync + Send> {
+        Arc::clone(&self.phone_code_sender)
+    }
+
+    pub fn email(&self) -> Arc<dyn CodeSender + Sync + Send> {
+        Arc::clone(&self.email_code_sender)
+    }
+}
+
+fn create_code_sender(config: &CodeSenderConfig, logger: &Arc<Logger>) -> Result<Arc<dyn CodeSender + Sync + Send>, Box<dyn Error>> {
+    let sender: Arc<dyn CodeSender + Sync + Send> = match config {
+        CodeSenderConfig::Webhook { url, timeout } => Arc::new(
+            WebhookCodeSender::new(url.clone(), *timeout)?
+        ),
+        CodeSenderConfig::Smtp { host, port, username, password, from, subject } => Arc::new(
+            SmtpCodeSender::new(host, *port, username.clone(), password.clone(), from, subject.clone())?
+        ),
+        CodeSenderConfig::File { path } => Arc::new(
+            FileCodeSender::new(path.clone())
+        ),
+        CodeSenderConfig::Log => Arc::new(
+            LogCodeSender::new(Arc::clone(logger))
+        ),
+    };
+
+    Ok(sender)
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use std::error::Error;
+
+use tokio::{fs::OpenOptions, io::AsyncWriteExt};
+use tonic::async_trait;
+
+use super::{CodeSender, CodeDeliveryState};
+
+#[derive(Debug)]
+pub struct FileCodeSender {
+    path: String,
+}
+
+impl FileCodeSender {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+        }
+    }
+}
+
+#[async_trait]
+impl CodeSender for FileCodeSender {
+    async fn send(&self, destination: &str, code: i64) -> Result<CodeDeliveryState, Box<dyn Error>> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+
+        let line = format!("{} {}\n", destination, code);
+        file.write_all(line.as_bytes()).await?;
+        // Tokio completes writes in the background, the code must be on disk once delivered
+        file.flush().await?;
+
+        Ok(CodeDeliveryState::Delivered)
+    }
+}
\ No newline at end of file
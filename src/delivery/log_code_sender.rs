@@ -0,0 +1,28 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::logging::Logger;
+
+use super::{CodeSender, CodeDeliveryState};
+
+#[derive(Debug)]
+pub struct LogCodeSender {
+    logger: Arc<Logger>,
+}
+
+impl LogCodeSender {
+    pub fn new(logger: Arc<Logger>) -> Self {
+        Self {
+            logger,
+        }
+    }
+}
+
+#[async_trait]
+impl CodeSender for LogCodeSender {
+    async fn send(&self, destination: &str, code: i64) -> Result<CodeDeliveryState, Box<dyn Error>> {
+        self.logger.log_info(&format!("Code for {}: {}", destination, code));
+        Ok(CodeDeliveryState::Delivered)
+    }
+}
\ No newline at end of file
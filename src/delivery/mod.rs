@@ -0,0 +1,17 @@
+mod code_sender;
+mod code_delivery_state;
+mod code_sender_config;
+mod code_sender_factory;
+mod webhook_code_sender;
+mod smtp_code_sender;
+mod log_code_sender;
+mod file_code_sender;
+
+pub use code_sender::CodeSender;
+pub use code_delivery_state::CodeDeliveryState;
+pub use code_sender_config::CodeSenderConfig;
+pub use code_sender_factory::CodeSenderFactory;
+pub use webhook_code_sender::WebhookCodeSender;
+pub use smtp_code_sender::SmtpCodeSender;
+pub use log_code_sender::LogCodeSender;
+pub use file_code_sender::FileCodeSender;
\ No newline at end of file
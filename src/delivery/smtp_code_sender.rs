@@ -0,0 +1,64 @@
+use std::error::Error;
+
+use lettre::{
+    AsyncSmtpTransport, 
+    AsyncTransport, 
+    Tokio1Executor, 
+    Message, 
+    message::Mailbox, 
+    transport::smtp::authentication::Credentials,
+};
+use tonic::async_trait;
+
+use super::{CodeSender, CodeDeliveryState};
+
+#[derive(Debug)]
+pub struct SmtpCodeSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+    subject: String,
+}
+
+impl SmtpCodeSender {
+    pub fn new(
+        host: &str, 
+        port: u16, 
+        username: String, 
+        password: String, 
+        from: &str, 
+        subject: String,
+    ) -> Result<Self, Box<dyn Error>> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)?
+            .port(port)
+            .credentials(Credentials::new(username, password))
+            .build();
+
+        Ok(Self {
+            transport,
+            from: from.parse()?,
+            subject,
+        })
+    }
+}
+
+#[async_trait]
+impl CodeSender for SmtpCodeSender {
+    async fn send(&self, destination: &str, code: i64) -> Result<CodeDeliveryState, Box<dyn Error>> {
+        let to: Mailbox = match destination.parse() {
+            Ok(to) => to,
+            Err(_) => return Ok(CodeDeliveryState::Rejected),
+        };
+
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(self.subject.clone())
+            .body(format!("Your code is {}", code))?;
+
+        match self.transport.send(message).await {
+            Ok(_) => Ok(CodeDeliveryState::Delivered),
+            Err(error) if error.is_permanent() => Ok(CodeDeliveryState::Rejected),
+            Err(_) => Ok(CodeDeliveryState::Unavailable),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,61 @@
+use std::{error::Error, time::Duration};
+
+use reqwest::Client;
+use serde::Serialize;
+use tonic::async_trait;
+
+use super::{CodeSender, CodeDeliveryState};
+
+#[derive(Debug)]
+pub struct WebhookCodeSender {
+    client: Client,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct WebhookPayload<'a> {
+    destination: &'a str,
+    code: i64,
+}
+
+impl WebhookCodeSender {
+    pub fn new(url: String, timeout: i64) -> Result<Self, Box<dyn Error>> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(timeout as u64))
+            .build()?;
+
+        Ok(Self {
+            client,
+            url,
+        })
+    }
+}
+
+#[async_trait]
+impl CodeSender for WebhookCodeSender {
+    async fn send(&self, destination: &str, code: i64) -> Result<CodeDeliveryState, Box<dyn Error>> {
+        let result = self.client
+            .post(&self.url)
+            .json(&WebhookPayload { destination, code })
+            .send()
+            .await;
+
+        let response = match result {
+            Ok(response) => response,
+            Err(error) if error.is_timeout() || error.is_connect() => return Ok(CodeDeliveryState::Unavailable),
+            Err(error) => return Err(error.into()),
+        };
+
+        let status = response.status();
+
+        if status.is_success() {
+            Ok(CodeDeliveryState::Delivered)
+        }
+        else if status.is_client_error() {
+            Ok(CodeDeliveryState::Rejected)
+        }
+        else {
+            Ok(CodeDeliveryState::Unavailable)
+        }
+    }
+}
\ No newline at end of file
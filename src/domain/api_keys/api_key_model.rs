@@ -0,0 +1,21 @@
+use crate::storage::api_keys::ApiKeyDto;
+
+pub struct ApiKeyModel {
+    pub id: String,
+    pub user_id: i64,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: i64,
+}
+
+impl From<ApiKeyDto> for ApiKeyModel {
+    fn from(dto: ApiKeyDto) -> Self {
+        Self {
+            id: dto.id,
+            user_id: dto.user_id,
+            name: dto.name,
+            scopes: dto.scopes,
+            created_at: dto.created_at,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use std::{sync::Arc, error::Error, time::{SystemTime, UNIX_EPOCH}};
+
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Sha256, Digest};
+
+use crate::storage::api_keys::{ApiKeyRepository, ApiKeyDto};
+
+use super::ApiKeyModel;
+
+const ID_LENGTH: usize = 12;
+const SECRET_LENGTH: usize = 32;
+
+pub struct ApiKeyService {
+    api_key_repository: Arc<dyn ApiKeyRepository + Sync + Send>,
+}
+
+impl ApiKeyService {
+    pub fn new(
+        api_key_repository: Arc<dyn ApiKeyRepository + Sync + Send>,
+    ) -> Self {
+        Self {
+            api_key_repository,
+        }
+    }
+
+    pub async fn create(
+        &self, 
+        user_id: i64, 
+        name: String, 
+        scopes: Vec<String>,
+    ) -> Result<Option<(ApiKeyModel, String)>, Box<dyn Error>> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        let mut id = [0u8; ID_LENGTH];
+        let mut secret = [0u8; SECRET_LENGTH];
+        OsRng.fill_bytes(&mut id);
+        OsRng.fill_bytes(&mut secret);
+
+        let id = base64_url::encode(&id);
+        let secret = base64_url::encode(&secret);
+
+        let dto = ApiKeyDto {
+            id: id.clone(),
+            secret_hash: hash(&secret),
+            user_id,
+            name,
+            scopes,
+            created_at: now,
+        };
+
+        if !self.api_key_repository.create(&dto).await? {
+            return Ok(None);
+        }
+
+        Ok(Some((dto.into(), format!("{}.{}", id, secret))))
+    }
+
+    pub async fn revoke(&self, id: &str) -> Result<bool, Box<dyn Error>> {
+        self.api_key_repository.delete(id).await
+    }
+
+    pub async fn authenticate(&self, key: &str) -> Result<Option<ApiKeyModel>, Box<dyn Error>> {
+        let (id, secret) = match key.split_once('.') {
+            Some(parts) => parts,
+            None => return Ok(None),
+        };
+
+        let dto = match self.api_key_repository.find(id).await? {
+            Some(dto) => dto,
+            None => return Ok(None),
+        };
+
+        let expected = hash(secret);
+
+        // Compare without early exit to not leak matching prefix length
+        let difference = dto.secret_hash
+            .iter()
+            .zip(expected.iter())
+            .fold(dto.secret_hash.len() ^ expected.len(), |acc, (a, b)| acc | (a ^ b) as usize);
+
+        if difference != 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(dto.into()))
+    }
+}
+
+fn hash(secret: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(secret.as_bytes());
+    hasher.finalize().to_vec()
+}
\ No newline at end of file
@@ -0,0 +1,5 @@
+mod api_key_model;
+mod api_key_service;
+
+pub use api_key_model::ApiKeyModel;
+pub use api_key_service::ApiKeyService;
\ No newline at end of file
@@ -0,0 +1,16 @@
+use std::error::Error;
+
+use tonic::async_trait;
+
+use super::CodeModel;
+
+// Storage of codes sent to one destination, phone and email codes differ only here
+#[async_trait]
+pub trait CodeChannel {
+    fn destination(&self) -> String;
+    async fn create(&self, model: &CodeModel) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, model: &CodeModel) -> Result<bool, Box<dyn Error>>;
+    async fn update_attempts(&self, model: &CodeModel, attempts: i16) -> Result<bool, Box<dyn Error>>;
+    async fn update_created_at(&self, model: &CodeModel, created_at: i64) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self) -> Result<Option<CodeModel>, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+use rand::{rngs::OsRng, RngCore};
+
+#[derive(Debug, Clone)]
+pub struct CodeModel {
+    pub code: i64,
+    pub created_at: i64,
+    pub attempts: i16,
+    pub ttl: i32,
+}
+
+impl CodeModel {
+    pub fn new(max_code: i64, ttl: i32, created_at: i64) -> Self {
+        Self {
+            code: (OsRng.next_u64() % max_code as u64) as i64,
+            created_at,
+            attempts: 0,
+            ttl,
+        }
+    }
+}
\ No newline at end of file
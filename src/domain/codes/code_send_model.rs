@@ -1,3 +1,4 @@
+#[derive(Debug, PartialEq)]
 pub enum CodeSendModel {
     Success(i64, i64),
     Timeout(i64),
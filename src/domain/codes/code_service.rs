@@ -1,28 +1,53 @@
 use std::{sync::Arc, error::Error, time::{SystemTime, UNIX_EPOCH}};
 
-use crate::storage::phone_codes::{PhoneCodeRepository, PhoneCodeDto};
-
-use super::{CodeSendModel, CodeAttemptModel, CodesConfig};
+use crate::{
+    storage::{phone_codes::PhoneCodeRepository, email_codes::EmailCodeRepository}, 
+    delivery::{CodeSender, CodeDeliveryState},
+};
+
+use super::{CodeSendModel, CodeAttemptModel, CodesConfig, CodeModel, CodeChannel, PhoneCodeChannel, EmailCodeChannel};
+
+struct CodeLimits {
+    attempts: i16,
+    max: i64,
+    timeout: i64,
+    expiration: i64,
+}
 
 pub struct CodeService {
-    attemtps_phone: i16,
-    max_phone: i64,
-    timeout_phone: i64,
-    expiration_phone: i64,
+    phone_limits: CodeLimits,
+    email_limits: CodeLimits,
     phone_code_repository: Arc<dyn PhoneCodeRepository + Sync + Send>,
+    email_code_repository: Arc<dyn EmailCodeRepository + Sync + Send>,
+    phone_code_sender: Arc<dyn CodeSender + Sync + Send>,
+    email_code_sender: Arc<dyn CodeSender + Sync + Send>,
 }
 
 impl CodeService {
     pub fn new(
         config: &CodesConfig,
         phone_code_repository: Arc<dyn PhoneCodeRepository + Sync + Send>,
+        email_code_repository: Arc<dyn EmailCodeRepository + Sync + Send>,
+        phone_code_sender: Arc<dyn CodeSender + Sync + Send>,
+        email_code_sender: Arc<dyn CodeSender + Sync + Send>,
     ) -> Self {
         Self {
-            attemtps_phone: config.attemtps_phone,
-            max_phone: config.max_phone,
-            timeout_phone: config.timeout_phone,
-            expiration_phone: config.expiration_phone,
+            phone_limits: CodeLimits {
+                attempts: config.attempts_phone,
+                max: config.max_phone,
+                timeout: config.timeout_phone,
+                expiration: config.expiration_phone,
+            },
+            email_limits: CodeLimits {
+                attempts: config.attempts_email,
+                max: config.max_email,
+                timeout: config.timeout_email,
+                expiration: config.expiration_email,
+            },
             phone_code_repository,
+            email_code_repository,
+            phone_code_sender,
+            email_code_sender,
         }
     }
 
@@ -31,80 +56,122 @@ impl CodeService {
             return Ok(CodeSendModel::Fail);
         }
 
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
+        let channel = PhoneCodeChannel::new(phone, Arc::clone(&self.phone_code_repository));
+        send(&channel, &self.phone_limits, self.phone_code_sender.as_ref()).await
+    }
+
+    pub async fn attempt_phone(&self, phone: i64, code: i64) -> Result<CodeAttemptModel, Box<dyn Error>> {
+        let channel = PhoneCodeChannel::new(phone, Arc::clone(&self.phone_code_repository));
+        attempt(&channel, &self.phone_limits, code).await
+    }
 
-        let dto_option = self.phone_code_repository.find(phone).await?;
+    pub async fn send_email(&self, email: &str) -> Result<CodeSendModel, Box<dyn Error>> {
+        if email.is_empty() || email.len() > 254 || !email.contains('@') {
+            return Ok(CodeSendModel::Fail);
+        }
 
-        let mut dto = if let Some(dto) = dto_option {
-            let until = dto.created_at + self.timeout_phone;
-            if until > now {
-                return Ok(CodeSendModel::Timeout(until));
-            }
+        let channel = EmailCodeChannel::new(email.to_owned(), Arc::clone(&self.email_code_repository));
+        send(&channel, &self.email_limits, self.email_code_sender.as_ref()).await
+    }
 
-            if !self.phone_code_repository.delete(&dto).await? {
-                return Ok(CodeSendModel::Retry);
-            }
+    pub async fn attempt_email(&self, email: &str, code: i64) -> Result<CodeAttemptModel, Box<dyn Error>> {
+        let channel = EmailCodeChannel::new(email.to_owned(), Arc::clone(&self.email_code_repository));
+        attempt(&channel, &self.email_limits, code).await
+    }
+}
 
-            dto
+async fn send(
+    channel: &(dyn CodeChannel + Sync), 
+    limits: &CodeLimits, 
+    sender: &(dyn CodeSender + Sync + Send),
+) -> Result<CodeSendModel, Box<dyn Error>> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64;
+
+    let model_option = channel.find().await?;
+
+    // Created at of a resent code, the code itself was already delivered before
+    let mut resent_from = None;
+
+    let model = if let Some(mut model) = model_option {
+        let until = model.created_at + limits.timeout;
+        if until > now {
+            return Ok(CodeSendModel::Timeout(until));
         }
-        else {
-            PhoneCodeDto::new(
-                phone, 
-                self.max_phone, 
-                (self.expiration_phone / 1000) as i32,
-            )
-        };
-
-        dto.created_at = now;
-        
-        if !self.phone_code_repository.create(&dto).await? {
+
+        // Resent code keeps its attempts and expiration
+        if !channel.update_created_at(&model, now).await? {
             return Ok(CodeSendModel::Retry);
         }
 
-        // TODO: Send the code
+        resent_from = Some(model.created_at);
+        model.created_at = now;
+        model
+    }
+    else {
+        let model = CodeModel::new(limits.max, (limits.expiration / 1000) as i32, now);
 
-        let result = CodeSendModel::Success(
-            now + self.timeout_phone, 
-            now + self.expiration_phone,
-        );
+        if !channel.create(&model).await? {
+            return Ok(CodeSendModel::Retry);
+        }
 
-        Ok(result)
-    }
+        model
+    };
 
-    pub async fn attempt_phone(&self, phone: i64, code: i64) -> Result<CodeAttemptModel, Box<dyn Error>> {
-        let dto_option = self.phone_code_repository.find(phone).await?;
+    let delivery = sender
+        .send(&channel.destination(), model.code)
+        .await
+        .map_err(|error| error.to_string());
 
-        if dto_option.is_none() {
-            return Ok(CodeAttemptModel::Absent);
+    // Undelivered code must not hold the timeout, a failed resend keeps the code it repeated
+    if !matches!(delivery, Ok(CodeDeliveryState::Delivered)) {
+        match resent_from {
+            Some(created_at) => { channel.update_created_at(&model, created_at).await?; },
+            None => { channel.delete(&model).await?; },
         }
+    }
 
-        let mut dto = dto_option.unwrap();
+    match delivery? {
+        CodeDeliveryState::Delivered => (),
+        CodeDeliveryState::Rejected => return Ok(CodeSendModel::Fail),
+        CodeDeliveryState::Unavailable => return Ok(CodeSendModel::Retry),
+    }
 
-        if !self.phone_code_repository.delete(&dto).await? {
-            return Ok(CodeAttemptModel::Retry)
-        }
+    let result = CodeSendModel::Success(
+        now + limits.timeout, 
+        now + limits.expiration,
+    );
 
-        dto.attempts += 1;
+    Ok(result)
+}
 
-        if dto.attempts > self.attemtps_phone {
-            if !self.phone_code_repository.create(&dto).await? {
-                return Ok(CodeAttemptModel::Retry)
-            }
-            
-            return Ok(CodeAttemptModel::Fail(-1))
-        }
-        
-        if dto.code == code {
-            return Ok(CodeAttemptModel::Success);
-        }
+async fn attempt(channel: &(dyn CodeChannel + Sync), limits: &CodeLimits, code: i64) -> Result<CodeAttemptModel, Box<dyn Error>> {
+    let mut model = match channel.find().await? {
+        Some(model) => model,
+        None => return Ok(CodeAttemptModel::Absent),
+    };
 
-        if !self.phone_code_repository.create(&dto).await? {
-            return Ok(CodeAttemptModel::Retry)
-        }
+    if model.attempts >= limits.attempts {
+        return Ok(CodeAttemptModel::Fail(-1))
+    }
+
+    // Concurrent attempts read the same counter, only one of them may spend it
+    if !channel.update_attempts(&model, model.attempts + 1).await? {
+        return Ok(CodeAttemptModel::Retry)
+    }
+
+    model.attempts += 1;
 
-        Ok(CodeAttemptModel::Fail(self.attemtps_phone - dto.attempts))
+    if model.code != code {
+        return Ok(CodeAttemptModel::Fail(limits.attempts - model.attempts))
     }
+
+    // Code is single use, so only the attempt that removes it succeeds
+    if !channel.delete(&model).await? {
+        return Ok(CodeAttemptModel::Retry)
+    }
+
+    Ok(CodeAttemptModel::Success)
 }
\ No newline at end of file
@@ -1,9 +1,22 @@
 use serde::{Serialize, Deserialize};
 
+use crate::delivery::CodeSenderConfig;
+
+use super::super::rate_limits::RateLimitConfig;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CodesConfig {
-    pub attemtps_phone: i16,
+    #[serde(alias = "attemtps_phone")]
+    pub attempts_phone: i16,
     pub max_phone: i64,
     pub timeout_phone: i64,
     pub expiration_phone: i64,
+    #[serde(alias = "attemtps_email")]
+    pub attempts_email: i16,
+    pub max_email: i64,
+    pub timeout_email: i64,
+    pub expiration_email: i64,
+    pub sender_phone: CodeSenderConfig,
+    pub sender_email: CodeSenderConfig,
+    pub rate_limit: RateLimitConfig,
 }
\ No newline at end of file
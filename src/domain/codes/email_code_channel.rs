@@ -0,0 +1,65 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::email_codes::{EmailCodeRepository, EmailCodeDto};
+
+use super::{CodeChannel, CodeModel};
+
+pub struct EmailCodeChannel {
+    email: String,
+    email_code_repository: Arc<dyn EmailCodeRepository + Sync + Send>,
+}
+
+impl EmailCodeChannel {
+    pub fn new(email: String, email_code_repository: Arc<dyn EmailCodeRepository + Sync + Send>) -> Self {
+        Self {
+            email,
+            email_code_repository,
+        }
+    }
+
+    fn dto(&self, model: &CodeModel) -> EmailCodeDto {
+        EmailCodeDto {
+            email: self.email.clone(),
+            code: model.code,
+            created_at: model.created_at,
+            attempts: model.attempts,
+            ttl: model.ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl CodeChannel for EmailCodeChannel {
+    fn destination(&self) -> String {
+        self.email.clone()
+    }
+
+    async fn create(&self, model: &CodeModel) -> Result<bool, Box<dyn Error>> {
+        self.email_code_repository.create(&self.dto(model)).await
+    }
+
+    async fn delete(&self, model: &CodeModel) -> Result<bool, Box<dyn Error>> {
+        self.email_code_repository.delete(&self.dto(model)).await
+    }
+
+    async fn update_attempts(&self, model: &CodeModel, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        self.email_code_repository.update_attempts(&self.dto(model), attempts).await
+    }
+
+    async fn update_created_at(&self, model: &CodeModel, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        self.email_code_repository.update_created_at(&self.dto(model), created_at).await
+    }
+
+    async fn find(&self) -> Result<Option<CodeModel>, Box<dyn Error>> {
+        let dto_option = self.email_code_repository.find(&self.email).await?;
+
+        Ok(dto_option.map(|dto| CodeModel {
+            code: dto.code,
+            created_at: dto.created_at,
+            attempts: dto.attempts,
+            ttl: dto.ttl,
+        }))
+    }
+}
\ No newline at end of file
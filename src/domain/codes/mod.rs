@@ -2,8 +2,18 @@ mod code_send_model;
 mod code_attempt_model;
 mod code_service;
 mod codes_config;
+mod code_model;
+mod code_channel;
+mod phone_code_channel;
+mod email_code_channel;
+#[cfg(test)]
+mod tests;
 
 pub use code_send_model::CodeSendModel;
 pub use code_attempt_model::CodeAttemptModel;
 pub use code_service::CodeService;
-pub use codes_config::CodesConfig;
\ No newline at end of file
+pub use codes_config::CodesConfig;
+pub use code_model::CodeModel;
+pub use code_channel::CodeChannel;
+pub use phone_code_channel::PhoneCodeChannel;
+pub use email_code_channel::EmailCodeChannel;
\ No newline at end of file
@@ -0,0 +1,65 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::phone_codes::{PhoneCodeRepository, PhoneCodeDto};
+
+use super::{CodeChannel, CodeModel};
+
+pub struct PhoneCodeChannel {
+    phone: i64,
+    phone_code_repository: Arc<dyn PhoneCodeRepository + Sync + Send>,
+}
+
+impl PhoneCodeChannel {
+    pub fn new(phone: i64, phone_code_repository: Arc<dyn PhoneCodeRepository + Sync + Send>) -> Self {
+        Self {
+            phone,
+            phone_code_repository,
+        }
+    }
+
+    fn dto(&self, model: &CodeModel) -> PhoneCodeDto {
+        PhoneCodeDto {
+            phone: self.phone,
+            code: model.code,
+            created_at: model.created_at,
+            attempts: model.attempts,
+            ttl: model.ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl CodeChannel for PhoneCodeChannel {
+    fn destination(&self) -> String {
+        self.phone.to_string()
+    }
+
+    async fn create(&self, model: &CodeModel) -> Result<bool, Box<dyn Error>> {
+        self.phone_code_repository.create(&self.dto(model)).await
+    }
+
+    async fn delete(&self, model: &CodeModel) -> Result<bool, Box<dyn Error>> {
+        self.phone_code_repository.delete(&self.dto(model)).await
+    }
+
+    async fn update_attempts(&self, model: &CodeModel, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        self.phone_code_repository.update_attempts(&self.dto(model), attempts).await
+    }
+
+    async fn update_created_at(&self, model: &CodeModel, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        self.phone_code_repository.update_created_at(&self.dto(model), created_at).await
+    }
+
+    async fn find(&self) -> Result<Option<CodeModel>, Box<dyn Error>> {
+        let dto_option = self.phone_code_repository.find(self.phone).await?;
+
+        Ok(dto_option.map(|dto| CodeModel {
+            code: dto.code,
+            created_at: dto.created_at,
+            attempts: dto.attempts,
+            ttl: dto.ttl,
+        }))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,329 @@
+use std::{sync::{Arc, atomic::{AtomicUsize, Ordering}}, error::Error};
+
+use tokio::sync::Barrier;
+use tonic::async_trait;
+
+use crate::{
+    storage::{
+        MemoryContext,
+        phone_codes::{PhoneCodeRepository, PhoneCodeDto, MemoryPhoneCodeRepository},
+        email_codes::{EmailCodeRepository, EmailCodeDto, MemoryEmailCodeRepository},
+    },
+    delivery::{CodeSenderConfig, CodeSender, CodeDeliveryState, LogCodeSender},
+    domain::rate_limits::{RateLimitConfig, RateLimitBackendConfig, RateBucketConfig},
+    logging::Logger,
+};
+
+use super::{CodeService, CodesConfig, CodeAttemptModel, CodeSendModel};
+
+const PHONE: i64 = 79990001122;
+const EMAIL: &str = "user@example.com";
+const CODE: i64 = 1234;
+const ATTEMPTS: i16 = 3;
+const TTL: i32 = 300;
+
+// First finds are held at the barrier so racing callers all read the same row
+#[derive(Debug)]
+struct FindGate {
+    gated: AtomicUsize,
+    barrier: Barrier,
+}
+
+impl FindGate {
+    fn new(gated: usize) -> Self {
+        Self {
+            gated: AtomicUsize::new(gated),
+            barrier: Barrier::new(gated.max(1)),
+        }
+    }
+
+    async fn pass(&self) {
+        let held = self.gated
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |gated| gated.checked_sub(1))
+            .is_ok();
+        if held {
+            self.barrier.wait().await;
+        }
+    }
+}
+
+#[derive(Debug)]
+struct GatedPhoneCodeRepository {
+    gate: FindGate,
+    inner: Arc<MemoryPhoneCodeRepository>,
+}
+
+#[async_trait]
+impl PhoneCodeRepository for GatedPhoneCodeRepository {
+    async fn create(&self, dto: &PhoneCodeDto) -> Result<bool, Box<dyn Error>> {
+        self.inner.create(dto).await
+    }
+
+    async fn delete(&self, dto: &PhoneCodeDto) -> Result<bool, Box<dyn Error>> {
+        self.inner.delete(dto).await
+    }
+
+    async fn update_attempts(&self, dto: &PhoneCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        self.inner.update_attempts(dto, attempts).await
+    }
+
+    async fn update_created_at(&self, dto: &PhoneCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        self.inner.update_created_at(dto, created_at).await
+    }
+
+    async fn find(&self, phone: i64) -> Result<Option<PhoneCodeDto>, Box<dyn Error>> {
+        let result = self.inner.find(phone).await?;
+        self.gate.pass().await;
+        Ok(result)
+    }
+}
+
+#[derive(Debug)]
+struct GatedEmailCodeRepository {
+    gate: FindGate,
+    inner: Arc<MemoryEmailCodeRepository>,
+}
+
+#[async_trait]
+impl EmailCodeRepository for GatedEmailCodeRepository {
+    async fn create(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>> {
+        self.inner.create(dto).await
+    }
+
+    async fn delete(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>> {
+        self.inner.delete(dto).await
+    }
+
+    async fn update_attempts(&self, dto: &EmailCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        self.inner.update_attempts(dto, attempts).await
+    }
+
+    async fn update_created_at(&self, dto: &EmailCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        self.inner.update_created_at(dto, created_at).await
+    }
+
+    async fn find(&self, email: &str) -> Result<Option<EmailCodeDto>, Box<dyn Error>> {
+        let result = self.inner.find(email).await?;
+        self.gate.pass().await;
+        Ok(result)
+    }
+}
+
+fn config() -> CodesConfig {
+    let bucket = RateBucketConfig {
+        capacity: 0,
+        refill_interval: 0,
+    };
+
+    CodesConfig {
+        attempts_phone: ATTEMPTS,
+        max_phone: 10000,
+        timeout_phone: 60000,
+        expiration_phone: TTL as i64 * 1000,
+        attempts_email: ATTEMPTS,
+        max_email: 10000,
+        timeout_email: 60000,
+        expiration_email: TTL as i64 * 1000,
+        sender_phone: CodeSenderConfig::Log,
+        sender_email: CodeSenderConfig::Log,
+        rate_limit: RateLimitConfig {
+            backend: RateLimitBackendConfig::Memory,
+            ip: bucket.clone(),
+            phone: bucket.clone(),
+            email: bucket.clone(),
+            lookup: bucket,
+        },
+    }
+}
+
+fn phone_code(attempts: i16) -> PhoneCodeDto {
+    PhoneCodeDto {
+        phone: PHONE,
+        code: CODE,
+        created_at: 0,
+        attempts,
+        ttl: TTL,
+    }
+}
+
+// Sender whose provider is down, nothing reaches the destination
+#[derive(Debug)]
+struct UnavailableCodeSender;
+
+#[async_trait]
+impl CodeSender for UnavailableCodeSender {
+    async fn send(&self, _destination: &str, _code: i64) -> Result<CodeDeliveryState, Box<dyn Error>> {
+        Ok(CodeDeliveryState::Unavailable)
+    }
+}
+
+fn service(gated: usize) -> (CodeService, Arc<MemoryPhoneCodeRepository>, Arc<MemoryEmailCodeRepository>) {
+    service_with(gated, Arc::new(LogCodeSender::new(Arc::new(Logger::new()))))
+}
+
+fn service_with(
+    gated: usize,
+    phone_sender: Arc<dyn CodeSender + Sync + Send>,
+) -> (CodeService, Arc<MemoryPhoneCodeRepository>, Arc<MemoryEmailCodeRepository>) {
+    let memory_context = Arc::new(MemoryContext::new());
+    let phone_codes = Arc::new(MemoryPhoneCodeRepository::new(Arc::clone(&memory_context)));
+    let email_codes = Arc::new(MemoryEmailCodeRepository::new(memory_context));
+    let service = CodeService::new(
+        &config(),
+        Arc::new(GatedPhoneCodeRepository { gate: FindGate::new(gated), inner: Arc::clone(&phone_codes) }),
+        Arc::new(GatedEmailCodeRepository { gate: FindGate::new(gated), inner: Arc::clone(&email_codes) }),
+        phone_sender,
+        Arc::new(LogCodeSender::new(Arc::new(Logger::new()))),
+    );
+
+    (service, phone_codes, email_codes)
+}
+
+#[tokio::test]
+async fn interleaved_wrong_attempts_spend_one_attempt() {
+    let (service, phone_codes, _) = service(2);
+    phone_codes.create(&phone_code(0)).await.unwrap();
+
+    let (first, second) = tokio::join!(
+        service.attempt_phone(PHONE, CODE + 1),
+        service.attempt_phone(PHONE, CODE + 1),
+    );
+
+    let mut results = vec![first.unwrap(), second.unwrap()];
+    results.sort_by_key(|result| matches!(result, CodeAttemptModel::Retry));
+
+    assert_eq!(results, vec![CodeAttemptModel::Fail(ATTEMPTS - 1), CodeAttemptModel::Retry]);
+    assert_eq!(phone_codes.find(PHONE).await.unwrap().unwrap().attempts, 1);
+}
+
+#[tokio::test]
+async fn interleaved_attempts_can_not_exceed_limit() {
+    let (service, phone_codes, _) = service(ATTEMPTS as usize + 2);
+    phone_codes.create(&phone_code(0)).await.unwrap();
+
+    let attempts = (0..ATTEMPTS + 2).map(|_| service.attempt_phone(PHONE, CODE + 1));
+    let results = futures_util::future::join_all(attempts).await;
+
+    let failed = results
+        .into_iter()
+        .filter(|result| matches!(result, Ok(CodeAttemptModel::Fail(_))))
+        .count();
+
+    assert_eq!(failed, 1);
+    assert!(phone_codes.find(PHONE).await.unwrap().unwrap().attempts <= ATTEMPTS);
+}
+
+#[tokio::test]
+async fn interleaved_correct_attempts_sign_in_once() {
+    let (service, phone_codes, _) = service(2);
+    phone_codes.create(&phone_code(0)).await.unwrap();
+
+    let (first, second) = tokio::join!(
+        service.attempt_phone(PHONE, CODE),
+        service.attempt_phone(PHONE, CODE),
+    );
+
+    let mut results = vec![first.unwrap(), second.unwrap()];
+    results.sort_by_key(|result| matches!(result, CodeAttemptModel::Retry));
+
+    assert_eq!(results, vec![CodeAttemptModel::Success, CodeAttemptModel::Retry]);
+    assert_eq!(service.attempt_phone(PHONE, CODE).await.unwrap(), CodeAttemptModel::Absent);
+}
+
+#[tokio::test]
+async fn exhausted_attempts_reject_correct_code() {
+    let (service, phone_codes, _) = service(0);
+    phone_codes.create(&phone_code(0)).await.unwrap();
+
+    for left in (0..ATTEMPTS).rev() {
+        assert_eq!(service.attempt_phone(PHONE, CODE + 1).await.unwrap(), CodeAttemptModel::Fail(left));
+    }
+
+    assert_eq!(service.attempt_phone(PHONE, CODE).await.unwrap(), CodeAttemptModel::Fail(-1));
+    assert_eq!(phone_codes.find(PHONE).await.unwrap().unwrap().attempts, ATTEMPTS);
+}
+
+#[tokio::test]
+async fn attempt_keeps_remaining_ttl() {
+    let (service, phone_codes, _) = service(0);
+    phone_codes.create(&PhoneCodeDto { ttl: 17, ..phone_code(1) }).await.unwrap();
+
+    assert_eq!(service.attempt_phone(PHONE, CODE + 1).await.unwrap(), CodeAttemptModel::Fail(ATTEMPTS - 2));
+
+    let stored = phone_codes.find(PHONE).await.unwrap().unwrap();
+    assert_eq!(stored.ttl, 17);
+    assert_eq!(stored.attempts, 2);
+}
+
+#[tokio::test]
+async fn resend_keeps_code_and_attempts() {
+    let (service, phone_codes, _) = service(0);
+    phone_codes.create(&phone_code(2)).await.unwrap();
+
+    assert!(matches!(service.send_phone(PHONE).await.unwrap(), CodeSendModel::Success(_, _)));
+
+    let stored = phone_codes.find(PHONE).await.unwrap().unwrap();
+    assert_eq!(stored.code, CODE);
+    assert_eq!(stored.attempts, 2);
+    assert!(stored.created_at > 0);
+}
+
+#[tokio::test]
+async fn failed_resend_keeps_delivered_code() {
+    let (service, phone_codes, _) = service_with(0, Arc::new(UnavailableCodeSender));
+    phone_codes.create(&phone_code(2)).await.unwrap();
+
+    assert!(matches!(service.send_phone(PHONE).await.unwrap(), CodeSendModel::Retry));
+
+    let stored = phone_codes.find(PHONE).await.unwrap().unwrap();
+    assert_eq!(stored.code, CODE);
+    assert_eq!(stored.attempts, 2);
+    assert_eq!(stored.created_at, 0);
+}
+
+#[tokio::test]
+async fn failed_first_send_leaves_no_code() {
+    let (service, phone_codes, _) = service_with(0, Arc::new(UnavailableCodeSender));
+
+    assert!(matches!(service.send_phone(PHONE).await.unwrap(), CodeSendModel::Retry));
+    assert!(phone_codes.find(PHONE).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn interleaved_sends_create_one_code() {
+    let (service, phone_codes, _) = service(2);
+
+    let (first, second) = tokio::join!(
+        service.send_phone(PHONE),
+        service.send_phone(PHONE),
+    );
+
+    let results = [first.unwrap(), second.unwrap()];
+
+    assert_eq!(results.iter().filter(|result| matches!(result, CodeSendModel::Success(_, _))).count(), 1);
+    assert_eq!(results.iter().filter(|result| matches!(result, CodeSendModel::Retry)).count(), 1);
+    assert_eq!(phone_codes.find(PHONE).await.unwrap().unwrap().attempts, 0);
+}
+
+#[tokio::test]
+async fn interleaved_email_attempts_spend_one_attempt() {
+    let (service, _, email_codes) = service(2);
+    email_codes.create(&EmailCodeDto {
+        email: String::from(EMAIL),
+        code: CODE,
+        created_at: 0,
+        attempts: 0,
+        ttl: TTL,
+    }).await.unwrap();
+
+    let (first, second) = tokio::join!(
+        service.attempt_email(EMAIL, CODE + 1),
+        service.attempt_email(EMAIL, CODE + 1),
+    );
+
+    let mut results = vec![first.unwrap(), second.unwrap()];
+    results.sort_by_key(|result| matches!(result, CodeAttemptModel::Retry));
+
+    assert_eq!(results, vec![CodeAttemptModel::Fail(ATTEMPTS - 1), CodeAttemptModel::Retry]);
+    assert_eq!(email_codes.find(EMAIL).await.unwrap().unwrap().attempts, 1);
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+pub struct ContactMatchModel {
+    pub hash: Vec<u8>,
+    pub user_id: i64,
+}
\ No newline at end of file
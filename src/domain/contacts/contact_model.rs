@@ -0,0 +1,19 @@
+use crate::storage::user_contacts::UserContactDto;
+
+use super::ContactStateModel;
+
+pub struct ContactModel {
+    pub contact_id: i64,
+    pub state: ContactStateModel,
+    pub updated_at: i64,
+}
+
+impl From<UserContactDto> for ContactModel {
+    fn from(dto: UserContactDto) -> Self {
+        Self {
+            contact_id: dto.contact_id,
+            state: dto.state.into(),
+            updated_at: dto.updated_at,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub enum ContactSendModel {
+    Sent,
+    Accepted,
+    Exists,
+    Absent,
+    Retry,
+}
\ No newline at end of file
@@ -0,0 +1,246 @@
+use std::{sync::Arc, error::Error, time::{SystemTime, UNIX_EPOCH}, collections::HashMap};
+
+use hmac::{Hmac, Mac};
+use sha2::{Sha256, Digest};
+
+use crate::storage::{
+    user_contacts::{UserContactRepository, UserContactDto}, 
+    phone_hashes::{PhoneHashRepository, PhoneHashDto}, 
+    users::UserRepository,
+};
+
+use super::{ContactModel, ContactStateModel, ContactSendModel, ContactMatchModel, ContactsConfig};
+
+pub struct ContactService {
+    require_for_direct: bool,
+    require_for_group: bool,
+    hash_secret: String,
+    user_contact_repository: Arc<dyn UserContactRepository + Sync + Send>,
+    phone_hash_repository: Arc<dyn PhoneHashRepository + Sync + Send>,
+    user_repository: Arc<dyn UserRepository + Sync + Send>,
+}
+
+impl ContactService {
+    pub fn new(
+        config: &ContactsConfig,
+        user_contact_repository: Arc<dyn UserContactRepository + Sync + Send>,
+        phone_hash_repository: Arc<dyn PhoneHashRepository + Sync + Send>,
+        user_repository: Arc<dyn UserRepository + Sync + Send>,
+    ) -> Self {
+        Self {
+            require_for_direct: config.require_for_direct,
+            require_for_group: config.require_for_group,
+            hash_secret: config.hash_secret.clone(),
+            user_contact_repository,
+            phone_hash_repository,
+            user_repository,
+        }
+    }
+
+    pub async fn send(&self, user_id: i64, contact_id: i64) -> Result<ContactSendModel, Box<dyn Error>> {
+        if self.user_repository.find_id(contact_id).await?.is_none() {
+            return Ok(ContactSendModel::Absent);
+        }
+
+        let existing_option = self.user_contact_repository.find(user_id, contact_id).await?;
+
+        if let Some(existing) = existing_option {
+            // Request in the opposite direction is answered by sending one back
+            if ContactStateModel::from(existing.state) == ContactStateModel::Incoming {
+                return self.accept_state(user_id, contact_id, ContactStateModel::Incoming).await;
+            }
+
+            return Ok(ContactSendModel::Exists);
+        }
+
+        let now = now();
+
+        let outgoing = contact_dto(user_id, contact_id, ContactStateModel::Outgoing, now);
+        if !self.user_contact_repository.create(&outgoing).await? {
+            return Ok(ContactSendModel::Retry);
+        }
+
+        let incoming = contact_dto(contact_id, user_id, ContactStateModel::Incoming, now);
+        if self.user_contact_repository.create(&incoming).await? {
+            return Ok(ContactSendModel::Sent);
+        }
+
+        // Both users sent requests to each other at the same time
+        let opposite_option = self.user_contact_repository.find(contact_id, user_id).await?;
+
+        match opposite_option.map(|dto| ContactStateModel::from(dto.state)) {
+            Some(ContactStateModel::Outgoing) => self.accept_state(user_id, contact_id, ContactStateModel::Outgoing).await,
+            _ => Ok(ContactSendModel::Exists),
+        }
+    }
+
+    pub async fn accept(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        let result = self.accept_state(user_id, contact_id, ContactStateModel::Incoming).await?;
+        Ok(matches!(result, ContactSendModel::Accepted))
+    }
+
+    // Rejects incoming, cancels outgoing and removes accepted contacts alike
+    pub async fn remove(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        if !self.user_contact_repository.delete(user_id, contact_id).await? {
+            return Ok(false);
+        }
+
+        self.user_contact_repository.delete(contact_id, user_id).await?;
+
+        Ok(true)
+    }
+
+    pub async fn list(&self, user_id: i64, last_contact_id: i64, limit: i32) -> Result<Vec<ContactModel>, Box<dyn Error>> {
+        let contacts = self.user_contact_repository.list(user_id, last_contact_id, limit).await?;
+        Ok(contacts.into_iter().map(|dto| dto.into()).collect())
+    }
+
+    pub async fn is_accepted(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        let contact = self.user_contact_repository.find(user_id, contact_id).await?;
+        Ok(matches!(contact.map(|dto| ContactStateModel::from(dto.state)), Some(ContactStateModel::Accepted)))
+    }
+
+    pub async fn can_create_direct(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        if !self.require_for_direct {
+            return Ok(true);
+        }
+
+        self.is_accepted(user_id, contact_id).await
+    }
+
+    // Group members are added by the owner, so they must exist and have accepted the owner
+    pub async fn can_add_to_group(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        if self.user_repository.find_id(contact_id).await?.is_none() {
+            return Ok(false);
+        }
+
+        if !self.require_for_group {
+            return Ok(true);
+        }
+
+        self.is_accepted(user_id, contact_id).await
+    }
+
+    pub async fn register_phone(&self, user_id: i64, phone: i64) -> Result<(), Box<dyn Error>> {
+        self.phone_hash_repository.create(&PhoneHashDto {
+            hash: self.keyed_hash(&hash_phone(phone)),
+            user_id,
+        }).await
+    }
+
+    // Users who signed up before hashes were kept are registered page by page,
+    // returns how many were registered and the id to continue from while pages are full
+    pub async fn backfill_phone_hashes(&self, last_user_id: Option<i64>, limit: i32) -> Result<(i32, Option<i64>), Box<dyn Error>> {
+        let users = self.user_repository.scan(last_user_id, limit).await?;
+
+        let mut registered = 0;
+
+        // Email and service accounts have no phone to match
+        for dto in users.iter().filter(|dto| dto.phone != 0) {
+            self.register_phone(dto.id, dto.phone).await?;
+            registered += 1;
+        }
+
+        let next = if users.len() < limit as usize {
+            None
+        }
+        else {
+            users.last().map(|dto| dto.id)
+        };
+
+        Ok((registered, next))
+    }
+
+    pub async fn match_phones(&self, user_id: i64, hashes: &[Vec<u8>]) -> Result<Vec<ContactMatchModel>, Box<dyn Error>> {
+        let keyed: HashMap<Vec<u8>, &Vec<u8>> = hashes
+            .iter()
+            .map(|hash| (self.keyed_hash(hash), hash))
+            .collect();
+
+        let keys: Vec<Vec<u8>> = keyed.keys().cloned().collect();
+        let found = self.phone_hash_repository.list(&keys).await?;
+
+        let mut matches = Vec::new();
+
+        for dto in found {
+            if dto.user_id == user_id {
+                continue;
+            }
+
+            let hash = match keyed.get(&dto.hash) {
+                Some(hash) => (*hash).clone(),
+                None => continue,
+            };
+
+            // Hidden users are matched only by accepted contacts, a pending request proves nothing
+            let discoverable = match self.user_repository.find_id(dto.user_id).await? {
+                Some(user) => user.discoverable,
+                None => continue,
+            };
+
+            if discoverable || self.is_accepted(user_id, dto.user_id).await? {
+                matches.push(ContactMatchModel {
+                    hash,
+                    user_id: dto.user_id,
+                });
+            }
+        }
+
+        Ok(matches)
+    }
+
+    // Stored digests are keyed, so a leaked table can not be reversed by hashing every phone
+    fn keyed_hash(&self, hash: &[u8]) -> Vec<u8> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.hash_secret.as_bytes()).unwrap();
+        mac.update(hash);
+        mac.finalize().into_bytes().to_vec()
+    }
+
+    async fn accept_state(&self, user_id: i64, contact_id: i64, state: ContactStateModel) -> Result<ContactSendModel, Box<dyn Error>> {
+        let now = now();
+
+        let own = contact_dto(user_id, contact_id, state, now);
+        if !self.user_contact_repository.update_state(&own, ContactStateModel::Accepted.into()).await? {
+            return Ok(ContactSendModel::Retry);
+        }
+
+        // Opposite row is either the matching request or was lost on the way
+        let opposite_option = self.user_contact_repository.find(contact_id, user_id).await?;
+
+        match opposite_option {
+            Some(opposite) => {
+                let opposite = UserContactDto { updated_at: now, ..opposite };
+                self.user_contact_repository.update_state(&opposite, ContactStateModel::Accepted.into()).await?;
+            },
+            None => {
+                let opposite = contact_dto(contact_id, user_id, ContactStateModel::Accepted, now);
+                self.user_contact_repository.create(&opposite).await?;
+            },
+        }
+
+        Ok(ContactSendModel::Accepted)
+    }
+}
+
+// Clients hash numbers the same way, so uploaded books never carry raw phones
+fn hash_phone(phone: i64) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(phone.to_string().as_bytes());
+    hasher.finalize().to_vec()
+}
+
+fn contact_dto(user_id: i64, contact_id: i64, state: ContactStateModel, updated_at: i64) -> UserContactDto {
+    UserContactDto {
+        user_id,
+        contact_id,
+        state: state.into(),
+        updated_at,
+    }
+}
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as i64
+}
\ No newline at end of file
@@ -0,0 +1,29 @@
+#[derive(Clone, PartialEq)]
+pub enum ContactStateModel {
+    Invalid,
+    Outgoing,
+    Incoming,
+    Accepted,
+}
+
+impl From<i16> for ContactStateModel {
+    fn from(id: i16) -> Self {
+        match id {
+            1 => ContactStateModel::Outgoing,
+            2 => ContactStateModel::Incoming,
+            3 => ContactStateModel::Accepted,
+            _ => ContactStateModel::Invalid,
+        }
+    }
+}
+
+impl From<ContactStateModel> for i16 {
+    fn from(state: ContactStateModel) -> Self {
+        match state {
+            ContactStateModel::Invalid => 0,
+            ContactStateModel::Outgoing => 1,
+            ContactStateModel::Incoming => 2,
+            ContactStateModel::Accepted => 3,
+        }
+    }
+}
\ No newline at end of file
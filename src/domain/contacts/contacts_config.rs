@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContactsConfig {
+    pub require_for_direct: bool,
+    pub require_for_group: bool,
+    // Key of the stored phone digests, rotating it requires users to sign in again to be matched
+    pub hash_secret: String,
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+mod contact_state_model;
+mod contact_model;
+mod contact_send_model;
+mod contact_match_model;
+mod contact_service;
+mod contacts_config;
+
+pub use contact_state_model::ContactStateModel;
+pub use contact_model::ContactModel;
+pub use contact_send_model::ContactSendModel;
+pub use contact_match_model::ContactMatchModel;
+pub use contact_service::ContactService;
+pub use contacts_config::ContactsConfig;
\ No newline at end of file
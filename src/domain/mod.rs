@@ -4,6 +4,9 @@ pub mod tokens;
 pub mod registries;
 pub mod transactions;
 pub mod registry_users;
+pub mod api_keys;
+pub mod rate_limits;
+pub mod contacts;
 mod service_factory;
 mod services_config;
 
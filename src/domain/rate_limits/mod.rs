@@ -0,0 +1,7 @@
+mod rate_limit_model;
+mod rate_limit_service;
+mod rate_limit_config;
+
+pub use rate_limit_model::RateLimitModel;
+pub use rate_limit_service::RateLimitService;
+pub use rate_limit_config::{RateLimitConfig, RateLimitBackendConfig, RateBucketConfig};
\ No newline at end of file
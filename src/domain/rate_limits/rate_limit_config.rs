@@ -0,0 +1,27 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitBackendConfig {
+    // Buckets live per instance, so N instances together allow N times the capacity
+    Memory,
+    // Buckets are shared, but a take that keeps losing the write race to the same key is refused
+    Scylla,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RateBucketConfig {
+    // Burst size, bucket starts full
+    pub capacity: i64,
+    // Millis per refilled token, so the sustained rate is one take per interval
+    pub refill_interval: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    pub backend: RateLimitBackendConfig,
+    pub ip: RateBucketConfig,
+    pub phone: RateBucketConfig,
+    pub email: RateBucketConfig,
+    pub lookup: RateBucketConfig,
+}
\ No newline at end of file
@@ -0,0 +1,4 @@
+pub enum RateLimitModel {
+    Allowed,
+    Limited(i64),
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+use std::{sync::Arc, error::Error, time::{SystemTime, UNIX_EPOCH}};
+
+use crate::storage::rate_limits::RateLimitRepository;
+
+use super::{RateLimitModel, RateLimitConfig, RateBucketConfig};
+
+pub struct RateLimitService {
+    ip: RateBucketConfig,
+    phone: RateBucketConfig,
+    email: RateBucketConfig,
+    lookup: RateBucketConfig,
+    rate_limit_repository: Arc<dyn RateLimitRepository + Sync + Send>,
+}
+
+impl RateLimitService {
+    pub fn new(
+        config: &RateLimitConfig,
+        rate_limit_repository: Arc<dyn RateLimitRepository + Sync + Send>,
+    ) -> Self {
+        Self {
+            ip: config.ip.clone(),
+            phone: config.phone.clone(),
+            email: config.email.clone(),
+            lookup: config.lookup.clone(),
+            rate_limit_repository,
+        }
+    }
+
+    pub async fn take_ip(&self, action: &str, ip: &str) -> Result<RateLimitModel, Box<dyn Error>> {
+        self.take(&format!("{}:ip:{}", action, ip), &self.ip, 1).await
+    }
+
+    pub async fn take_phone(&self, action: &str, phone: i64) -> Result<RateLimitModel, Box<dyn Error>> {
+        self.take(&format!("{}:phone:{}", action, phone), &self.phone, 1).await
+    }
+
+    pub async fn take_email(&self, action: &str, email: &str) -> Result<RateLimitModel, Box<dyn Error>> {
+        self.take(&format!("{}:email:{}", action, email), &self.email, 1).await
+    }
+
+    pub async fn take_lookup(&self, action: &str, user_id: i64) -> Result<RateLimitModel, Box<dyn Error>> {
+        self.take(&format!("{}:user:{}", action, user_id), &self.lookup, 1).await
+    }
+
+    // Batch lookups are charged per item, a batch larger than the bucket drains it whole
+    pub async fn take_lookups(&self, action: &str, user_id: i64, count: i64) -> Result<RateLimitModel, Box<dyn Error>> {
+        self.take(&format!("{}:user:{}", action, user_id), &self.lookup, count.min(self.lookup.capacity)).await
+    }
+
+    async fn take(&self, key: &str, bucket: &RateBucketConfig, count: i64) -> Result<RateLimitModel, Box<dyn Error>> {
+        if bucket.capacity <= 0 || bucket.refill_interval <= 0 {
+            return Ok(RateLimitModel::Allowed);
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        let result = match self.rate_limit_repository.take(key, count, bucket.capacity, bucket.refill_interval, now).await? {
+            Some(retry_after) => RateLimitModel::Limited(retry_after),
+            None => RateLimitModel::Allowed,
+        };
+
+        Ok(result)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BalancePolicyConfig {
+    Unlimited,
+    NonNegative,
+    CreditLimit(String),
+}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, Zero, ParseBigDecimalError};
+
+use super::BalancePolicyConfig;
+
+#[derive(Debug, Clone)]
+pub enum BalancePolicyModel {
+    Invalid,
+    Unlimited,
+    NonNegative,
+    CreditLimit(BigDecimal),
+}
+
+impl BalancePolicyModel {
+    pub fn allows(&self, balance: &BigDecimal) -> bool {
+        match self {
+            BalancePolicyModel::Invalid => false,
+            BalancePolicyModel::Unlimited => true,
+            BalancePolicyModel::NonNegative => balance >= &BigDecimal::zero(),
+            BalancePolicyModel::CreditLimit(limit) => balance + limit >= BigDecimal::zero(),
+        }
+    }
+
+    // Whether every balance this policy allows is also allowed by the current one
+    pub fn tightens(&self, current: &BalancePolicyModel) -> bool {
+        match (self, current) {
+            (BalancePolicyModel::Invalid, _) | (_, BalancePolicyModel::Invalid) => false,
+            (_, BalancePolicyModel::Unlimited) => true,
+            _ => match (self.credit(), current.credit()) {
+                (Some(credit), Some(current_credit)) => credit <= current_credit,
+                _ => false,
+            },
+        }
+    }
+
+    pub fn variant(&self) -> i16 {
+        match self {
+            BalancePolicyModel::Invalid => 0,
+            BalancePolicyModel::Unlimited => 1,
+            BalancePolicyModel::NonNegative => 2,
+            BalancePolicyModel::CreditLimit(_) => 3,
+        }
+    }
+
+    fn credit(&self) -> Option<BigDecimal> {
+        match self {
+            BalancePolicyModel::NonNegative => Some(BigDecimal::zero()),
+            BalancePolicyModel::CreditLimit(limit) => Some(limit.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<(i16, Option<BigDecimal>)> for BalancePolicyModel {
+    fn from(row: (i16, Option<BigDecimal>)) -> Self {
+        match row {
+            (1, _) => BalancePolicyModel::Unlimited,
+            (2, _) => BalancePolicyModel::NonNegative,
+            (3, Some(limit)) => BalancePolicyModel::CreditLimit(limit),
+            _ => BalancePolicyModel::Invalid,
+        }
+    }
+}
+
+impl TryFrom<&BalancePolicyConfig> for BalancePolicyModel {
+    type Error = ParseBigDecimalError;
+
+    fn try_from(config: &BalancePolicyConfig) -> Result<Self, Self::Error> {
+        let result = match config {
+            BalancePolicyConfig::Unlimited => BalancePolicyModel::Unlimited,
+            BalancePolicyConfig::NonNegative => BalancePolicyModel::NonNegative,
+            BalancePolicyConfig::CreditLimit(limit) => BalancePolicyModel::CreditLimit(
+                BigDecimal::from_str(limit)?
+            ),
+        };
+
+        Ok(result)
+    }
+}
\ No newline at end of file
@@ -1,7 +1,15 @@
 mod registry_model;
 mod registry_service;
 mod registry_variant_model;
+mod registry_policy_model;
+mod balance_policy_model;
+mod balance_policy_config;
+mod registries_config;
 
 pub use registry_model::RegistryModel;
 pub use registry_variant_model::RegistryVariantModel;
-pub use registry_service::RegistryService;
\ No newline at end of file
+pub use registry_service::RegistryService;
+pub use registry_policy_model::RegistryPolicyModel;
+pub use balance_policy_model::BalancePolicyModel;
+pub use balance_policy_config::BalancePolicyConfig;
+pub use registries_config::RegistriesConfig;
\ No newline at end of file
@@ -0,0 +1,8 @@
+use serde::{Serialize, Deserialize};
+
+use super::BalancePolicyConfig;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistriesConfig {
+    pub default_balance_policy: BalancePolicyConfig,
+}
\ No newline at end of file
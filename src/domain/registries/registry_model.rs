@@ -16,12 +16,20 @@ pub struct RegistryModel {
 
 impl RegistryModel {
     pub fn direct(timestamp: i64, id: i64, name: String, image: String) -> Self {
+        Self::new(timestamp, id, RegistryVariantModel::Direct, name, image)
+    }
+
+    pub fn group(timestamp: i64, id: i64, name: String, image: String) -> Self {
+        Self::new(timestamp, id, RegistryVariantModel::Group, name, image)
+    }
+
+    fn new(timestamp: i64, id: i64, variant: RegistryVariantModel, name: String, image: String) -> Self {
         Self {
             id,
             created_at: timestamp,
             updated_at: timestamp,
             current_pack: 0,
-            variant: RegistryVariantModel::Direct,
+            variant,
             current_sequence: -1,
             name,
             image,
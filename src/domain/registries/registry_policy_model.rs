@@ -0,0 +1,39 @@
+use crate::storage::registry_policies::RegistryPolicyDto;
+
+use super::BalancePolicyModel;
+
+#[derive(Clone)]
+pub struct RegistryPolicyModel {
+    pub registry_id: i64,
+    pub currency: String,
+    pub updated_at: i64,
+    pub balance: BalancePolicyModel,
+}
+
+impl From<RegistryPolicyDto> for RegistryPolicyModel {
+    fn from(dto: RegistryPolicyDto) -> Self {
+        Self {
+            registry_id: dto.registry_id,
+            currency: dto.currency,
+            updated_at: dto.updated_at,
+            balance: (dto.variant, dto.credit_limit).into(),
+        }
+    }
+}
+
+impl From<RegistryPolicyModel> for RegistryPolicyDto {
+    fn from(model: RegistryPolicyModel) -> Self {
+        let variant = model.balance.variant();
+
+        Self {
+            registry_id: model.registry_id,
+            currency: model.currency,
+            updated_at: model.updated_at,
+            variant,
+            credit_limit: match model.balance {
+                BalancePolicyModel::CreditLimit(limit) => Some(limit),
+                _ => None,
+            },
+        }
+    }
+}
\ No newline at end of file
@@ -1,33 +1,50 @@
 use std::{sync::{Arc, Mutex}, error::Error, time::{SystemTime, UNIX_EPOCH}};
 
+use bigdecimal::Zero;
+
 use crate::storage::{
     registries::RegistryRepository, 
     id_generator::IdGenerator, 
     registry_users::{RegistryUserDto, RegistryUserRepository}, 
-    user_registries::UserRegistryRepository,
+    user_registries::UserRegistryRepository, 
+    registry_policies::RegistryPolicyRepository,
+    direct_registries::{DirectRegistryRepository, DirectRegistryDto},
 };
 
-use super::RegistryModel;
+use crate::domain::registry_users::{RegistryUserModel, RegistryUserRoleModel, MemberRemovalModel};
+
+use super::{RegistryModel, RegistryPolicyModel, BalancePolicyModel, RegistryVariantModel};
+
+const MAX_SHARED_SCAN: i32 = 1000;
 
 pub struct RegistryService {
     id_generator: Arc<Mutex<IdGenerator>>,
+    default_balance_policy: BalancePolicyModel,
     registry_repository: Arc<dyn RegistryRepository + Sync + Send>,
     registry_user_repository: Arc<dyn RegistryUserRepository + Sync + Send>,
     user_registry_repository: Arc<dyn UserRegistryRepository + Sync + Send>,
+    registry_policy_repository: Arc<dyn RegistryPolicyRepository + Sync + Send>,
+    direct_registry_repository: Arc<dyn DirectRegistryRepository + Sync + Send>,
 }
 
 impl RegistryService {
     pub fn new(
         id_generator: Arc<Mutex<IdGenerator>>,
+        default_balance_policy: BalancePolicyModel,
         registry_repository: Arc<dyn RegistryRepository + Sync + Send>,
         registry_user_repository: Arc<dyn RegistryUserRepository + Sync + Send>,
         user_registry_repository: Arc<dyn UserRegistryRepository + Sync + Send>,
+        registry_policy_repository: Arc<dyn RegistryPolicyRepository + Sync + Send>,
+        direct_registry_repository: Arc<dyn DirectRegistryRepository + Sync + Send>,
     ) -> Self {
         Self {
             id_generator,
+            default_balance_policy,
             registry_repository,
             user_registry_repository,
             registry_user_repository,
+            registry_policy_repository,
+            direct_registry_repository,
         }
     }
 
@@ -37,24 +54,94 @@ impl RegistryService {
         second_user_id: i64,
         name: String,
         image: String,
+    ) -> Result<Option<RegistryModel>, Box<dyn Error>> {
+        if former_user_id == second_user_id {
+            return Ok(None);
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        let registry_id = match self.claim_direct(former_user_id, second_user_id).await? {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        // Registry and members are written after the claim, so a repeated create
+        // also finishes whatever an interrupted one left behind
+        let existing_option = self.registry_repository.find(registry_id).await?;
+
+        let registry_option = match existing_option {
+            Some(dto) => Some(RegistryModel::from(dto)),
+            None => {
+                let registry = RegistryModel::direct(timestamp, registry_id, name, image);
+
+                if self.registry_repository.create(&registry.clone().into()).await? {
+                    Some(registry)
+                }
+                else {
+                    self.find(registry_id).await?
+                }
+            },
+        };
+
+        let registry = match registry_option {
+            Some(registry) => registry,
+            None => return Ok(None),
+        };
+
+        let existing = self.registry_user_repository.list(registry.id, &[former_user_id, second_user_id]).await?;
+
+        let missing: Vec<RegistryUserDto> = [former_user_id, second_user_id]
+            .into_iter()
+            .filter(|&user_id| existing.iter().all(|dto| dto.user_id != user_id))
+            .map(|user_id| RegistryUserDto {
+                current_pack: registry.current_pack,
+                current_sequence: registry.current_sequence,
+                joined_pack: registry.current_pack,
+                joined_sequence: registry.current_sequence,
+                ..RegistryUserDto::new(timestamp, registry.id, user_id, RegistryUserRoleModel::Member.into())
+            })
+            .collect();
+
+        if !missing.is_empty() && !self.registry_user_repository.create(&missing).await? {
+            return Ok(None)
+        }
+
+        Ok(Some(registry))
+    }
+
+    pub async fn create_group(
+        &self, 
+        owner_user_id: i64, 
+        member_user_ids: &[i64],
+        name: String,
+        image: String,
     ) -> Result<Option<RegistryModel>, Box<dyn Error>> {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
             .as_millis() as i64;
 
-        let registry = RegistryModel::direct(
+        let registry = RegistryModel::group(
             timestamp,
             self.id_generator.lock().unwrap().create(), 
             name, 
             image,
         );
 
-        let registry_users = [
-            RegistryUserDto::new(timestamp, registry.id, former_user_id),
-            RegistryUserDto::new(timestamp, registry.id, second_user_id),
+        let mut registry_users = vec![
+            RegistryUserDto::new(timestamp, registry.id, owner_user_id, RegistryUserRoleModel::Owner.into()),
         ];
 
+        for &user_id in member_user_ids {
+            if registry_users.iter().all(|dto| dto.user_id != user_id) {
+                registry_users.push(RegistryUserDto::new(timestamp, registry.id, user_id, RegistryUserRoleModel::Member.into()));
+            }
+        }
+
         if !self.registry_repository.create(&registry.clone().into()).await? {
             return Ok(None)
         }
@@ -66,11 +153,75 @@ impl RegistryService {
         Ok(Some(registry))
     }
 
+    pub async fn add_member(&self, registry: &RegistryModel, user_id: i64) -> Result<Option<RegistryUserModel>, Box<dyn Error>> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        // New member must not be settled against transactions that precede joining
+        let registry_user = RegistryUserDto {
+            current_pack: registry.current_pack,
+            current_sequence: registry.current_sequence,
+            joined_pack: registry.current_pack,
+            joined_sequence: registry.current_sequence,
+            ..RegistryUserDto::new(timestamp, registry.id, user_id, RegistryUserRoleModel::Member.into())
+        };
+
+        if !self.registry_user_repository.create(&[registry_user]).await? {
+            return Ok(None);
+        }
+
+        let member = self.find_member(registry.id, user_id).await?;
+        Ok(member)
+    }
+
+    pub async fn remove_member(&self, registry_id: i64, user_id: i64) -> Result<MemberRemovalModel, Box<dyn Error>> {
+        let registry_user = match self.registry_user_repository.list(registry_id, &[user_id]).await?.pop() {
+            Some(dto) => dto,
+            None => return Ok(MemberRemovalModel::NotMember),
+        };
+
+        if registry_user.balance.values().any(|value| !value.is_zero()) {
+            return Ok(MemberRemovalModel::OutstandingBalance);
+        }
+
+        if !self.registry_user_repository.delete(&registry_user).await? {
+            return Ok(MemberRemovalModel::Conflict);
+        }
+
+        Ok(MemberRemovalModel::Removed)
+    }
+
+    pub async fn update_role(&self, registry_id: i64, user_id: i64, role: RegistryUserRoleModel) -> Result<bool, Box<dyn Error>> {
+        let registry_user = match self.registry_user_repository.list(registry_id, &[user_id]).await?.pop() {
+            Some(dto) => dto,
+            None => return Ok(false),
+        };
+
+        self.registry_user_repository.update_role(&registry_user, role.into()).await
+    }
+
+    pub async fn find_member(&self, registry_id: i64, user_id: i64) -> Result<Option<RegistryUserModel>, Box<dyn Error>> {
+        let registry_user = self.registry_user_repository.list(registry_id, &[user_id]).await?.pop();
+        Ok(registry_user.map(|dto| dto.into()))
+    }
+
+    pub async fn list_members(&self, registry_id: i64) -> Result<Vec<RegistryUserModel>, Box<dyn Error>> {
+        let registry_users = self.registry_user_repository.list_all(registry_id).await?;
+        Ok(registry_users.into_iter().map(|dto| dto.into()).collect())
+    }
+
     pub async fn access(&self, registry_id: i64, user_ids: &[i64]) -> Result<bool, Box<dyn Error>> {
         let count = self.registry_user_repository.count(registry_id, user_ids).await?;
         Ok(count == user_ids.len() as i64)
     }
 
+    pub async fn shares_registry(&self, user_id: i64, other_user_id: i64) -> Result<bool, Box<dyn Error>> {
+        let shared = self.list_shared(user_id, other_user_id).await?;
+        Ok(!shared.is_empty())
+    }
+
     pub async fn find(&self, id: i64) -> Result<Option<RegistryModel>, Box<dyn Error>> {
         let registry = self.registry_repository.find(id).await?;
         Ok(registry.map(|dto| dto.into()))
@@ -94,4 +245,90 @@ impl RegistryService {
 
         Ok(registries.into_iter().map(|dto| dto.into()).collect())
     }
+
+    pub async fn set_policy(
+        &self,
+        registry_id: i64,
+        currency: String,
+        balance: BalancePolicyModel,
+    ) -> Result<RegistryPolicyModel, Box<dyn Error>> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        let policy = RegistryPolicyModel {
+            registry_id,
+            currency,
+            updated_at: timestamp,
+            balance,
+        };
+
+        self.registry_policy_repository.update(&policy.clone().into()).await?;
+
+        Ok(policy)
+    }
+
+    pub async fn find_policy(&self, registry_id: i64, currency: String) -> Result<RegistryPolicyModel, Box<dyn Error>> {
+        let dto_option = self.registry_policy_repository.find(registry_id, &currency).await?;
+
+        let policy = match dto_option {
+            Some(dto) => dto.into(),
+            None => RegistryPolicyModel {
+                registry_id,
+                currency,
+                updated_at: 0,
+                balance: self.default_balance_policy.clone(),
+            },
+        };
+
+        Ok(policy)
+    }
+
+    async fn claim_direct(&self, user_id: i64, other_user_id: i64) -> Result<Option<i64>, Box<dyn Error>> {
+        let low_user_id = user_id.min(other_user_id);
+        let high_user_id = user_id.max(other_user_id);
+
+        if let Some(dto) = self.direct_registry_repository.find(low_user_id, high_user_id).await? {
+            return Ok(Some(dto.registry_id));
+        }
+
+        // Registries created before pairs were claimed are adopted instead of duplicated
+        let shared = self.list_shared(user_id, other_user_id).await?;
+        let legacy = self.registry_repository
+            .list(&shared)
+            .await?
+            .into_iter()
+            .filter(|dto| RegistryVariantModel::from(dto.variant) == RegistryVariantModel::Direct)
+            .map(|dto| dto.id)
+            .min();
+
+        let registry_id = legacy.unwrap_or_else(|| self.id_generator.lock().unwrap().create());
+        let claim = DirectRegistryDto::new(user_id, other_user_id, registry_id);
+
+        if self.direct_registry_repository.create(&claim).await? {
+            return Ok(Some(registry_id));
+        }
+
+        let existing = self.direct_registry_repository.find(low_user_id, high_user_id).await?;
+        Ok(existing.map(|dto| dto.registry_id))
+    }
+
+    // Only the most recently updated registries of both users are compared
+    async fn list_shared(&self, user_id: i64, other_user_id: i64) -> Result<Vec<i64>, Box<dyn Error>> {
+        let user_registries = self.user_registry_repository.list(user_id, i64::MAX, MAX_SHARED_SCAN).await?;
+        if user_registries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let other_registries = self.user_registry_repository.list(other_user_id, i64::MAX, MAX_SHARED_SCAN).await?;
+
+        let shared = other_registries
+            .into_iter()
+            .filter(|other| user_registries.iter().any(|dto| dto.registry_id == other.registry_id))
+            .map(|dto| dto.registry_id)
+            .collect();
+
+        Ok(shared)
+    }
 }
\ No newline at end of file
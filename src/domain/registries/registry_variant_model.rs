@@ -1,13 +1,15 @@
-#[derive(Clone)]
+#[derive(Clone, PartialEq)]
 pub enum RegistryVariantModel {
     Invalid,
     Direct,
+    Group,
 }
 
 impl From<i16> for RegistryVariantModel {
     fn from(id: i16) -> Self {
         match id {
             1 => RegistryVariantModel::Direct,
+            2 => RegistryVariantModel::Group,
             _ => RegistryVariantModel::Invalid,
         }
     }
@@ -18,6 +20,7 @@ impl From<RegistryVariantModel> for i16 {
         match variant {
             RegistryVariantModel::Invalid => 0,
             RegistryVariantModel::Direct => 1,
+            RegistryVariantModel::Group => 2,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,6 @@
+pub enum MemberRemovalModel {
+    Removed,
+    NotMember,
+    OutstandingBalance,
+    Conflict,
+}
\ No newline at end of file
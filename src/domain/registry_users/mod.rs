@@ -1,5 +1,9 @@
 mod registry_user_model;
+mod registry_user_role_model;
 mod registry_user_service;
+mod member_removal_model;
 
 pub use registry_user_model::RegistryUserModel;
-pub use registry_user_service::RegistryUserService;
\ No newline at end of file
+pub use registry_user_role_model::RegistryUserRoleModel;
+pub use registry_user_service::RegistryUserService;
+pub use member_removal_model::MemberRemovalModel;
\ No newline at end of file
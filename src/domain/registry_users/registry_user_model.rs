@@ -4,12 +4,15 @@ use bigdecimal::BigDecimal;
 
 use crate::storage::registry_users::RegistryUserDto;
 
+use super::RegistryUserRoleModel;
+
 pub struct RegistryUserModel {
     pub registry_id: i64,
     pub user_id: i64,
     pub updated_at: i64,
     pub current_pack: i64,
     pub current_sequence: i16,
+    pub role: RegistryUserRoleModel,
     pub balance: HashMap<String, BigDecimal>,
 }
 
@@ -21,6 +24,7 @@ impl From<RegistryUserDto> for RegistryUserModel {
             updated_at: dto.updated_at,
             current_pack: dto.current_pack,
             current_sequence: dto.current_sequence,
+            role: dto.role.into(),
             balance: dto.balance,
         }
     }
@@ -0,0 +1,29 @@
+#[derive(Clone, PartialEq)]
+pub enum RegistryUserRoleModel {
+    Invalid,
+    Owner,
+    Member,
+    Leaving,
+}
+
+impl From<i16> for RegistryUserRoleModel {
+    fn from(id: i16) -> Self {
+        match id {
+            1 => RegistryUserRoleModel::Owner,
+            2 => RegistryUserRoleModel::Member,
+            3 => RegistryUserRoleModel::Leaving,
+            _ => RegistryUserRoleModel::Invalid,
+        }
+    }
+}
+
+impl From<RegistryUserRoleModel> for i16 {
+    fn from(role: RegistryUserRoleModel) -> Self {
+        match role {
+            RegistryUserRoleModel::Invalid => 0,
+            RegistryUserRoleModel::Owner => 1,
+            RegistryUserRoleModel::Member => 2,
+            RegistryUserRoleModel::Leaving => 3,
+        }
+    }
+}
\ No newline at end of file
@@ -2,25 +2,36 @@ use std::{sync::{Arc, Mutex}, error::Error};
 
 use uuid::Uuid;
 
-use crate::storage::{RepositoryFactory, id_generator::IdGenerator};
+use crate::{storage::{RepositoryFactory, id_generator::IdGenerator, rate_limits::{RateLimitRepository, MemoryRateLimitRepository}}, delivery::CodeSenderFactory, logging::Logger};
 
-use super::{codes::CodeService, users::UserService, tokens::{TokenService, TokensState}, ServicesConfig, registries::RegistryService, registry_users::RegistryUserService, transactions::TransactionService};
+use super::{codes::CodeService, users::UserService, tokens::{TokenService, TokensState}, ServicesConfig, registries::{RegistryService, BalancePolicyModel}, registry_users::RegistryUserService, transactions::{TransactionService, TransactionRepositories}, api_keys::ApiKeyService, rate_limits::{RateLimitService, RateLimitBackendConfig}, contacts::ContactService};
 
 #[derive(Debug)]
 pub struct ServiceFactory {
     config: ServicesConfig,
     id_generator: Arc<Mutex<IdGenerator>>,
     tokens_state: Arc<TokensState>,
+    default_balance_policy: BalancePolicyModel,
     repository_factory: RepositoryFactory,
+    code_sender_factory: CodeSenderFactory,
+    rate_limit_repository: Arc<dyn RateLimitRepository + Sync + Send>,
+    logger: Arc<Logger>,
 }
 
 impl ServiceFactory {
     pub fn new(
         config: ServicesConfig,
-        repository_factory: RepositoryFactory
+        repository_factory: RepositoryFactory,
+        logger: &Arc<Logger>,
     ) -> Result<Self, Box<dyn Error>> {
         let instance_id = Uuid::new_v4();
 
+        // Memory buckets are per instance, so they must outlive the services
+        let rate_limit_repository: Arc<dyn RateLimitRepository + Sync + Send> = match config.codes.rate_limit.backend {
+            RateLimitBackendConfig::Memory => Arc::new(MemoryRateLimitRepository::new()),
+            RateLimitBackendConfig::Scylla => repository_factory.rate_limit(),
+        };
+
         let result = Self {
             id_generator: Arc::new(
                 Mutex::new(
@@ -30,7 +41,11 @@ impl ServiceFactory {
                 )
             ),
             tokens_state: Arc::new(TokensState::new(instance_id, &config.tokens)?),
+            default_balance_policy: BalancePolicyModel::try_from(&config.registries.default_balance_policy)?,
             repository_factory: repository_factory,
+            rate_limit_repository,
+            code_sender_factory: CodeSenderFactory::new(&config.codes.sender_phone, &config.codes.sender_email, logger)?,
+            logger: Arc::clone(logger),
             config,
         };
 
@@ -41,6 +56,9 @@ impl ServiceFactory {
         CodeService::new(
             &self.config.codes,
             self.repository_factory.phone_code(),
+            self.repository_factory.email_code(),
+            self.code_sender_factory.phone(),
+            self.code_sender_factory.email(),
         )
     }
 
@@ -48,22 +66,28 @@ impl ServiceFactory {
         UserService::new(
             Arc::clone(&self.id_generator),
             self.repository_factory.user(),
+            self.repository_factory.user_login(),
+            self.repository_factory.user_email(),
         )  
     }
 
     pub fn token(&self) -> TokenService {
         TokenService::new(
             Arc::clone(&self.tokens_state),
-            self.repository_factory.user_token(),    
+            self.repository_factory.user_token(),
+            self.repository_factory.user_revocation(),
         )  
     }
 
     pub fn registry(&self) -> RegistryService {
         RegistryService::new(
             Arc::clone(&self.id_generator),
+            self.default_balance_policy.clone(),
             self.repository_factory.registry(),    
             self.repository_factory.registry_user(),    
             self.repository_factory.user_registry(),    
+            self.repository_factory.registry_policy(),    
+            self.repository_factory.direct_registry(),
         )  
     }
 
@@ -75,9 +99,39 @@ impl ServiceFactory {
 
     pub fn transaction(&self) -> TransactionService {
         TransactionService::new(
-            self.repository_factory.transaction(),
-            self.repository_factory.registry(),
-            self.repository_factory.registry_user(),
+            self.default_balance_policy.clone(),
+            TransactionRepositories {
+                transaction: self.repository_factory.transaction(),
+                registry: self.repository_factory.registry(),
+                registry_user: self.repository_factory.registry_user(),
+                registry_policy: self.repository_factory.registry_policy(),
+                user: self.repository_factory.user(),
+                user_registry: self.repository_factory.user_registry(),
+                pending_registry: self.repository_factory.pending_registry(),
+            },
+            Arc::clone(&self.logger),
+        )
+    }
+
+    pub fn api_key(&self) -> ApiKeyService {
+        ApiKeyService::new(
+            self.repository_factory.api_key(),
+        )
+    }
+
+    pub fn rate_limit(&self) -> RateLimitService {
+        RateLimitService::new(
+            &self.config.codes.rate_limit,
+            Arc::clone(&self.rate_limit_repository),
+        )
+    }
+
+    pub fn contact(&self) -> ContactService {
+        ContactService::new(
+            &self.config.contacts,
+            self.repository_factory.user_contact(),
+            self.repository_factory.phone_hash(),
+            self.repository_factory.user(),
         )
     }
 }
\ No newline at end of file
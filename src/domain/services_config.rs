@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-use super::{tokens::TokensConfig, codes::CodesConfig};
+use super::{tokens::TokensConfig, codes::CodesConfig, registries::RegistriesConfig, contacts::ContactsConfig};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ServicesConfig {
     pub codes: CodesConfig,
     pub tokens: TokensConfig,
+    pub registries: RegistriesConfig,
+    pub contacts: ContactsConfig,
 }
\ No newline at end of file
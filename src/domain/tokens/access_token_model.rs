@@ -10,4 +10,6 @@ pub struct AccessTokenModel {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub aud: Option<String>,            // Optional. Audience
+    #[serde(default)]
+    pub scopes: Vec<String>,
 }
\ No newline at end of file
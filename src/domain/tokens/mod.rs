@@ -1,9 +1,23 @@
+pub mod scopes;
 mod token_service;
 mod tokens_config;
 mod tokens_state;
+mod token_key_config;
+mod token_keys;
+mod public_key_model;
 mod access_token_model;
+mod refresh_state_model;
+mod session_device_model;
+mod session_model;
+mod principal_model;
 
 pub use token_service::TokenService;
 pub use tokens_config::TokensConfig;
 pub use access_token_model::AccessTokenModel;
-pub use tokens_state::TokensState;
\ No newline at end of file
+pub use refresh_state_model::RefreshStateModel;
+pub use session_device_model::SessionDeviceModel;
+pub use session_model::SessionModel;
+pub use principal_model::PrincipalModel;
+pub use tokens_state::TokensState;
+pub use token_key_config::TokenKeyConfig;
+pub use public_key_model::PublicKeyModel;
\ No newline at end of file
@@ -0,0 +1,32 @@
+use crate::domain::api_keys::ApiKeyModel;
+
+use super::AccessTokenModel;
+
+pub struct PrincipalModel {
+    pub user_id: i64,
+    pub scopes: Vec<String>,
+}
+
+impl PrincipalModel {
+    pub fn allows(&self, scopes: &[&str]) -> bool {
+        scopes.iter().all(|scope| self.scopes.iter().any(|granted| granted == scope))
+    }
+}
+
+impl From<AccessTokenModel> for PrincipalModel {
+    fn from(model: AccessTokenModel) -> Self {
+        Self {
+            user_id: model.sub,
+            scopes: model.scopes,
+        }
+    }
+}
+
+impl From<ApiKeyModel> for PrincipalModel {
+    fn from(model: ApiKeyModel) -> Self {
+        Self {
+            user_id: model.user_id,
+            scopes: model.scopes,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use std::error::Error;
+
+// Uncompressed EC point of P-384: 0x04 prefix, then x and y
+const POINT_LENGTH: usize = 97;
+const COORDINATE_LENGTH: usize = 48;
+
+// id-ecPublicKey and secp384r1
+const EC_PUBLIC_KEY_OID: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+const SECP384R1_OID: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x22];
+
+const DER_SEQUENCE: u8 = 0x30;
+const DER_OBJECT_IDENTIFIER: u8 = 0x06;
+const DER_BIT_STRING: u8 = 0x03;
+
+#[derive(Clone)]
+pub struct PublicKeyModel {
+    pub kid: String,
+    pub x: String,
+    pub y: String,
+}
+
+impl PublicKeyModel {
+    pub fn from_pem(kid: &str, pem: &str) -> Result<Self, Box<dyn Error>> {
+        let body: String = pem
+            .lines()
+            .filter(|line| !line.starts_with("-----"))
+            .collect::<String>()
+            .trim()
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_");
+
+        let der = base64_url::decode(&body)?;
+
+        // SubjectPublicKeyInfo ::= SEQUENCE { algorithm SEQUENCE { OID, OID }, subjectPublicKey BIT STRING }
+        let mut spki = read_last(&der, DER_SEQUENCE)?;
+        let mut algorithm = read_element(&mut spki, DER_SEQUENCE)?;
+        let algorithm_oid = read_element(&mut algorithm, DER_OBJECT_IDENTIFIER)?;
+        let curve_oid = read_last(algorithm, DER_OBJECT_IDENTIFIER)?;
+
+        if algorithm_oid != EC_PUBLIC_KEY_OID || curve_oid != SECP384R1_OID {
+            return Err("Public key must be a P-384 EC key".into());
+        }
+
+        // Bit string content starts with the count of unused bits
+        let bits = read_last(spki, DER_BIT_STRING)?;
+        if bits.len() != POINT_LENGTH + 1 || bits[0] != 0 || bits[1] != 0x04 {
+            return Err("Public key must be an uncompressed P-384 point".into());
+        }
+
+        let x = &bits[2..2 + COORDINATE_LENGTH];
+        let y = &bits[2 + COORDINATE_LENGTH..];
+
+        Ok(Self {
+            kid: kid.to_owned(),
+            x: base64_url::encode(x),
+            y: base64_url::encode(y),
+        })
+    }
+}
+
+// Takes one DER element with the expected tag off the input and returns its content
+fn read_element<'a>(input: &mut &'a [u8], tag: u8) -> Result<&'a [u8], Box<dyn Error>> {
+    if input.len() < 2 || input[0] != tag {
+        return Err("Unexpected DER element in public key".into());
+    }
+
+    let (length, offset) = match input[1] {
+        length if length < 0x80 => (length as usize, 2),
+        0x81 if input.len() > 2 => (input[2] as usize, 3),
+        0x82 if input.len() > 3 => (((input[2] as usize) << 8) | input[3] as usize, 4),
+        _ => return Err("Unsupported DER length in public key".into()),
+    };
+
+    if input.len() < offset + length {
+        return Err("Truncated DER element in public key".into());
+    }
+
+    let content = &input[offset..offset + length];
+    *input = &input[offset + length..];
+
+    Ok(content)
+}
+
+fn read_last(mut input: &[u8], tag: u8) -> Result<&[u8], Box<dyn Error>> {
+    let content = read_element(&mut input, tag)?;
+
+    if !input.is_empty() {
+        return Err("Trailing data in public key".into());
+    }
+
+    Ok(content)
+}
\ No newline at end of file
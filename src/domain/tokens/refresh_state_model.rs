@@ -0,0 +1,6 @@
+pub enum RefreshStateModel {
+    Valid(i64),
+    Rotated(i64, String, i64),
+    Absent,
+    Reused,
+}
\ No newline at end of file
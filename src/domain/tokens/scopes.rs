@@ -0,0 +1,34 @@
+pub const REGISTRIES_READ: &str = "registries:read";
+pub const REGISTRIES_WRITE: &str = "registries:write";
+pub const TRANSACTIONS_READ: &str = "transactions:read";
+pub const TRANSACTIONS_SEND: &str = "transactions:send";
+pub const SESSIONS_READ: &str = "sessions:read";
+pub const SESSIONS_WRITE: &str = "sessions:write";
+pub const PROFILE_WRITE: &str = "profile:write";
+pub const USERS_READ: &str = "users:read";
+pub const CONTACTS_READ: &str = "contacts:read";
+pub const CONTACTS_WRITE: &str = "contacts:write";
+
+pub const ALL: [&str; 10] = [
+    REGISTRIES_READ,
+    REGISTRIES_WRITE,
+    TRANSACTIONS_READ,
+    TRANSACTIONS_SEND,
+    SESSIONS_READ,
+    SESSIONS_WRITE,
+    PROFILE_WRITE,
+    USERS_READ,
+    CONTACTS_READ,
+    CONTACTS_WRITE,
+];
+
+// Granted only to configured admin users, never to plain sign-in
+pub const API_KEYS_WRITE: &str = "api_keys:write";
+pub const BALANCES_REBUILD: &str = "balances:rebuild";
+pub const CONTACTS_BACKFILL: &str = "contacts:backfill";
+
+pub const ADMIN: [&str; 3] = [
+    API_KEYS_WRITE,
+    BALANCES_REBUILD,
+    CONTACTS_BACKFILL,
+];
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub struct SessionDeviceModel {
+    pub device_name: String,
+    pub user_agent: String,
+    pub ip: String,
+}
\ No newline at end of file
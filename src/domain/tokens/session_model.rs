@@ -0,0 +1,23 @@
+use crate::storage::user_tokens::UserTokenDto;
+
+pub struct SessionModel {
+    pub id: String,
+    pub device_name: String,
+    pub user_agent: String,
+    pub ip: String,
+    pub created_at: i64,
+    pub last_used_at: i64,
+}
+
+impl From<UserTokenDto> for SessionModel {
+    fn from(dto: UserTokenDto) -> Self {
+        Self {
+            id: dto.family,
+            device_name: dto.device_name,
+            user_agent: dto.user_agent,
+            ip: dto.ip,
+            created_at: dto.created_at,
+            last_used_at: dto.last_used_at,
+        }
+    }
+}
\ No newline at end of file
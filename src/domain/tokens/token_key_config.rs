@@ -0,0 +1,8 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenKeyConfig {
+    pub kid: String,
+    pub private_key_path: Option<String>,
+    pub public_key_path: String,
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use std::{error::Error, collections::HashMap, fs, time::SystemTime, fmt};
+
+use jsonwebtoken::{EncodingKey, DecodingKey};
+
+use super::{TokenKeyConfig, PublicKeyModel};
+
+pub struct TokenKeys {
+    pub active_kid: String,
+    pub encoding: EncodingKey,
+    pub decoding: HashMap<String, DecodingKey>,
+    pub public: Vec<PublicKeyModel>,
+    pub modified: Vec<Option<SystemTime>>,
+}
+
+impl TokenKeys {
+    pub fn load(configs: &[TokenKeyConfig], active_kid: &str) -> Result<Self, Box<dyn Error>> {
+        let modified = modified_times(configs);
+
+        let mut encoding = None;
+        let mut decoding = HashMap::new();
+        let mut public = Vec::new();
+
+        for config in configs {
+            let public_pem = fs::read_to_string(&config.public_key_path)?;
+
+            decoding.insert(config.kid.clone(), DecodingKey::from_ec_pem(public_pem.as_bytes())?);
+            public.push(PublicKeyModel::from_pem(&config.kid, &public_pem)?);
+
+            if config.kid == active_kid {
+                let private_key_path = config.private_key_path
+                    .as_ref()
+                    .ok_or("Active token key must have a private key")?;
+
+                let private_pem = fs::read_to_string(private_key_path)?;
+                encoding = Some(EncodingKey::from_ec_pem(private_pem.as_bytes())?);
+            }
+        }
+
+        Ok(Self {
+            active_kid: active_kid.to_owned(),
+            encoding: encoding.ok_or("Active token key is not configured")?,
+            decoding,
+            public,
+            modified,
+        })
+    }
+
+    pub fn find_decoding(&self, kid: Option<&str>) -> Option<&DecodingKey> {
+        // Tokens issued before key sets carry no kid
+        self.decoding.get(kid.unwrap_or(&self.active_kid))
+    }
+}
+
+impl fmt::Debug for TokenKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenKeys")
+            .field("active_kid", &self.active_kid)
+            .field("kids", &self.decoding.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+pub fn modified_times(configs: &[TokenKeyConfig]) -> Vec<Option<SystemTime>> {
+    configs
+        .iter()
+        .flat_map(|config| config.private_key_path.iter().chain([&config.public_key_path]))
+        .map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .collect()
+}
\ No newline at end of file
@@ -1,50 +1,59 @@
-use std::{sync::Arc, error::Error, time::{SystemTime, UNIX_EPOCH}};
+use std::{sync::Arc, error::Error, time::{SystemTime, UNIX_EPOCH}, cmp::Reverse};
 
-use jsonwebtoken::{encode, Header, Algorithm, EncodingKey, decode, Validation, DecodingKey};
+use jsonwebtoken::{encode, Header, Algorithm, decode, decode_header, Validation};
 
-use crate::storage::user_tokens::{UserTokenRepository, UserTokenDto};
+use crate::storage::{user_tokens::{UserTokenRepository, UserTokenDto}, user_revocations::{UserRevocationRepository, UserRevocationDto}};
 
-use super::{AccessTokenModel, TokensState};
+use super::{scopes, AccessTokenModel, TokensState, RefreshStateModel, SessionDeviceModel, SessionModel, PublicKeyModel};
 
 pub struct TokenService {
     state: Arc<TokensState>,
     user_token_repository: Arc<dyn UserTokenRepository + Sync + Send>,
+    user_revocation_repository: Arc<dyn UserRevocationRepository + Sync + Send>,
 }
 
 impl TokenService {
     pub fn new(
         state: Arc<TokensState>,
         user_token_repository: Arc<dyn UserTokenRepository + Sync + Send>,
+        user_revocation_repository: Arc<dyn UserRevocationRepository + Sync + Send>,
     ) -> Self {
         Self {
             state,
             user_token_repository,
+            user_revocation_repository,
         }
     }
 
-    pub async fn create_refresh(&self, user_id: i64) -> Result<(String, i64), Box<dyn Error>> {
+    pub async fn create_refresh(&self, user_id: i64, device: SessionDeviceModel) -> Result<(String, i64), Box<dyn Error>> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
             .as_millis() as i64;
 
-        let refresh_token = UserTokenDto::new(user_id);
+        let refresh_token = UserTokenDto::new(
+            now,
+            user_id, 
+            device.device_name,
+            device.user_agent,
+            device.ip,
+            (self.state.refresh_lifetime / 1000) as i32,
+        );
 
-        let ttl = (self.state.refresh_lifetime / 1000) as i32;
-        self.user_token_repository.create(&refresh_token, ttl).await?;
+        self.issue_refresh(&refresh_token).await
+    }
 
-        let token = format!(
-            "{}:{}", 
-            base64_url::encode(&refresh_token.user_id.to_le_bytes()), 
-            &refresh_token.id
-        );
+    pub fn full_scopes(&self, user_id: i64) -> Vec<String> {
+        let mut result: Vec<String> = scopes::ALL.map(String::from).to_vec();
 
-        let expires_at = now + self.state.refresh_lifetime;
+        if self.state.admin_users.contains(&user_id) {
+            result.extend(scopes::ADMIN.map(String::from));
+        }
 
-        Ok((token, expires_at))
+        result
     }
 
-    pub fn create_access(&self, user_id: i64) -> Result<(String, i64), Box<dyn Error>> {
+    pub fn create_access(&self, user_id: i64, scopes: Vec<String>) -> Result<(String, i64), Box<dyn Error>> {
         let now = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
@@ -58,19 +67,206 @@ impl TokenService {
             iat: now,
             nbf: now,
             iss: base64_url::encode(&self.state.instance_id.as_bytes()),
-            aud: None,
+            aud: Some(self.state.audience.clone()),
+            scopes,
         };
         
+        let keys = self.state.keys();
+
+        let mut header = Header::new(Algorithm::ES384);
+        header.kid = Some(keys.active_kid.clone());
+
         let token = encode(
-            &Header::new(Algorithm::ES384), 
+            &header, 
             &claims, 
-            &EncodingKey::from_ec_pem(self.state.jwt_private_key.as_bytes())?,
+            &keys.encoding,
         )?;
 
         Ok((token, expires_at))
     }
 
-    pub async fn find_refresh(&self, token: &str) -> Result<Option<i64>, Box<dyn Error>> {
+    pub async fn use_refresh(&self, token: &str, ip: String) -> Result<RefreshStateModel, Box<dyn Error>> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        let mut dto = match self.find_refresh_dto(token).await? {
+            Some(dto) => dto,
+            None => return Ok(RefreshStateModel::Absent),
+        };
+
+        if dto.used {
+            self.revoke_family(&dto).await?;
+            return Ok(RefreshStateModel::Reused);
+        }
+
+        // Tokens issued before rotation have no known ttl
+        if dto.ttl <= 0 {
+            dto.ttl = (self.state.refresh_lifetime / 1000) as i32;
+        }
+
+        if !self.state.refresh_rotation {
+            dto.ip = ip;
+            dto.last_used_at = now;
+            self.user_token_repository.touch(&dto).await?;
+
+            return Ok(RefreshStateModel::Valid(dto.user_id));
+        }
+
+        // Losing the race means the same token was presented twice
+        if !self.user_token_repository.use_token(&dto).await? {
+            self.revoke_family(&dto).await?;
+            return Ok(RefreshStateModel::Reused);
+        }
+
+        let rotated = dto.rotate(now, ip, (self.state.refresh_lifetime / 1000) as i32);
+        let (token, expires_at) = self.issue_refresh(&rotated).await?;
+
+        Ok(RefreshStateModel::Rotated(dto.user_id, token, expires_at))
+    }
+
+    pub async fn revoke_refresh(&self, token: &str) -> Result<bool, Box<dyn Error>> {
+        let dto_option = self.find_refresh_dto(token).await?;
+
+        match dto_option {
+            Some(dto) => {
+                self.user_token_repository.delete(dto.user_id, &[dto.id]).await?;
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    pub async fn revoke_all_refresh(&self, token: &str) -> Result<bool, Box<dyn Error>> {
+        let dto_option = self.find_refresh_dto(token).await?;
+
+        match dto_option {
+            Some(dto) => {
+                self.user_token_repository.delete_all(dto.user_id).await?;
+                self.revoke_access(dto.user_id).await?;
+                Ok(true)
+            },
+            None => Ok(false),
+        }
+    }
+
+    pub async fn list_sessions(&self, user_id: i64) -> Result<Vec<SessionModel>, Box<dyn Error>> {
+        let mut sessions: Vec<SessionModel> = self.user_token_repository
+            .list(user_id)
+            .await?
+            .into_iter()
+            .filter(|dto| !dto.used)
+            .map(SessionModel::from)
+            .collect();
+
+        sessions.sort_by_key(|session| Reverse(session.last_used_at));
+
+        Ok(sessions)
+    }
+
+    pub async fn revoke_session(&self, user_id: i64, session_id: &str) -> Result<bool, Box<dyn Error>> {
+        let ids: Vec<String> = self.user_token_repository
+            .list(user_id)
+            .await?
+            .into_iter()
+            .filter(|dto| dto.family == session_id)
+            .map(|dto| dto.id)
+            .collect();
+
+        if ids.is_empty() {
+            return Ok(false);
+        }
+
+        self.user_token_repository.delete(user_id, &ids).await?;
+        self.revoke_access(user_id).await?;
+
+        Ok(true)
+    }
+
+    // Access tokens cannot be recalled one by one, so every token of the user issued so far is rejected
+    // and sessions that are still valid get new ones on their next refresh
+    pub async fn revoke_access(&self, user_id: i64) -> Result<(), Box<dyn Error>> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        let dto = UserRevocationDto {
+            user_id,
+            revoked_before: now,
+            // Older tokens are expired by then anyway
+            ttl: (self.state.access_lifetime / 1000) as i32 + 1,
+        };
+
+        self.user_revocation_repository.update(&dto).await
+    }
+
+    pub async fn reload_keys(&self) -> Result<bool, Box<dyn Error>> {
+        let state = Arc::clone(&self.state);
+
+        let reloaded = tokio::task::spawn_blocking(move || state.reload_keys().map_err(|error| error.to_string())).await??;
+
+        Ok(reloaded)
+    }
+
+    pub fn list_keys(&self) -> Vec<PublicKeyModel> {
+        self.state.keys().public.clone()
+    }
+
+    pub async fn decode_access(&self, token: &str) -> Result<Option<AccessTokenModel>, Box<dyn Error>> {
+        let header = match decode_header(token) {
+            Ok(header) => header,
+            Err(_) => return Ok(None),
+        };
+
+        let claims = {
+            let keys = self.state.keys();
+
+            let decoding_key = match keys.find_decoding(header.kid.as_deref()) {
+                Some(key) => key,
+                None => return Ok(None),
+            };
+
+            let mut validation = Validation::new(Algorithm::ES384);
+            validation.set_audience(&[&self.state.audience]);
+
+            match decode::<AccessTokenModel>(token, decoding_key, &validation) {
+                Ok(model) => model.claims,
+                Err(_) => return Ok(None),
+            }
+        };
+
+        // Token issued in the same millisecond as the revocation may predate it
+        if let Some(revoked_before) = self.user_revocation_repository.find(claims.sub).await? {
+            if claims.iat <= revoked_before {
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(claims))
+    }
+
+    async fn issue_refresh(&self, dto: &UserTokenDto) -> Result<(String, i64), Box<dyn Error>> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        self.user_token_repository.create(dto).await?;
+
+        let token = format!(
+            "{}:{}", 
+            base64_url::encode(&dto.user_id.to_le_bytes()), 
+            &dto.id
+        );
+
+        let expires_at = now + self.state.refresh_lifetime;
+
+        Ok((token, expires_at))
+    }
+
+    async fn find_refresh_dto(&self, token: &str) -> Result<Option<UserTokenDto>, Box<dyn Error>> {
         let parts: Vec<&str> = token.split(':').collect();
         if parts.len() != 2 {
             return Ok(None)
@@ -81,30 +277,16 @@ impl TokenService {
                 return Ok(None)
             }
 
-            let id = i64::from_le_bytes(id_bytes[0..8].try_into().unwrap());
-            let dto = UserTokenDto { 
-                user_id: id, 
-                id: String::from(parts[1]), 
-            };
+            let user_id = i64::from_le_bytes(id_bytes[0..8].try_into().unwrap());
 
-            if self.user_token_repository.exists(&dto).await? {
-                return Ok(Some(id))
-            }
+            return self.user_token_repository.find(user_id, parts[1]).await;
         }
 
         Ok(None)
     }
 
-    pub fn decode_access(&self, token: &str) -> Result<Option<AccessTokenModel>, Box<dyn Error>> {
-        let result = decode::<AccessTokenModel>(
-            token, 
-            &DecodingKey::from_ec_pem(self.state.jwt_public_key.as_bytes())?,
-            &Validation::new(Algorithm::ES384)
-        );
-
-        match result {
-            Ok(model) => Ok(Some(model.claims)),
-            Err(_) => Ok(None)
-        }
+    async fn revoke_family(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>> {
+        self.revoke_session(dto.user_id, &dto.family).await?;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,9 +1,15 @@
 use serde::{Serialize, Deserialize};
 
+use super::TokenKeyConfig;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokensConfig {
-    pub jwt_private_key_path: String,
-    pub jwt_public_key_path: String,
+    pub keys: Vec<TokenKeyConfig>,
+    pub active_key: String,
+    pub key_reload_interval: i64,
+    pub audience: String,
+    pub admin_users: Vec<i64>,
     pub refresh_lifetime: i64,
     pub access_lifetime: i64,
+    pub refresh_rotation: bool,
 }
\ No newline at end of file
@@ -1,16 +1,20 @@
-use std::{error::Error, fs::File, io::Read};
+use std::{error::Error, sync::{Arc, RwLock}};
 
 use uuid::Uuid;
 
-use super::TokensConfig;
+use super::{TokensConfig, TokenKeyConfig, token_keys::{TokenKeys, modified_times}};
 
 #[derive(Debug)]
 pub struct TokensState {
     pub instance_id: Uuid,
-    pub jwt_private_key: String,
-    pub jwt_public_key: String,
     pub refresh_lifetime: i64,
     pub access_lifetime: i64,
+    pub refresh_rotation: bool,
+    pub audience: String,
+    pub admin_users: Vec<i64>,
+    key_configs: Vec<TokenKeyConfig>,
+    active_key: String,
+    keys: RwLock<Arc<TokenKeys>>,
 }
 
 impl TokensState {
@@ -18,22 +22,37 @@ impl TokensState {
         instance_id: Uuid, 
         config: &TokensConfig,
     ) -> Result<Self, Box<dyn Error>> {
-        let mut jwt_private_key = String::new();
-        let mut jwt_public_key = String::new();
-
-        File::open(&config.jwt_private_key_path)?
-            .read_to_string(&mut jwt_private_key)?;
-        File::open(&config.jwt_public_key_path)?
-            .read_to_string(&mut jwt_public_key)?;
+        let keys = TokenKeys::load(&config.keys, &config.active_key)?;
 
         let result = Self {
             instance_id,
-            jwt_private_key,
-            jwt_public_key,
             refresh_lifetime: config.refresh_lifetime,
             access_lifetime: config.access_lifetime,
+            refresh_rotation: config.refresh_rotation,
+            audience: config.audience.clone(),
+            admin_users: config.admin_users.clone(),
+            key_configs: config.keys.clone(),
+            active_key: config.active_key.clone(),
+            keys: RwLock::new(Arc::new(keys)),
         };
 
         Ok(result)
     }
+
+    pub fn keys(&self) -> Arc<TokenKeys> {
+        Arc::clone(&self.keys.read().unwrap())
+    }
+
+    // Reads key files, so it runs off the request path
+    pub fn reload_keys(&self) -> Result<bool, Box<dyn Error>> {
+        if self.keys().modified == modified_times(&self.key_configs) {
+            return Ok(false);
+        }
+
+        // Keep serving current keys while files are being replaced
+        let keys = TokenKeys::load(&self.key_configs, &self.active_key)?;
+        *self.keys.write().unwrap() = Arc::new(keys);
+
+        Ok(true)
+    }
 }
\ No newline at end of file
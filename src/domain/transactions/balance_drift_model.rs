@@ -0,0 +1,10 @@
+use bigdecimal::BigDecimal;
+
+#[derive(Debug)]
+pub struct BalanceDriftModel {
+    pub user_id: i64,
+    pub currency: String,
+    pub stored: BigDecimal,
+    pub expected: BigDecimal,
+    pub repaired: bool,
+}
\ No newline at end of file
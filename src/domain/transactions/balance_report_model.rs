@@ -0,0 +1,23 @@
+use super::{BalanceDriftModel, ChainFaultModel};
+
+pub struct BalanceReportModel {
+    pub registry_id: i64,
+    pub replayed: i64,
+    pub last_pack: i64,
+    pub last_sequence: i16,
+    pub drifts: Vec<BalanceDriftModel>,
+    pub fault: Option<ChainFaultModel>,
+}
+
+impl BalanceReportModel {
+    pub fn new(registry_id: i64) -> Self {
+        Self {
+            registry_id,
+            replayed: 0,
+            last_pack: 0,
+            last_sequence: -1,
+            drifts: Vec::new(),
+            fault: None,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+#[derive(Debug)]
+pub enum ChainFaultModel {
+    BrokenLink(i64, i16),
+    MissingSequence(i64, i16),
+    PackGap(i64),
+}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use super::ChainFaultModel;
+
+pub struct ChainReportModel {
+    pub registry_id: i64,
+    pub checked: i64,
+    pub last_pack: i64,
+    pub last_sequence: i16,
+    pub fault: Option<ChainFaultModel>,
+}
+
+impl ChainReportModel {
+    pub fn new(registry_id: i64, pack: i64, sequence: i16) -> Self {
+        Self {
+            registry_id,
+            checked: 0,
+            last_pack: pack,
+            last_sequence: sequence - 1,
+            fault: None,
+        }
+    }
+}
\ No newline at end of file
@@ -2,8 +2,24 @@ mod transaction_model;
 mod transaction_state_model;
 mod transaction_variant_model;
 mod transaction_service;
+mod transaction_repositories;
+mod chain_report_model;
+mod chain_fault_model;
+mod transaction_completion_model;
+mod balance_drift_model;
+mod balance_report_model;
+mod pending_registry_model;
+#[cfg(test)]
+mod tests;
 
 pub use transaction_model::TransactionModel;
 pub use transaction_state_model::TransactionStateModel;
 pub use transaction_variant_model::TransactionVariantModel;
-pub use transaction_service::TransactionService;
\ No newline at end of file
+pub use transaction_service::TransactionService;
+pub use transaction_repositories::TransactionRepositories;
+pub use chain_report_model::ChainReportModel;
+pub use chain_fault_model::ChainFaultModel;
+pub use transaction_completion_model::TransactionCompletionModel;
+pub use balance_drift_model::BalanceDriftModel;
+pub use balance_report_model::BalanceReportModel;
+pub use pending_registry_model::PendingRegistryModel;
\ No newline at end of file
@@ -0,0 +1,44 @@
+use crate::storage::pending_registries::PendingRegistryDto;
+
+use super::TransactionModel;
+
+#[derive(Clone)]
+pub struct PendingRegistryModel {
+    pub registry_id: i64,
+    pub pack: i64,
+    pub sequence: i16,
+    pub created_at: i64,
+}
+
+impl From<&TransactionModel> for PendingRegistryModel {
+    fn from(transaction: &TransactionModel) -> Self {
+        Self {
+            registry_id: transaction.registry_id,
+            pack: transaction.pack,
+            sequence: transaction.sequence,
+            created_at: transaction.created_at,
+        }
+    }
+}
+
+impl From<PendingRegistryDto> for PendingRegistryModel {
+    fn from(dto: PendingRegistryDto) -> Self {
+        Self {
+            registry_id: dto.registry_id,
+            pack: dto.pack,
+            sequence: dto.sequence,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+impl From<PendingRegistryModel> for PendingRegistryDto {
+    fn from(model: PendingRegistryModel) -> Self {
+        Self {
+            registry_id: model.registry_id,
+            pack: model.pack,
+            sequence: model.sequence,
+            created_at: model.created_at,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,452 @@
+use std::{sync::Arc, collections::HashMap};
+
+use bigdecimal::{BigDecimal, Zero};
+use proptest::{prelude::*, collection::vec, test_runner::TestCaseError};
+
+use crate::{
+    storage::{
+        transactions::{TransactionRepository, MemoryTransactionRepository},
+        registries::{RegistryRepository, RegistryDto, MemoryRegistryRepository},
+        registry_users::{RegistryUserRepository, RegistryUserDto, RegistryUserUpdateDto, MemoryRegistryUserRepository},
+        registry_policies::MemoryRegistryPolicyRepository,
+        users::{UserRepository, UserDto, MemoryUserRepository},
+        user_registries::MemoryUserRegistryRepository,
+        pending_registries::MemoryPendingRegistryRepository,
+        simulation::{SimulationScheduler, SimulatedTransactionRepository, SimulatedRegistryRepository, SimulatedRegistryUserRepository, SimulatedUserRepository},
+    },
+    domain::{registries::{BalancePolicyModel, RegistryModel}, registry_users::RegistryUserRoleModel},
+    logging::Logger,
+};
+
+use super::{TransactionService, TransactionRepositories, TransactionCompletionModel, TransactionModel};
+
+const REGISTRY_ID: i64 = 1;
+const CURRENCY: &str = "USD";
+const MAX_RECONCILE: usize = 8;
+
+#[derive(Debug, Clone)]
+struct Transfer {
+    source: usize,
+    offset: usize,
+    amount: i64,
+}
+
+#[derive(Debug, Clone)]
+struct Scenario {
+    seed: u64,
+    fault_rate: f64,
+    members: usize,
+    senders: Vec<Vec<Transfer>>,
+    reconciler_runs: usize,
+    refresh_runs: usize,
+}
+
+struct Simulation {
+    scheduler: Arc<SimulationScheduler>,
+    service: Arc<TransactionService>,
+    transactions: Arc<MemoryTransactionRepository>,
+    registries: Arc<SimulatedRegistryRepository>,
+    registry_users: Arc<MemoryRegistryUserRepository>,
+    users: Arc<MemoryUserRepository>,
+}
+
+impl Simulation {
+    async fn new(scenario: &Scenario) -> Self {
+        let scheduler = Arc::new(SimulationScheduler::new(scenario.seed, scenario.fault_rate));
+        let transactions = Arc::new(MemoryTransactionRepository::new());
+        let registry_users = Arc::new(MemoryRegistryUserRepository::new());
+        let registries = Arc::new(SimulatedRegistryRepository::new(
+            Arc::clone(&scheduler),
+            Arc::new(MemoryRegistryRepository::new()),
+        ));
+
+        registries.create(&RegistryDto {
+            id: REGISTRY_ID,
+            created_at: 0,
+            updated_at: 0,
+            current_pack: 0,
+            current_sequence: -1,
+            variant: 2,
+            name: String::new(),
+            image: String::new(),
+        }).await.unwrap();
+
+        let members: Vec<RegistryUserDto> = (0..scenario.members)
+            .map(|member| RegistryUserDto::new(0, REGISTRY_ID, user_id(member), 2))
+            .collect();
+        registry_users.create(&members).await.unwrap();
+
+        let users = Arc::new(MemoryUserRepository::new());
+        for member in 0..scenario.members {
+            users.create(&UserDto::from_phone(user_id(member), member as i64)).await.unwrap();
+        }
+
+        let service = Arc::new(TransactionService::new(
+            BalancePolicyModel::Unlimited,
+            TransactionRepositories {
+                transaction: Arc::new(SimulatedTransactionRepository::new(
+                    Arc::clone(&scheduler),
+                    Arc::clone(&transactions) as Arc<dyn TransactionRepository + Sync + Send>,
+                )),
+                registry: Arc::clone(&registries) as Arc<dyn RegistryRepository + Sync + Send>,
+                registry_user: Arc::new(SimulatedRegistryUserRepository::new(
+                    Arc::clone(&scheduler),
+                    Arc::clone(&registry_users) as Arc<dyn RegistryUserRepository + Sync + Send>,
+                )),
+                registry_policy: Arc::new(MemoryRegistryPolicyRepository::new()),
+                user: Arc::new(SimulatedUserRepository::new(
+                    Arc::clone(&scheduler),
+                    Arc::clone(&users) as Arc<dyn UserRepository + Sync + Send>,
+                )),
+                user_registry: Arc::new(MemoryUserRegistryRepository::new(Arc::clone(&registry_users))),
+                pending_registry: Arc::new(MemoryPendingRegistryRepository::new()),
+            },
+            Arc::new(Logger::new()),
+        ));
+
+        Self {
+            scheduler,
+            service,
+            transactions,
+            registries,
+            registry_users,
+            users,
+        }
+    }
+
+    async fn run(&self, scenario: &Scenario) {
+        let mut handles = Vec::new();
+
+        for (actor, transfers) in scenario.senders.iter().enumerate() {
+            let service = Arc::clone(&self.service);
+            let registries = Arc::clone(&self.registries);
+            let transfers = transfers.clone();
+            let members = scenario.members;
+
+            handles.push(self.scheduler.spawn(actor, async move {
+                for transfer in transfers {
+                    // Like the grpc layer, every send starts from a fresh registry read
+                    let registry_option = registries.find(REGISTRY_ID).await.ok().flatten();
+                    let registry: RegistryModel = match registry_option {
+                        Some(dto) => dto.into(),
+                        None => continue,
+                    };
+
+                    let source = transfer.source % members;
+                    let target = (source + 1 + transfer.offset % (members - 1)) % members;
+
+                    let _ = service.send_basic(
+                        &registry,
+                        user_id(source),
+                        user_id(target),
+                        BigDecimal::from(transfer.amount),
+                        String::from(CURRENCY),
+                        String::new(),
+                        String::new(),
+                    ).await.is_ok();
+                }
+            }));
+        }
+
+        let service = Arc::clone(&self.service);
+        let reconciler_runs = scenario.reconciler_runs;
+        handles.push(self.scheduler.spawn(scenario.senders.len(), async move {
+            for _ in 0..reconciler_runs {
+                let _ = service.complete(REGISTRY_ID).await.is_ok();
+            }
+        }));
+
+        // Full recompute races the settlement of every send
+        let service = Arc::clone(&self.service);
+        let refresh_runs = scenario.refresh_runs;
+        let members = scenario.members;
+        handles.push(self.scheduler.spawn(scenario.senders.len() + 1, async move {
+            for run in 0..refresh_runs * members {
+                let _ = service.refresh_user_balance(user_id(run % members)).await.is_ok();
+            }
+        }));
+
+        self.scheduler.run().await;
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+
+    // Calls outside of actors are neither gated nor failed, pending entries are drained like the worker does
+    async fn reconcile(&self) -> bool {
+        for _ in 0..MAX_RECONCILE {
+            let pending_registries = self.service.list_pending(None, i32::MAX).await.unwrap();
+
+            for pending in &pending_registries {
+                self.service.complete_pending(pending).await.unwrap();
+            }
+
+            if let TransactionCompletionModel::Consistent = self.service.complete(REGISTRY_ID).await.unwrap() {
+                if self.service.list_pending(None, i32::MAX).await.unwrap().is_empty() {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    async fn chain_balances(&self) -> HashMap<i64, BigDecimal> {
+        let mut balances = HashMap::new();
+
+        for dto in self.transactions.list_forward(REGISTRY_ID, 0, 0, i32::MAX).await.unwrap() {
+            *balances.entry(dto.source_user_id).or_insert_with(BigDecimal::zero) -= &dto.amount;
+            *balances.entry(dto.target_user_id).or_insert_with(BigDecimal::zero) += &dto.amount;
+        }
+
+        balances
+    }
+}
+
+fn user_id(member: usize) -> i64 {
+    member as i64 + 100
+}
+
+// Small amounts make balances repeat, which is what stale conditional updates trip on
+fn transfer() -> impl Strategy<Value = Transfer> {
+    (0usize..4, 0usize..4, 1i64..=2).prop_map(|(source, offset, amount)| Transfer { source, offset, amount })
+}
+
+fn scenario() -> impl Strategy<Value = Scenario> {
+    (
+        any::<u64>(),
+        prop_oneof![Just(0.0), 0.0f64..0.3],
+        2usize..=4,
+        vec(vec(transfer(), 1..=6), 1..=4),
+        0usize..=3,
+        0usize..=2,
+    ).prop_map(|(seed, fault_rate, members, senders, reconciler_runs, refresh_runs)| Scenario {
+        seed,
+        fault_rate,
+        members,
+        senders,
+        reconciler_runs,
+        refresh_runs,
+    })
+}
+
+async fn check(scenario: Scenario) -> Result<(), TestCaseError> {
+    let simulation = Simulation::new(&scenario).await;
+    simulation.run(&scenario).await;
+
+    prop_assert!(simulation.reconcile().await, "registry did not settle");
+
+    let report = simulation.service.verify_chain(REGISTRY_ID, (0, 0), (i64::MAX, i16::MAX)).await.unwrap().unwrap();
+    prop_assert!(report.fault.is_none(), "chain is broken at {:?}", report.fault);
+
+    let chain_balances = simulation.chain_balances().await;
+
+    for dto in simulation.registry_users.list_all(REGISTRY_ID).await.unwrap() {
+        let balance = dto.balance.get(CURRENCY).cloned().unwrap_or_else(BigDecimal::zero);
+        let expected = chain_balances.get(&dto.user_id).cloned().unwrap_or_else(BigDecimal::zero);
+
+        prop_assert_eq!(balance, expected, "balance of {} differs from the chain", dto.user_id);
+        prop_assert!(
+            (dto.current_pack, dto.current_sequence) <= (report.last_pack, report.last_sequence),
+            "cursor of {} is ahead of the chain", dto.user_id,
+        );
+    }
+
+    let rebuild = simulation.service.rebuild_balances(REGISTRY_ID, false).await.unwrap().unwrap();
+    prop_assert!(rebuild.fault.is_none());
+    prop_assert!(rebuild.drifts.is_empty(), "rebuild reports drift {:?}", rebuild.drifts);
+
+    // Projection has no repair refresh here, deltas lost to faults are recomputed by the reconciler
+    for member in 0..scenario.members {
+        let user = simulation.users.find_id(user_id(member)).await.unwrap().unwrap();
+        let balance = user.balance.get(CURRENCY).cloned().unwrap_or_else(BigDecimal::zero);
+        let expected = chain_balances.get(&user_id(member)).cloned().unwrap_or_else(BigDecimal::zero);
+
+        prop_assert_eq!(balance, expected, "user balance of {} differs from the chain", user_id(member));
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rebuild_repairs_drifted_balance() {
+    let scenario = Scenario {
+        seed: 0,
+        fault_rate: 0.0,
+        members: 2,
+        senders: vec![vec![Transfer { source: 0, offset: 0, amount: 2 }, Transfer { source: 1, offset: 0, amount: 1 }]],
+        reconciler_runs: 0,
+        refresh_runs: 0,
+    };
+
+    let simulation = Simulation::new(&scenario).await;
+    simulation.run(&scenario).await;
+    assert!(simulation.reconcile().await);
+
+    let dto = simulation.registry_users.list_all(REGISTRY_ID).await.unwrap().remove(0);
+    let corrupted = simulation.registry_users.update(&[RegistryUserUpdateDto {
+        registry_id: REGISTRY_ID,
+        user_id: dto.user_id,
+        updated_at: dto.updated_at,
+        source_pack: dto.current_pack,
+        source_sequence: dto.current_sequence,
+        current_pack: dto.current_pack,
+        current_sequence: dto.current_sequence,
+        currency: String::from(CURRENCY),
+        source_value: dto.balance.get(CURRENCY).cloned(),
+        target_value: BigDecimal::from(7),
+    }]).await.unwrap();
+    assert!(corrupted);
+
+    let report = simulation.service.rebuild_balances(REGISTRY_ID, true).await.unwrap().unwrap();
+    assert_eq!(report.replayed, 2);
+    assert_eq!(report.drifts.len(), 1);
+    assert_eq!(report.drifts[0].user_id, dto.user_id);
+    assert_eq!(report.drifts[0].stored, BigDecimal::from(7));
+    assert_eq!(report.drifts[0].expected, simulation.chain_balances().await[&dto.user_id]);
+    assert!(report.drifts[0].repaired);
+    let report_expected = report.drifts[0].expected.clone();
+
+    let report = simulation.service.rebuild_balances(REGISTRY_ID, false).await.unwrap().unwrap();
+    assert!(report.drifts.is_empty());
+
+    let user = simulation.users.find_id(dto.user_id).await.unwrap().unwrap();
+    assert_eq!(user.balance[CURRENCY], report_expected);
+}
+
+#[tokio::test]
+async fn rebuild_replays_readded_member_from_join() {
+    let scenario = Scenario {
+        seed: 0,
+        fault_rate: 0.0,
+        members: 2,
+        senders: vec![vec![Transfer { source: 0, offset: 0, amount: 2 }]],
+        reconciler_runs: 0,
+        refresh_runs: 0,
+    };
+
+    let simulation = Simulation::new(&scenario).await;
+    simulation.run(&scenario).await;
+    assert!(simulation.reconcile().await);
+
+    // Member comes back with a fresh balance, so the transaction before leaving is not theirs anymore
+    let member = simulation.registry_users.list(REGISTRY_ID, &[user_id(1)]).await.unwrap().remove(0);
+    assert!(simulation.registry_users.delete(&member).await.unwrap());
+
+    let registry = simulation.registries.find(REGISTRY_ID).await.unwrap().unwrap();
+    assert!(simulation.registry_users.create(&[RegistryUserDto {
+        current_pack: registry.current_pack,
+        current_sequence: registry.current_sequence,
+        joined_pack: registry.current_pack,
+        joined_sequence: registry.current_sequence,
+        ..RegistryUserDto::new(0, REGISTRY_ID, user_id(1), 2)
+    }]).await.unwrap());
+
+    let report = simulation.service.rebuild_balances(REGISTRY_ID, false).await.unwrap().unwrap();
+    assert_eq!(report.replayed, 1);
+    assert!(report.drifts.is_empty());
+}
+
+#[tokio::test]
+async fn stale_balance_update_is_not_applied_twice() {
+    let transfers = |first: usize| (0..4)
+        .map(|index| Transfer {
+            source: (first + index) % 2,
+            offset: 0,
+            amount: 1,
+        })
+        .collect::<Vec<Transfer>>();
+
+    let scenario = Scenario {
+        seed: 624,
+        fault_rate: 0.0,
+        members: 2,
+        senders: vec![transfers(0), transfers(1), transfers(0)],
+        reconciler_runs: 2,
+        refresh_runs: 0,
+    };
+
+    check(scenario).await.unwrap();
+}
+
+#[tokio::test]
+async fn refresh_during_settlement_counts_each_transaction_once() {
+    let transfers = (0..4)
+        .map(|index| Transfer {
+            source: index % 3,
+            offset: index,
+            amount: 1 + index as i64 % 2,
+        })
+        .collect::<Vec<Transfer>>();
+
+    for seed in 0..32 {
+        let scenario = Scenario {
+            seed,
+            fault_rate: 0.0,
+            members: 3,
+            senders: vec![transfers.clone(), transfers.clone()],
+            reconciler_runs: 1,
+            refresh_runs: 2,
+        };
+
+        check(scenario).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn member_deleted_before_write_is_settled_on_both_sides() {
+    let scenario = Scenario {
+        seed: 0,
+        fault_rate: 0.0,
+        members: 2,
+        senders: Vec::new(),
+        reconciler_runs: 0,
+        refresh_runs: 0,
+    };
+
+    // Owner removed the target of the send, or the sender left right after it passed its checks
+    for deleted in [user_id(1), user_id(0)] {
+        let simulation = Simulation::new(&scenario).await;
+
+        // Removal settled and deleted the member before a send that passed its checks wrote the transaction
+        let member = simulation.registry_users.list(REGISTRY_ID, &[deleted]).await.unwrap().remove(0);
+        assert!(simulation.registry_users.delete(&member).await.unwrap());
+
+        let transaction = TransactionModel::basic(
+            REGISTRY_ID,
+            user_id(0),
+            user_id(1),
+            BigDecimal::from(3),
+            String::from(CURRENCY),
+            String::new(),
+            String::new(),
+            &None,
+        );
+        assert!(simulation.transactions.create(&transaction.into()).await.unwrap());
+
+        assert!(simulation.reconcile().await);
+
+        let chain_balances = simulation.chain_balances().await;
+        let registry_users = simulation.registry_users.list_all(REGISTRY_ID).await.unwrap();
+        assert_eq!(registry_users.len(), 2);
+
+        for dto in registry_users {
+            assert_eq!(dto.balance[CURRENCY], chain_balances[&dto.user_id]);
+
+            // Restored member stays leaving until its removal is finished
+            let leaving = RegistryUserRoleModel::from(dto.role) == RegistryUserRoleModel::Leaving;
+            assert_eq!(leaving, dto.user_id == deleted);
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn balances_match_chain_after_reconciliation(scenario in scenario()) {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(check(scenario))?;
+    }
+}
\ No newline at end of file
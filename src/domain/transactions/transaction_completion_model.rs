@@ -0,0 +1,5 @@
+pub enum TransactionCompletionModel {
+    Consistent,
+    Completed(i64, i16),
+    Conflict,
+}
\ No newline at end of file
@@ -7,6 +7,8 @@ use crate::storage::transactions::TransactionDto;
 
 use super::transaction_variant_model::TransactionVariantModel;
 
+pub const HASH_VERSION: i16 = 2;
+
 #[derive(Clone)]
 pub struct TransactionModel {
     pub registry_id: i64,
@@ -20,6 +22,7 @@ pub struct TransactionModel {
     pub currency: String,
     pub label: String,
     pub description: String,
+    pub hash_version: i16,
     pub hash: Vec<u8>,
 }
 
@@ -58,6 +61,7 @@ impl TransactionModel {
             currency,
             label,
             description,
+            hash_version: HASH_VERSION,
             hash: Vec::new(),
         };
 
@@ -80,7 +84,25 @@ impl TransactionModel {
         }
     }
 
+    pub fn previous(pack: i64, sequence: i16) -> (i64, i16) {
+        if sequence > 0 {
+            (pack, sequence - 1)
+        }
+        else {
+            (pack - 1, i16::MAX)
+        }
+    }
+
     pub fn hash(&self, previous: &Vec<u8>) -> Vec<u8> {
+        match self.hash_version {
+            1 => self.hash_v1(previous),
+            2 => self.hash_v2(previous),
+            _ => Vec::new(),
+        }
+    }
+
+    // Legacy format, label and description are not covered
+    fn hash_v1(&self, previous: &Vec<u8>) -> Vec<u8> {
         let (bigint, amaount_exponent) = self.amount.as_bigint_and_exponent();
         
         let (amount_sign, amount_bytes) = bigint.to_bytes_le();
@@ -119,6 +141,48 @@ impl TransactionModel {
         hasher.update(&content);
         hasher.finalize().to_vec()
     }
+
+    fn hash_v2(&self, previous: &[u8]) -> Vec<u8> {
+        let (bigint, amount_exponent) = self.amount.as_bigint_and_exponent();
+        let (amount_sign, amount_bytes) = bigint.to_bytes_le();
+
+        let mut content = Vec::with_capacity(73
+            + amount_bytes.len()
+            + self.currency.len()
+            + self.label.len()
+            + self.description.len()
+            + previous.len()
+        );
+
+        content.extend_from_slice(&self.hash_version.to_le_bytes());
+        content.extend_from_slice(&self.registry_id.to_le_bytes());
+        content.extend_from_slice(&self.pack.to_le_bytes());
+        content.extend_from_slice(&self.created_at.to_le_bytes());
+        content.extend_from_slice(&self.source_user_id.to_le_bytes());
+        content.extend_from_slice(&self.target_user_id.to_le_bytes());
+        content.extend_from_slice(&self.sequence.to_le_bytes());
+        content.extend_from_slice(&i16::from(self.variant).to_le_bytes());
+        content.push(match amount_sign {
+            Sign::Minus => 1,
+            Sign::NoSign => 2,
+            Sign::Plus => 3,
+        });
+        extend_prefixed(&mut content, &amount_bytes);
+        content.extend_from_slice(&amount_exponent.to_le_bytes());
+        extend_prefixed(&mut content, self.currency.as_bytes());
+        extend_prefixed(&mut content, self.label.as_bytes());
+        extend_prefixed(&mut content, self.description.as_bytes());
+        extend_prefixed(&mut content, previous);
+
+        let mut hasher = Sha256::new();
+        hasher.update(&content);
+        hasher.finalize().to_vec()
+    }
+}
+
+fn extend_prefixed(content: &mut Vec<u8>, bytes: &[u8]) {
+    content.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    content.extend_from_slice(bytes);
 }
 
 impl From<TransactionDto> for TransactionModel {
@@ -135,6 +199,7 @@ impl From<TransactionDto> for TransactionModel {
             currency: dto.currency,
             label: dto.label,
             description: dto.description,
+            hash_version: dto.hash_version,
             hash: dto.hash,
         }
     }
@@ -154,6 +219,7 @@ impl From<TransactionModel> for TransactionDto {
             currency: model.currency,
             label: model.label,
             description: model.description,
+            hash_version: model.hash_version,
             hash: model.hash,
         }
     }
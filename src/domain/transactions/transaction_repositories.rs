@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use crate::storage::{
+    transactions::TransactionRepository, 
+    registry_users::RegistryUserRepository, 
+    registries::RegistryRepository, 
+    registry_policies::RegistryPolicyRepository,
+    users::UserRepository,
+    user_registries::UserRegistryRepository,
+    pending_registries::PendingRegistryRepository,
+};
+
+// Settlement touches most tables, so the service takes them as one set
+pub struct TransactionRepositories {
+    pub transaction: Arc<dyn TransactionRepository + Sync + Send>,
+    pub registry: Arc<dyn RegistryRepository + Sync + Send>,
+    pub registry_user: Arc<dyn RegistryUserRepository + Sync + Send>,
+    pub registry_policy: Arc<dyn RegistryPolicyRepository + Sync + Send>,
+    pub user: Arc<dyn UserRepository + Sync + Send>,
+    pub user_registry: Arc<dyn UserRegistryRepository + Sync + Send>,
+    pub pending_registry: Arc<dyn PendingRegistryRepository + Sync + Send>,
+}
\ No newline at end of file
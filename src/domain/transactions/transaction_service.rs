@@ -1,34 +1,64 @@
-use std::{sync::Arc, error::Error};
+use std::{sync::Arc, error::Error, cmp::min, collections::HashMap};
 
 use bigdecimal::{BigDecimal, Zero};
 
 use crate::{
     storage::{
         transactions::TransactionRepository, 
-        registry_users::{RegistryUserRepository, RegistryUserUpdateDto}, 
-        registries::{RegistryTransactionUpdateDto, RegistryRepository}
+        registry_users::{RegistryUserRepository, RegistryUserDto, RegistryUserUpdateDto}, 
+        registries::{RegistryTransactionUpdateDto, RegistryRepository}, 
+        registry_policies::RegistryPolicyRepository,
+        users::{UserRepository, UserBalanceUpdateDto},
+        user_registries::UserRegistryRepository,
+        pending_registries::PendingRegistryRepository,
     }, 
-    domain::{registries::RegistryModel, registry_users::RegistryUserModel},
+    domain::{registries::{RegistryModel, BalancePolicyModel, RegistryPolicyModel}, registry_users::{RegistryUserModel, RegistryUserRoleModel}},
+    logging::Logger,
 };
 
-use super::{TransactionModel, TransactionStateModel};
+use super::{
+    TransactionModel, 
+    TransactionStateModel, 
+    ChainReportModel, 
+    ChainFaultModel, 
+    TransactionCompletionModel, 
+    BalanceReportModel, 
+    BalanceDriftModel, 
+    PendingRegistryModel,
+    TransactionRepositories,
+};
+
+const VERIFY_BATCH: i32 = 256;
+const MAX_BALANCE_UPDATE: usize = 4;
 
 pub struct TransactionService {
+    default_balance_policy: BalancePolicyModel,
     transaction_repository: Arc<dyn TransactionRepository + Sync + Send>,
     registry_repository: Arc<dyn RegistryRepository + Sync + Send>,
     registry_user_repository: Arc<dyn RegistryUserRepository + Sync + Send>,
+    registry_policy_repository: Arc<dyn RegistryPolicyRepository + Sync + Send>,
+    user_repository: Arc<dyn UserRepository + Sync + Send>,
+    user_registry_repository: Arc<dyn UserRegistryRepository + Sync + Send>,
+    pending_registry_repository: Arc<dyn PendingRegistryRepository + Sync + Send>,
+    logger: Arc<Logger>,
 }
 
 impl TransactionService {
     pub fn new(
-        transaction_repository: Arc<dyn TransactionRepository + Send + Sync>,
-        registry_repository: Arc<dyn RegistryRepository + Sync + Send>,
-        registry_user_repository: Arc<dyn RegistryUserRepository + Sync + Send>,
+        default_balance_policy: BalancePolicyModel,
+        repositories: TransactionRepositories,
+        logger: Arc<Logger>,
     ) -> Self {
         Self {
-            transaction_repository,
-            registry_repository,
-            registry_user_repository,
+            default_balance_policy,
+            transaction_repository: repositories.transaction,
+            registry_repository: repositories.registry,
+            registry_user_repository: repositories.registry_user,
+            registry_policy_repository: repositories.registry_policy,
+            user_repository: repositories.user,
+            user_registry_repository: repositories.user_registry,
+            pending_registry_repository: repositories.pending_registry,
+            logger,
         }
     }
 
@@ -50,6 +80,22 @@ impl TransactionService {
             }
         }
 
+        let registry_users = self.registry_user_repository.list(registry.id, &[source_user_id, target_user_id]).await?;
+
+        if registry_users.iter().any(|dto| RegistryUserRoleModel::from(dto.role) == RegistryUserRoleModel::Leaving) {
+            return Ok(TransactionStateModel::MemberLeaving);
+        }
+
+        let balance = registry_users
+            .into_iter()
+            .find(|dto| dto.user_id == source_user_id)
+            .and_then(|dto| dto.balance.get(&currency).cloned())
+            .unwrap_or_else(BigDecimal::zero);
+
+        if !self.sufficient(registry.id, balance, &amount, &currency).await? {
+            return Ok(TransactionStateModel::InsufficientFunds);
+        }
+
         let transaction = TransactionModel::basic(
             registry.id, 
             source_user_id, 
@@ -65,16 +111,442 @@ impl TransactionService {
             return Ok(TransactionStateModel::Fail);
         }
 
-        if !self.update_registry(registry, &transaction).await? {
+        if !self.update_registry(registry, &transaction).await? || !self.update_registry_users(&transaction).await? {
+            // Written transaction that is not settled is left to the reconciler
+            self.pending_registry_repository.create(&PendingRegistryModel::from(&transaction).into()).await?;
             return Ok(TransactionStateModel::Pending(transaction));
         }
 
-        if !self.update_registry_users(&transaction).await? {
-            return Ok(TransactionStateModel::Pending(transaction));
+        Ok(TransactionStateModel::Sent(transaction))
+    }
+
+    // Lists backwards from the newest transaction, or from the one before the last listed
+    pub async fn list(
+        &self,
+        registry: &RegistryModel,
+        last: Option<(i64, i16)>,
+        limit: i32,
+    ) -> Result<Vec<TransactionModel>, Box<dyn Error>> {
+        // Pending transaction may already be written to the pack after the current one
+        let newest = (registry.current_pack + 1, i16::MAX);
+        let (mut pack, mut sequence) = match last {
+            Some((last_pack, last_sequence)) => min(TransactionModel::previous(last_pack, last_sequence), newest),
+            None => newest,
+        };
+
+        let mut transactions = Vec::new();
+
+        while pack >= 0 && (transactions.len() as i32) < limit {
+            let dtos = self.transaction_repository.list(
+                registry.id,
+                pack,
+                sequence,
+                limit - transactions.len() as i32,
+            ).await?;
+
+            transactions.extend(dtos.into_iter().map(TransactionModel::from));
+
+            pack -= 1;
+            sequence = i16::MAX;
         }
-        
 
-        Ok(TransactionStateModel::Sent(transaction))
+        Ok(transactions)
+    }
+
+    pub async fn verify_chain(
+        &self,
+        registry_id: i64,
+        from: (i64, i16),
+        to: (i64, i16),
+    ) -> Result<Option<ChainReportModel>, Box<dyn Error>> {
+        let registry: RegistryModel = match self.registry_repository.find(registry_id).await? {
+            Some(dto) => dto.into(),
+            None => return Ok(None),
+        };
+
+        let (mut pack, mut sequence) = from;
+        let mut report = ChainReportModel::new(registry_id, pack, sequence);
+
+        let to = match self.find_last(&registry).await? {
+            Some(last) => min(to, (last.pack, last.sequence)),
+            None => return Ok(Some(report)),
+        };
+
+        if (pack, sequence) > to {
+            return Ok(Some(report));
+        }
+
+        let mut previous_hash = if (pack, sequence) == (0, 0) {
+            Vec::new()
+        }
+        else {
+            let (previous_pack, previous_sequence) = TransactionModel::previous(pack, sequence);
+
+            match self.transaction_repository.find(registry_id, previous_pack, previous_sequence).await? {
+                Some(dto) => dto.hash,
+                None => {
+                    report.fault = Some(ChainFaultModel::MissingSequence(previous_pack, previous_sequence));
+                    return Ok(Some(report));
+                }
+            }
+        };
+
+        while (pack, sequence) <= to {
+            let limit = if pack == to.0 {
+                min(VERIFY_BATCH, to.1 as i32 - sequence as i32 + 1)
+            }
+            else {
+                VERIFY_BATCH
+            };
+
+            let dtos = self.transaction_repository.list_forward(registry_id, pack, sequence, limit).await?;
+
+            if dtos.is_empty() {
+                report.fault = Some(if sequence == 0 {
+                    ChainFaultModel::PackGap(pack)
+                }
+                else {
+                    ChainFaultModel::MissingSequence(pack, sequence)
+                });
+                return Ok(Some(report));
+            }
+
+            for dto in dtos {
+                if (dto.pack, dto.sequence) != (pack, sequence) {
+                    report.fault = Some(ChainFaultModel::MissingSequence(pack, sequence));
+                    return Ok(Some(report));
+                }
+
+                let transaction = TransactionModel::from(dto);
+
+                if transaction.hash(&previous_hash) != transaction.hash {
+                    report.fault = Some(ChainFaultModel::BrokenLink(pack, sequence));
+                    return Ok(Some(report));
+                }
+
+                report.checked += 1;
+                report.last_pack = pack;
+                report.last_sequence = sequence;
+
+                previous_hash = transaction.hash;
+                (pack, sequence) = TransactionModel::next(pack, sequence);
+            }
+        }
+
+        Ok(Some(report))
+    }
+
+    pub async fn complete(&self, registry_id: i64) -> Result<TransactionCompletionModel, Box<dyn Error>> {
+        let registry: RegistryModel = match self.registry_repository.find(registry_id).await? {
+            Some(dto) => dto.into(),
+            None => return Ok(TransactionCompletionModel::Consistent),
+        };
+
+        let transaction = match self.find_last(&registry).await? {
+            Some(transaction) => transaction,
+            None => return Ok(TransactionCompletionModel::Consistent),
+        };
+
+        if !self.is_pending(&registry, &transaction).await? {
+            return Ok(TransactionCompletionModel::Consistent);
+        }
+
+        if !self.ensure_complete(&registry, &transaction).await? {
+            return Ok(TransactionCompletionModel::Conflict);
+        }
+
+        Ok(TransactionCompletionModel::Completed(transaction.pack, transaction.sequence))
+    }
+
+    pub async fn list_pending(&self, last_registry_id: Option<i64>, limit: i32) -> Result<Vec<PendingRegistryModel>, Box<dyn Error>> {
+        let pending_registries = self.pending_registry_repository.scan(last_registry_id, limit).await?;
+        Ok(pending_registries.into_iter().map(|dto| dto.into()).collect())
+    }
+
+    pub async fn complete_pending(&self, pending: &PendingRegistryModel) -> Result<TransactionCompletionModel, Box<dyn Error>> {
+        let completion = self.complete(pending.registry_id).await?;
+
+        if let TransactionCompletionModel::Conflict = completion {
+            return Ok(completion);
+        }
+
+        if !self.sync_user_balances(pending.registry_id).await? {
+            return Ok(TransactionCompletionModel::Conflict);
+        }
+
+        self.pending_registry_repository.delete(&pending.clone().into()).await?;
+
+        Ok(completion)
+    }
+
+    // Replays the chain from the start, a member only accumulates transactions up to
+    // its own cursor, so a pending last transaction is not reported as drift
+    pub async fn rebuild_balances(&self, registry_id: i64, repair: bool) -> Result<Option<BalanceReportModel>, Box<dyn Error>> {
+        let registry: RegistryModel = match self.registry_repository.find(registry_id).await? {
+            Some(dto) => dto.into(),
+            None => return Ok(None),
+        };
+
+        let mut report = BalanceReportModel::new(registry_id);
+
+        let last = match self.find_last(&registry).await? {
+            Some(transaction) => (transaction.pack, transaction.sequence),
+            None => (0, -1),
+        };
+
+        let registry_users = self.registry_user_repository.list_all(registry_id).await?;
+        // Re-added members start over, so only transactions after their join count
+        let cursors: HashMap<i64, _> = registry_users
+            .iter()
+            .map(|dto| (dto.user_id, ((dto.joined_pack, dto.joined_sequence), (dto.current_pack, dto.current_sequence))))
+            .collect();
+        let mut expected: HashMap<i64, HashMap<String, BigDecimal>> = HashMap::new();
+
+        let (mut pack, mut sequence) = (0, 0);
+        let mut previous_hash = Vec::new();
+
+        'replay: while (pack, sequence) <= last {
+            let dtos = self.transaction_repository.list_forward(registry_id, pack, sequence, VERIFY_BATCH).await?;
+
+            if dtos.is_empty() {
+                report.fault = Some(if sequence == 0 {
+                    ChainFaultModel::PackGap(pack)
+                }
+                else {
+                    ChainFaultModel::MissingSequence(pack, sequence)
+                });
+                break;
+            }
+
+            for dto in dtos {
+                if (dto.pack, dto.sequence) != (pack, sequence) {
+                    report.fault = Some(ChainFaultModel::MissingSequence(pack, sequence));
+                    break 'replay;
+                }
+
+                let transaction = TransactionModel::from(dto);
+
+                if transaction.hash(&previous_hash) != transaction.hash {
+                    report.fault = Some(ChainFaultModel::BrokenLink(pack, sequence));
+                    break 'replay;
+                }
+
+                for (user_id, amount) in [
+                    (transaction.source_user_id, -&transaction.amount),
+                    (transaction.target_user_id, transaction.amount.clone()),
+                ] {
+                    if cursors.get(&user_id).is_some_and(|(joined, current)| *joined < (pack, sequence) && (pack, sequence) <= *current) {
+                        *expected
+                            .entry(user_id)
+                            .or_default()
+                            .entry(transaction.currency.clone())
+                            .or_insert_with(BigDecimal::zero) += amount;
+                    }
+                }
+
+                report.replayed += 1;
+                report.last_pack = pack;
+                report.last_sequence = sequence;
+
+                previous_hash = transaction.hash;
+                (pack, sequence) = TransactionModel::next(pack, sequence);
+            }
+        }
+
+        // Balances of a broken chain are unknown, so nothing is compared or repaired
+        if report.fault.is_some() {
+            return Ok(Some(report));
+        }
+
+        for dto in registry_users {
+            let balance = expected.remove(&dto.user_id).unwrap_or_default();
+
+            let mut currencies: Vec<String> = dto.balance.keys().chain(balance.keys()).cloned().collect();
+            currencies.sort();
+            currencies.dedup();
+
+            let mut drifts = Vec::new();
+            let mut update_dtos = Vec::new();
+
+            for currency in currencies {
+                let stored = dto.balance.get(&currency).cloned();
+                let expected_value = balance.get(&currency).cloned().unwrap_or_else(BigDecimal::zero);
+
+                if stored.clone().unwrap_or_else(BigDecimal::zero) == expected_value {
+                    continue;
+                }
+
+                update_dtos.push(RegistryUserUpdateDto {
+                    registry_id,
+                    user_id: dto.user_id,
+                    updated_at: dto.updated_at,
+                    source_pack: dto.current_pack,
+                    source_sequence: dto.current_sequence,
+                    current_pack: dto.current_pack,
+                    current_sequence: dto.current_sequence,
+                    currency: currency.clone(),
+                    source_value: stored.clone(),
+                    target_value: expected_value.clone(),
+                });
+
+                drifts.push(BalanceDriftModel {
+                    user_id: dto.user_id,
+                    currency,
+                    stored: stored.unwrap_or_else(BigDecimal::zero),
+                    expected: expected_value,
+                    repaired: false,
+                });
+            }
+
+            if repair && !update_dtos.is_empty() && self.registry_user_repository.update(&update_dtos).await? {
+                drifts.iter_mut().for_each(|drift| drift.repaired = true);
+                self.refresh_user_balance(dto.user_id).await?;
+            }
+
+            report.drifts.extend(drifts);
+        }
+
+        Ok(Some(report))
+    }
+
+    // users.balance is a projection of the user's registry balances, with the position each registry
+    // is counted up to. Every write bumps the revision, so a concurrent refresh that summed older rows
+    // fails its condition and retries. Full recompute is the repair, settlement only applies its delta
+    pub async fn refresh_user_balance(&self, user_id: i64) -> Result<Option<HashMap<String, BigDecimal>>, Box<dyn Error>> {
+        for _ in 0..MAX_BALANCE_UPDATE {
+            let user = match self.user_repository.find_id(user_id).await? {
+                Some(dto) => dto,
+                None => return Ok(None),
+            };
+
+            let user_registries = self.user_registry_repository.list_all(user_id).await?;
+            let mut balance: HashMap<String, BigDecimal> = HashMap::new();
+            let mut balance_positions = HashMap::new();
+
+            for user_registry in user_registries {
+                let registry_users = self.registry_user_repository.list(user_registry.registry_id, &[user_id]).await?;
+
+                for registry_user in registry_users {
+                    balance_positions.insert(registry_user.registry_id, (registry_user.current_pack, registry_user.current_sequence));
+
+                    for (currency, value) in registry_user.balance {
+                        *balance.entry(currency).or_insert_with(BigDecimal::zero) += value;
+                    }
+                }
+            }
+
+            let update_dto = UserBalanceUpdateDto {
+                id: user_id,
+                source_revision: user.balance_revision,
+                target_revision: user.balance_revision + 1,
+                balance,
+                balance_positions,
+            };
+
+            if self.user_repository.update_balance(&update_dto).await? {
+                return Ok(Some(update_dto.balance));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Delta is added only to a projection that counts the registry exactly up to the member's previous
+    // transaction, so a refresh that already summed the settled rows or a replayed settlement skips it.
+    // Projection that is further behind missed a delta and is recomputed instead
+    async fn apply_user_delta(&self, update_dto: &RegistryUserUpdateDto, joined: (i64, i16)) -> Result<(), Box<dyn Error>> {
+        let source = (update_dto.source_pack, update_dto.source_sequence);
+        let target = (update_dto.current_pack, update_dto.current_sequence);
+        let delta = &update_dto.target_value - update_dto.source_value.clone().unwrap_or_else(BigDecimal::zero);
+
+        for _ in 0..MAX_BALANCE_UPDATE {
+            let user = match self.user_repository.find_id(update_dto.user_id).await? {
+                Some(dto) => dto,
+                None => return Ok(()),
+            };
+
+            match user.balance_positions.get(&update_dto.registry_id).copied() {
+                Some(position) if position >= target => return Ok(()),
+                Some(position) if position == source => (),
+                // Member without transactions since joining adds nothing to the balance yet
+                None if source == joined => (),
+                _ => {
+                    self.refresh_user_balance(update_dto.user_id).await?;
+                    return Ok(());
+                },
+            }
+
+            let mut balance = user.balance;
+            *balance.entry(update_dto.currency.clone()).or_insert_with(BigDecimal::zero) += &delta;
+
+            let mut balance_positions = user.balance_positions;
+            balance_positions.insert(update_dto.registry_id, target);
+
+            let user_update_dto = UserBalanceUpdateDto {
+                id: update_dto.user_id,
+                source_revision: user.balance_revision,
+                target_revision: user.balance_revision + 1,
+                balance,
+                balance_positions,
+            };
+
+            if self.user_repository.update_balance(&user_update_dto).await? {
+                return Ok(());
+            }
+        }
+
+        Ok(())
+    }
+
+    // Projection behind its member's settled position is recomputed before the pending entry is dropped,
+    // every member is checked since a newer settlement may have replaced the entry of the one left behind
+    async fn sync_user_balances(&self, registry_id: i64) -> Result<bool, Box<dyn Error>> {
+        let registry_users = self.registry_user_repository.list_all(registry_id).await?;
+
+        for registry_user in registry_users {
+            let position = match self.user_repository.find_id(registry_user.user_id).await? {
+                Some(dto) => dto.balance_positions.get(&registry_id).copied(),
+                None => continue,
+            };
+
+            if position == Some((registry_user.current_pack, registry_user.current_sequence)) {
+                continue;
+            }
+
+            if self.refresh_user_balance(registry_user.user_id).await?.is_none() {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    async fn is_pending(&self, registry: &RegistryModel, transaction: &TransactionModel) -> Result<bool, Box<dyn Error>> {
+        if (registry.current_pack, registry.current_sequence) < (transaction.pack, transaction.sequence) {
+            return Ok(true);
+        }
+
+        let registry_users = self.registry_user_repository.list(
+            transaction.registry_id, 
+            &[transaction.source_user_id, transaction.target_user_id],
+        ).await?;
+
+        Ok(registry_users.iter().any(|dto| (dto.current_pack, dto.current_sequence) < (transaction.pack, transaction.sequence)))
+    }
+
+    async fn sufficient(
+        &self, 
+        registry_id: i64, 
+        balance: BigDecimal, 
+        amount: &BigDecimal, 
+        currency: &str,
+    ) -> Result<bool, Box<dyn Error>> {
+        let policy = match self.registry_policy_repository.find(registry_id, currency).await? {
+            Some(dto) => RegistryPolicyModel::from(dto).balance,
+            None => self.default_balance_policy.clone(),
+        };
+
+        Ok(policy.allows(&(balance - amount)))
     }
 
     async fn find_last(&self, registry: &RegistryModel) -> Result<Option<TransactionModel>, Box<dyn Error>> {
@@ -126,56 +598,90 @@ impl TransactionService {
         &self,
         transaction: &TransactionModel,
     ) -> Result<bool, Box<dyn Error>> {
-        let mut registry_users = self.registry_user_repository.list(
-            transaction.registry_id, 
-            &[transaction.source_user_id, transaction.target_user_id],
-        ).await?;
+        let user_ids = [transaction.source_user_id, transaction.target_user_id];
+        let mut registry_users = self.registry_user_repository.list(transaction.registry_id, &user_ids).await?;
 
+        // Both sides are settled by one batch, so a settled member means the whole transaction is settled
         if !registry_users.iter().any(|dto| (dto.current_pack, dto.current_sequence) < (transaction.pack, transaction.sequence)) {
             return Ok(true);
         }
 
-        let first_registry_user: RegistryUserModel = registry_users.pop().unwrap().into();
-        let second_registry_user: RegistryUserModel = registry_users.pop().unwrap().into();
+        // Member deleted while the transaction was in flight is restored as leaving, so its removal can be finished after settling
+        if registry_users.len() < user_ids.len() {
+            let (pack, sequence) = TransactionModel::previous(transaction.pack, transaction.sequence);
 
-        let first_value = first_registry_user.balance.get(&transaction.currency).cloned();
-        let first_value_base = first_value.clone().unwrap_or(BigDecimal::zero());
-        let second_value = second_registry_user.balance.get(&transaction.currency).cloned();
-        let second_value_base = second_value.clone().unwrap_or(BigDecimal::zero());
+            let missing: Vec<RegistryUserDto> = user_ids
+                .into_iter()
+                .filter(|&user_id| registry_users.iter().all(|dto| dto.user_id != user_id))
+                .map(|user_id| RegistryUserDto {
+                    current_pack: pack,
+                    current_sequence: sequence,
+                    joined_pack: pack,
+                    joined_sequence: sequence,
+                    ..RegistryUserDto::new(transaction.created_at, transaction.registry_id, user_id, RegistryUserRoleModel::Leaving.into())
+                })
+                .collect();
+
+            for dto in &missing {
+                self.logger.log_info(&format!(
+                    "Restoring deleted member {} of registry {} as leaving to settle transaction {}:{}",
+                    dto.user_id, dto.registry_id, transaction.pack, transaction.sequence,
+                ));
+            }
+
+            // Concurrent completion may restore it first, the list below sees either row
+            self.registry_user_repository.create(&missing).await?;
+
+            registry_users = self.registry_user_repository.list(transaction.registry_id, &user_ids).await?;
+            if registry_users.len() < user_ids.len() {
+                return Ok(false);
+            }
+        }
+
+        let joined: HashMap<i64, (i64, i16)> = registry_users
+            .iter()
+            .map(|dto| (dto.user_id, (dto.joined_pack, dto.joined_sequence)))
+            .collect();
+
+        let update_dtos: Vec<RegistryUserUpdateDto> = registry_users.into_iter().map(|dto| {
+            let registry_user: RegistryUserModel = dto.into();
+
+            let value = registry_user.balance.get(&transaction.currency).cloned();
+            let value_base = value.clone().unwrap_or(BigDecimal::zero());
 
-        let update_dtos = [
-            RegistryUserUpdateDto {
-                registry_id: transaction.registry_id,
-                user_id: first_registry_user.user_id,
-                updated_at: transaction.created_at,
-                current_pack: transaction.pack,
-                current_sequence: transaction.sequence,
-                currency: transaction.currency.clone(),
-                source_value: first_value,
-                target_value: if first_registry_user.user_id == transaction.source_user_id {
-                    &first_value_base - &transaction.amount
-                }
-                else {
-                    &first_value_base + &transaction.amount
-                }
-            },
             RegistryUserUpdateDto {
                 registry_id: transaction.registry_id,
-                user_id: second_registry_user.user_id,
+                user_id: registry_user.user_id,
                 updated_at: transaction.created_at,
+                source_pack: registry_user.current_pack,
+                source_sequence: registry_user.current_sequence,
                 current_pack: transaction.pack,
                 current_sequence: transaction.sequence,
                 currency: transaction.currency.clone(),
-                source_value: second_value,
-                target_value: if second_registry_user.user_id == transaction.source_user_id {
-                    &second_value_base - &transaction.amount
+                source_value: value,
+                target_value: if registry_user.user_id == transaction.source_user_id {
+                    &value_base - &transaction.amount
                 }
                 else {
-                    &second_value_base + &transaction.amount
+                    &value_base + &transaction.amount
                 }
-            },
-        ];
+            }
+        }).collect();
+
+        // Indexed before the batch, so a projection left behind by a failed delta or a crash is recomputed
+        // by the reconciler, which drops the entry once every member's projection caught up
+        self.pending_registry_repository.create(&PendingRegistryModel::from(transaction).into()).await?;
+
+        if !self.registry_user_repository.update(&update_dtos).await? {
+            return Ok(false);
+        }
+
+        for update_dto in &update_dtos {
+            if let Err(error) = self.apply_user_delta(update_dto, joined[&update_dto.user_id]).await {
+                self.logger.log_fail(&error);
+            }
+        }
 
-        self.registry_user_repository.update(&update_dtos).await
+        Ok(true)
     }
 }
\ No newline at end of file
@@ -2,6 +2,8 @@ use super::TransactionModel;
 
 pub enum TransactionStateModel {
     Fail,
+    InsufficientFunds,
+    MemberLeaving,
     Pending(TransactionModel),
     Sent(TransactionModel),
 }
\ No newline at end of file
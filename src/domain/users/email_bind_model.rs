@@ -0,0 +1,6 @@
+pub enum EmailBindModel {
+    Bound,
+    Taken,
+    Absent,
+    Retry,
+}
\ No newline at end of file
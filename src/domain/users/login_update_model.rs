@@ -0,0 +1,7 @@
+pub enum LoginUpdateModel {
+    Updated,
+    Invalid,
+    Taken,
+    Absent,
+    Retry,
+}
\ No newline at end of file
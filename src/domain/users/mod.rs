@@ -1,5 +1,11 @@
 mod user_service;
 mod user_model;
+mod login_update_model;
+mod email_bind_model;
+mod user_relation_model;
 
 pub use user_service::UserService;
-pub use user_model::UserModel;
\ No newline at end of file
+pub use user_model::UserModel;
+pub use login_update_model::LoginUpdateModel;
+pub use email_bind_model::EmailBindModel;
+pub use user_relation_model::UserRelationModel;
\ No newline at end of file
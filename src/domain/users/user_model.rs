@@ -10,6 +10,8 @@ pub struct UserModel {
     pub email: String,
     pub login: String,
     pub image: String,
+    pub discoverable: bool,
+    pub service_account: bool,
     pub balance: HashMap<String, BigDecimal>,
 }
 
@@ -21,6 +23,8 @@ impl From<UserDto> for UserModel {
             email: user_dto.email,
             login: user_dto.login,
             image: user_dto.image,
+            discoverable: user_dto.discoverable,
+            service_account: user_dto.service_account,
             balance: user_dto.balance,
         }
     }
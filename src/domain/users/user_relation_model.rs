@@ -0,0 +1,6 @@
+#[derive(PartialEq)]
+pub enum UserRelationModel {
+    Itself,
+    Related,
+    Stranger,
+}
\ No newline at end of file
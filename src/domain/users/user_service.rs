@@ -1,22 +1,36 @@
 use std::{sync::{Arc, Mutex}, error::Error};
 
-use crate::storage::{id_generator::{IdGenerator}, users::{UserRepository, UserDto}};
+use crate::storage::{
+    id_generator::{IdGenerator}, 
+    users::{UserRepository, UserDto}, 
+    user_logins::{UserLoginRepository, UserLoginDto}, 
+    user_emails::{UserEmailRepository, UserEmailDto},
+};
 
-use super::UserModel;
+use super::{UserModel, LoginUpdateModel, EmailBindModel};
+
+const MIN_LOGIN: usize = 3;
+const MAX_LOGIN: usize = 32;
 
 pub struct UserService {
     id_generator: Arc<Mutex<IdGenerator>>,
     user_repository: Arc<dyn UserRepository + Sync + Send>,
+    user_login_repository: Arc<dyn UserLoginRepository + Sync + Send>,
+    user_email_repository: Arc<dyn UserEmailRepository + Sync + Send>,
 }
 
 impl UserService {
     pub fn new(
         id_generator: Arc<Mutex<IdGenerator>>,
         user_repository: Arc<dyn UserRepository + Sync + Send>,
+        user_login_repository: Arc<dyn UserLoginRepository + Sync + Send>,
+        user_email_repository: Arc<dyn UserEmailRepository + Sync + Send>,
     ) -> Self {
         Self {
             id_generator,
             user_repository,
+            user_login_repository,
+            user_email_repository,
         }
     }
 
@@ -41,6 +55,68 @@ impl UserService {
         }
     }  
 
+    pub async fn get_id_email(&self, email: &String) -> Result<Option<i64>, Box<dyn Error>> {
+        let dto_option = self.user_repository.find_email(email).await?;
+
+        match dto_option {
+            Some(dto) => Ok(Some(dto.id)),
+            None => {
+                // Email may be claimed by a user whose row is not updated yet
+                let claim_option = self.user_email_repository.find(email).await?;
+
+                if let Some(claim) = claim_option {
+                    if self.user_repository.find_id(claim.user_id).await?.is_some() {
+                        return Ok(Some(claim.user_id));
+                    }
+
+                    // Sign up interrupted after the claim, its user is finished here
+                    let dto = UserDto::from_email(claim.user_id, email.clone());
+
+                    if self.user_repository.create(&dto).await? {
+                        return Ok(Some(claim.user_id));
+                    }
+
+                    // Concurrent sign in may have finished it first
+                    let finished = self.user_repository.find_id(claim.user_id).await?;
+
+                    return Ok(finished.map(|dto| dto.id));
+                }
+
+                let dto = UserDto::from_email(
+                    self.id_generator.lock().unwrap().create(), 
+                    email.clone(),
+                );
+
+                let claim = UserEmailDto {
+                    email: email.clone(),
+                    user_id: dto.id,
+                };
+
+                if !self.user_email_repository.create(&claim).await? {
+                    return Ok(None);
+                }
+
+                if self.user_repository.create(&dto).await? {
+                    Ok(Some(dto.id))
+                }
+                else {
+                    Ok(None)
+                }
+            }
+        }
+    }  
+
+    pub async fn create_service_account(&self) -> Result<Option<i64>, Box<dyn Error>> {
+        let dto = UserDto::service_account(self.id_generator.lock().unwrap().create());
+
+        if self.user_repository.create(&dto).await? {
+            Ok(Some(dto.id))
+        }
+        else {
+            Ok(None)
+        }
+    }
+
     pub async fn find_id(&self, id: i64) -> Result<Option<UserModel>, Box<dyn Error>> {
         let dto_option = self.user_repository.find_id(id).await?;
 
@@ -58,4 +134,143 @@ impl UserService {
 
         Ok(dto_option.map(|dto| UserModel::from(dto)))
     }
+
+    pub async fn find_login(&self, login: &str) -> Result<Option<UserModel>, Box<dyn Error>> {
+        let claim_option = self.user_login_repository.find(login).await?;
+
+        match claim_option {
+            Some(claim) => self.find_id(claim.user_id).await,
+            None => Ok(None),
+        }
+    }
+
+    pub async fn update_login(&self, id: i64, login: &str) -> Result<LoginUpdateModel, Box<dyn Error>> {
+        if !is_valid_login(login) {
+            return Ok(LoginUpdateModel::Invalid);
+        }
+
+        let dto = match self.user_repository.find_id(id).await? {
+            Some(dto) => dto,
+            None => return Ok(LoginUpdateModel::Absent),
+        };
+
+        if dto.login == login {
+            return Ok(LoginUpdateModel::Updated);
+        }
+
+        let claim = UserLoginDto {
+            login: login.to_owned(),
+            user_id: id,
+        };
+
+        // Claim of the same user may be left by an interrupted update
+        if !self.user_login_repository.create(&claim).await? {
+            match self.user_login_repository.find(login).await? {
+                Some(existing) if existing.user_id == id => (),
+                _ => return Ok(LoginUpdateModel::Taken),
+            }
+        }
+
+        if !self.user_repository.update_login(id, &dto.login, login).await? {
+            // Another update won the row, the claim is released unless it ended up with this login
+            let current = self.user_repository.find_id(id).await?;
+            if current.as_ref().map(|current| current.login.as_str()) == Some(login) {
+                return Ok(LoginUpdateModel::Updated);
+            }
+
+            self.user_login_repository.delete(&claim).await?;
+
+            return match current {
+                Some(_) => Ok(LoginUpdateModel::Retry),
+                None => Ok(LoginUpdateModel::Absent),
+            };
+        }
+
+        if !dto.login.is_empty() {
+            self.user_login_repository.delete(&UserLoginDto {
+                login: dto.login,
+                user_id: id,
+            }).await?;
+        }
+
+        Ok(LoginUpdateModel::Updated)
+    }
+
+    pub async fn update_image(&self, id: i64, image: &str) -> Result<bool, Box<dyn Error>> {
+        self.user_repository.update_image(id, image).await
+    }
+
+    pub async fn update_discoverable(&self, id: i64, discoverable: bool) -> Result<bool, Box<dyn Error>> {
+        self.user_repository.update_discoverable(id, discoverable).await
+    }
+
+    pub async fn is_email_available(&self, id: i64, email: &String) -> Result<bool, Box<dyn Error>> {
+        if let Some(claim) = self.user_email_repository.find(email).await? {
+            return Ok(claim.user_id == id);
+        }
+
+        // Users signed up before claims existed are only found by the index
+        match self.user_repository.find_email(email).await? {
+            Some(dto) => Ok(dto.id == id),
+            None => Ok(true),
+        }
+    }
+
+    pub async fn bind_email(&self, id: i64, email: &String) -> Result<EmailBindModel, Box<dyn Error>> {
+        let dto = match self.user_repository.find_id(id).await? {
+            Some(dto) => dto,
+            None => return Ok(EmailBindModel::Absent),
+        };
+
+        if &dto.email == email {
+            return Ok(EmailBindModel::Bound);
+        }
+
+        if !self.is_email_available(id, email).await? {
+            return Ok(EmailBindModel::Taken);
+        }
+
+        let claim = UserEmailDto {
+            email: email.clone(),
+            user_id: id,
+        };
+
+        if !self.user_email_repository.create(&claim).await? {
+            match self.user_email_repository.find(email).await? {
+                Some(existing) if existing.user_id == id => (),
+                _ => return Ok(EmailBindModel::Taken),
+            }
+        }
+
+        if !self.user_repository.update_email(id, &dto.email, email).await? {
+            // Another bind won the row, the claim is released unless it ended up with this email
+            let current = self.user_repository.find_id(id).await?;
+            if current.as_ref().map(|current| &current.email) == Some(email) {
+                return Ok(EmailBindModel::Bound);
+            }
+
+            self.user_email_repository.delete(&claim).await?;
+
+            return match current {
+                Some(_) => Ok(EmailBindModel::Retry),
+                None => Ok(EmailBindModel::Absent),
+            };
+        }
+
+        if !dto.email.is_empty() {
+            self.user_email_repository.delete(&UserEmailDto {
+                email: dto.email,
+                user_id: id,
+            }).await?;
+        }
+
+        Ok(EmailBindModel::Bound)
+    }
+}
+
+fn is_valid_login(login: &str) -> bool {
+    login.len() >= MIN_LOGIN 
+        && login.len() <= MAX_LOGIN
+        && login.starts_with(|char: char| char.is_ascii_lowercase())
+        && login.chars().all(|char| char.is_ascii_lowercase() || char.is_ascii_digit() || char == '_')
 }
\ No newline at end of file
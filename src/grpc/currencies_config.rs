@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrenciesConfig {
+    pub default_max_scale: i64,
+    pub max_scales: HashMap<String, i64>,
+}
+
+impl CurrenciesConfig {
+    pub fn max_scale(&self, currency: &str) -> i64 {
+        self.max_scales
+            .get(currency)
+            .cloned()
+            .unwrap_or(self.default_max_scale)
+    }
+}
\ No newline at end of file
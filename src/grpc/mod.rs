@@ -1,6 +1,10 @@
 mod services;
 mod server;
 mod server_config;
+mod currencies_config;
+mod proxies_config;
 
 pub use server_config::ServerConfig;
+pub use currencies_config::CurrenciesConfig;
+pub use proxies_config::ProxiesConfig;
 pub use server::GrpcServer;
\ No newline at end of file
@@ -0,0 +1,15 @@
+use std::net::IpAddr;
+
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProxiesConfig {
+    // Only these peers may set x-forwarded-for, anyone else is the client itself
+    pub trusted: Vec<IpAddr>,
+}
+
+impl ProxiesConfig {
+    pub fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.trusted.contains(ip)
+    }
+}
\ No newline at end of file
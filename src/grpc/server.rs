@@ -1,6 +1,10 @@
 use std::{sync::Arc, time::Duration, net::SocketAddr, error::Error};
 
 use tonic::transport::{Server, server::Router};
+#[cfg(test)]
+use tokio::net::TcpListener;
+#[cfg(test)]
+use tokio_stream::wrappers::TcpListenerStream;
 
 use crate::{domain::ServiceFactory, logging::Logger};
 
@@ -9,6 +13,8 @@ use super::{services::{
     UsersGrpcService, 
     RegistriesGrpcService, 
     ProfileGrpcService, AuthServer, UsersServer, RegistriesServer, ProfileServer, TransactionsGrpcService, TransactionsServer,
+    AdminGrpcService, AdminServer,
+    ContactsGrpcService, ContactsServer,
 }, ServerConfig};
 
 pub struct GrpcServer {
@@ -25,14 +31,26 @@ impl GrpcServer {
         let auth = AuthGrpcService::new(
             Arc::clone(&logger),
             Arc::clone(&service_factory),
+            Arc::new(config.proxies.clone()),
         );
         let users = UsersGrpcService::new(
             Arc::clone(&logger),
             Arc::clone(&service_factory),
         );
+        let admin = AdminGrpcService::new(
+            Arc::clone(&logger),
+            Arc::clone(&service_factory),
+        );
+        let contacts = ContactsGrpcService::new(
+            Arc::clone(&logger),
+            Arc::clone(&service_factory),
+        );
+        let currencies = Arc::new(config.currencies.clone());
+
         let registries = RegistriesGrpcService::new(
             Arc::clone(&logger),
             Arc::clone(&service_factory),
+            Arc::clone(&currencies),
         );
         let profile = ProfileGrpcService::new(
             Arc::clone(&logger),
@@ -41,6 +59,7 @@ impl GrpcServer {
         let transactions = TransactionsGrpcService::new(
             Arc::clone(&logger),
             Arc::clone(&service_factory),
+            Arc::clone(&currencies),
         );
 
         Self {
@@ -52,11 +71,19 @@ impl GrpcServer {
                 .add_service(UsersServer::new(users))
                 .add_service(RegistriesServer::new(registries))
                 .add_service(ProfileServer::new(profile))
-                .add_service(TransactionsServer::new(transactions)),
+                .add_service(TransactionsServer::new(transactions))
+                .add_service(AdminServer::new(admin))
+                .add_service(ContactsServer::new(contacts)),
         }
     }
 
     pub async fn serve(self) -> Result<(), Box<dyn Error>> {
         Ok(self.router.serve(self.address).await?)
     }
+
+    // Serves on an already bound listener, so tests can take an ephemeral port
+    #[cfg(test)]
+    pub async fn serve_with_listener(self, listener: TcpListener) -> Result<(), Box<dyn Error>> {
+        Ok(self.router.serve_with_incoming(TcpListenerStream::new(listener)).await?)
+    }
 }
\ No newline at end of file
@@ -1,6 +1,11 @@
 use serde::{Serialize, Deserialize};
 
+use super::{CurrenciesConfig, ProxiesConfig};
+
 #[derive(Serialize, Deserialize)]
 pub struct ServerConfig {
     pub host: String,
+    pub currencies: CurrenciesConfig,
+    #[serde(default)]
+    pub proxies: ProxiesConfig,
 }
\ No newline at end of file
@@ -0,0 +1,274 @@
+pub mod api_admin {
+    tonic::include_proto!("api_core.admin");
+}
+
+use super::api_common as common;
+
+pub use api_admin::admin_server::AdminServer;
+use tonic::{Request, Response, Status};
+
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        ServiceFactory,
+        api_keys::ApiKeyModel,
+        tokens::scopes,
+        transactions::{BalanceReportModel, BalanceDriftModel, ChainFaultModel},
+    },
+    logging::Logger,
+};
+
+use self::api_admin::{
+    admin_server::Admin,
+    CreateServiceAccountRequest,
+    CreateServiceAccountResponse,
+    ApiKeyResource,
+    CreateApiKeyRequest,
+    CreateApiKeyResponse,
+    RevokeApiKeyRequest,
+    RevokeApiKeyResponse,
+    RebuildBalancesRequest,
+    RebuildBalancesResponse,
+    RebuildUserBalanceRequest,
+    RebuildUserBalanceResponse,
+    BackfillPhoneHashesRequest,
+    BackfillPhoneHashesResponse,
+    BalanceReportResource,
+    balance_report_resource,
+    BalanceDriftResource,
+};
+
+use super::extensions::{StatusResult, AuthorizedRequest};
+
+
+#[derive(Debug)]
+pub struct AdminGrpcService {
+    logger: Arc<Logger>,
+    service_factory: Arc<ServiceFactory>,
+}
+
+impl AdminGrpcService {
+    pub fn new(
+        logger: Arc<Logger>,
+        service_factory: Arc<ServiceFactory>,
+    ) -> Self {
+        Self {
+            logger,
+            service_factory,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Admin for AdminGrpcService {
+    async fn create_service_account(&self, request: Request<CreateServiceAccountRequest>) -> Result<Response<CreateServiceAccountResponse>, Status> {
+        request.authorize(&self.logger, &self.service_factory, &[scopes::API_KEYS_WRITE]).await?;
+
+        let user_id = self.service_factory
+            .user()
+            .create_service_account()
+            .await
+            .consume_error(&self.logger)?
+            .ok_or_else(|| Status::unavailable("Id collision, try again"))?;
+
+        Ok(
+            Response::new(
+                CreateServiceAccountResponse {
+                    user_id,
+                }
+            )
+        )
+    }
+
+    async fn create_api_key(&self, request: Request<CreateApiKeyRequest>) -> Result<Response<CreateApiKeyResponse>, Status> {
+        request.authorize(&self.logger, &self.service_factory, &[scopes::API_KEYS_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        let name = request_data.name.trim();
+        if name.is_empty() || name.len() > 64 {
+            return Err(Status::invalid_argument("Name length must be in [1:64]"));
+        }
+
+        if request_data.scopes.is_empty() {
+            return Err(Status::invalid_argument("At least one scope is required"));
+        }
+
+        if let Some(scope) = request_data.scopes.iter().find(|scope| !scopes::ALL.contains(&scope.as_str())) {
+            return Err(Status::invalid_argument(format!("Unknown scope {}", scope)));
+        }
+
+        let user = self.service_factory
+            .user()
+            .find_id(request_data.user_id)
+            .await
+            .consume_error(&self.logger)?
+            .ok_or_else(|| Status::not_found("User does not exists"))?;
+
+        // Keys carry admin scopes, so they are never issued for people signing in by phone or email
+        if !user.service_account {
+            return Err(Status::failed_precondition("API keys can only be issued to service accounts"));
+        }
+
+        let mut key_scopes = request_data.scopes.clone();
+        key_scopes.sort();
+        key_scopes.dedup();
+
+        let (api_key, key) = self.service_factory
+            .api_key()
+            .create(request_data.user_id, String::from(name), key_scopes)
+            .await
+            .consume_error(&self.logger)?
+            .ok_or_else(|| Status::unavailable("Key collision, try again"))?;
+
+        Ok(
+            Response::new(
+                CreateApiKeyResponse {
+                    api_key: Some(api_key.into()),
+                    key,
+                }
+            )
+        )
+    }
+
+    async fn revoke_api_key(&self, request: Request<RevokeApiKeyRequest>) -> Result<Response<RevokeApiKeyResponse>, Status> {
+        request.authorize(&self.logger, &self.service_factory, &[scopes::API_KEYS_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        if !self.service_factory.api_key().revoke(&request_data.id).await.consume_error(&self.logger)? {
+            return Err(Status::not_found("Api key does not exists"));
+        }
+
+        Ok(Response::new(RevokeApiKeyResponse {}))
+    }
+
+    async fn rebuild_balances(&self, request: Request<RebuildBalancesRequest>) -> Result<Response<RebuildBalancesResponse>, Status> {
+        request.authorize(&self.logger, &self.service_factory, &[scopes::BALANCES_REBUILD]).await?;
+        let request_data = request.get_ref();
+
+        let report = self.service_factory
+            .transaction()
+            .rebuild_balances(request_data.registry_id, request_data.repair)
+            .await
+            .consume_error(&self.logger)?
+            .ok_or_else(|| Status::not_found("Registry not found"))?;
+
+        Ok(
+            Response::new(
+                RebuildBalancesResponse {
+                    report: Some(report.into()),
+                }
+            )
+        )
+    }
+
+    async fn rebuild_user_balance(&self, request: Request<RebuildUserBalanceRequest>) -> Result<Response<RebuildUserBalanceResponse>, Status> {
+        request.authorize(&self.logger, &self.service_factory, &[scopes::BALANCES_REBUILD]).await?;
+        let request_data = request.get_ref();
+
+        if self.service_factory.user().find_id(request_data.user_id).await.consume_error(&self.logger)?.is_none() {
+            return Err(Status::not_found("User does not exists"));
+        }
+
+        let balance = self.service_factory
+            .transaction()
+            .refresh_user_balance(request_data.user_id)
+            .await
+            .consume_error(&self.logger)?
+            .ok_or_else(|| Status::unavailable("Balance is changing, try again"))?;
+
+        Ok(
+            Response::new(
+                RebuildUserBalanceResponse {
+                    balance: balance
+                        .into_iter()
+                        .map(|(key, value)| (key, value.into()))
+                        .collect(),
+                }
+            )
+        )
+    }
+
+    async fn backfill_phone_hashes(&self, request: Request<BackfillPhoneHashesRequest>) -> Result<Response<BackfillPhoneHashesResponse>, Status> {
+        request.authorize(&self.logger, &self.service_factory, &[scopes::CONTACTS_BACKFILL]).await?;
+        let request_data = request.get_ref();
+
+        if request_data.limit <= 0 {
+            return Err(Status::invalid_argument("Limit must be positive"));
+        }
+
+        let last_user_id = if request_data.last_user_id == 0 {
+            None
+        }
+        else {
+            Some(request_data.last_user_id)
+        };
+
+        let (registered, next) = self.service_factory
+            .contact()
+            .backfill_phone_hashes(last_user_id, request_data.limit)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(
+            Response::new(
+                BackfillPhoneHashesResponse {
+                    registered,
+                    last_user_id: next.unwrap_or(0),
+                }
+            )
+        )
+    }
+}
+
+impl From<ApiKeyModel> for ApiKeyResource {
+    fn from(model: ApiKeyModel) -> Self {
+        Self {
+            id: model.id,
+            user_id: model.user_id,
+            name: model.name,
+            scopes: model.scopes,
+            created_at: model.created_at,
+        }
+    }
+}
+
+impl From<BalanceDriftModel> for BalanceDriftResource {
+    fn from(model: BalanceDriftModel) -> Self {
+        Self {
+            user_id: model.user_id,
+            currency: model.currency,
+            stored: Some(model.stored.into()),
+            expected: Some(model.expected.into()),
+            repaired: model.repaired,
+        }
+    }
+}
+
+impl From<BalanceReportModel> for BalanceReportResource {
+    fn from(model: BalanceReportModel) -> Self {
+        let payload = match model.fault {
+            None => balance_report_resource::Payload::Valid(
+                balance_report_resource::Valid {}
+            ),
+            Some(ChainFaultModel::BrokenLink(pack, sequence)) => balance_report_resource::Payload::BrokenLink(
+                balance_report_resource::BrokenLink { pack, sequence: sequence as i32 }
+            ),
+            Some(ChainFaultModel::MissingSequence(pack, sequence)) => balance_report_resource::Payload::MissingSequence(
+                balance_report_resource::MissingSequence { pack, sequence: sequence as i32 }
+            ),
+            Some(ChainFaultModel::PackGap(pack)) => balance_report_resource::Payload::PackGap(
+                balance_report_resource::PackGap { pack }
+            ),
+        };
+
+        Self {
+            registry_id: model.registry_id,
+            replayed: model.replayed,
+            last_pack: model.last_pack,
+            last_sequence: model.last_sequence as i32,
+            drifts: model.drifts.into_iter().map(BalanceDriftResource::from).collect(),
+            payload: Some(payload),
+        }
+    }
+}
\ No newline at end of file
@@ -17,111 +17,102 @@ use api_auth::{
 };
 use tonic::{Request, Response, Status};
 
-use crate::{domain::{ServiceFactory, codes::{CodeSendModel, CodeAttemptModel}}, logging::Logger};
+use crate::{grpc::ProxiesConfig, domain::{ServiceFactory, codes::{CodeSendModel, CodeAttemptModel}, rate_limits::RateLimitModel, tokens::{RefreshStateModel, SessionDeviceModel, SessionModel, PublicKeyModel, scopes}}, logging::Logger};
 
 use self::api_auth::{
     SendCodePhoneResponse, 
     SendCodePhoneRequest, 
+    SendCodeEmailRequest, 
+    SendCodeEmailResponse, 
+    SignInEmailRequest, 
+    SignInEmailResponse, 
     SendCodeResultResource, 
     send_code_result_resource, 
     sign_in_result_resource::{Fail, Absent, Retry}, 
     CreateGenericAccessTokenRequest, 
     CreateGenericAccessTokenResponse,
+    LogoutRequest, 
+    LogoutResponse, 
+    LogoutAllRequest, 
+    LogoutAllResponse,
+    ListSessionsRequest, 
+    ListSessionsResponse, 
+    RevokeSessionRequest, 
+    RevokeSessionResponse, 
+    SessionResource,
+    ListKeysRequest, 
+    ListKeysResponse, 
+    KeyResource,
 };
 
-use super::extensions::StatusResult;
+use super::extensions::{StatusResult, RefreshRequest, AuthorizedRequest, SessionRequest, rate_limited};
 
 #[derive(Debug)]
 pub struct AuthGrpcService {
     logger: Arc<Logger>,
     service_factory: Arc<ServiceFactory>,
+    proxies: Arc<ProxiesConfig>,
 }
 
 impl AuthGrpcService {
     pub fn new(
         logger: Arc<Logger>,
         service_factory: Arc<ServiceFactory>,
+        proxies: Arc<ProxiesConfig>,
     ) -> Self {
         Self {
             logger,
             service_factory,
+            proxies,
         }
     }
-}
 
-#[tonic::async_trait]
-impl Auth for AuthGrpcService {
-    async fn send_code_phone(&self, request: Request<SendCodePhoneRequest>) -> Result<Response<SendCodePhoneResponse>, Status> {
-        let request_data = request.get_ref();
-        
-        let service = self.service_factory.code();
-        
-        let result = service
-            .send_phone(request_data.phone)
-            .await
-            .consume_error(&self.logger)?;
+    async fn limit_phone(&self, action: &str, ip: &str, phone: i64) -> Result<(), Status> {
+        let service = self.service_factory.rate_limit();
 
-        let payload = match result {
-            CodeSendModel::Success(timeout, valid) => send_code_result_resource::Payload::Success(
-                send_code_result_resource::Success {
-                    timeout_until: timeout,
-                    valid_until: valid,
-                }
-            ),
-            CodeSendModel::Timeout(timeout) => send_code_result_resource::Payload::Timeout(
-                send_code_result_resource::Timeout {
-                    timeout_until: timeout,
-                },
-            ),
-            CodeSendModel::Fail => send_code_result_resource::Payload::Fail(
-                send_code_result_resource::Fail {
-                }
-            ),
-            CodeSendModel::Retry => send_code_result_resource::Payload::Retry(
-                send_code_result_resource::Retry {
-                }
-            )
-        };
+        if let RateLimitModel::Limited(retry_after) = service.take_ip(action, ip).await.consume_error(&self.logger)? {
+            return Err(rate_limited(retry_after));
+        }
 
-        Ok(
-            Response::new(
-                SendCodePhoneResponse { 
-                    result: Some(SendCodeResultResource { payload: Some(payload) })
-                }
-            )
-        )
+        if let RateLimitModel::Limited(retry_after) = service.take_phone(action, phone).await.consume_error(&self.logger)? {
+            return Err(rate_limited(retry_after));
+        }
+
+        Ok(())
     }
 
+    async fn limit_email(&self, action: &str, ip: &str, email: &str) -> Result<(), Status> {
+        let service = self.service_factory.rate_limit();
 
-    async fn sign_in_phone(&self, request: Request<SignInPhoneRequest>) -> Result<Response<SignInPhoneResponse>, Status> {
-        let request_data = request.get_ref();
-        
-        let code_service = self.service_factory.code();
+        if let RateLimitModel::Limited(retry_after) = service.take_ip(action, ip).await.consume_error(&self.logger)? {
+            return Err(rate_limited(retry_after));
+        }
 
-        let attempt_result = code_service.attempt_phone(
-            request_data.phone, 
-            request_data.code,
-        ).await.consume_error(&self.logger)?;
+        if let RateLimitModel::Limited(retry_after) = service.take_email(action, email).await.consume_error(&self.logger)? {
+            return Err(rate_limited(retry_after));
+        }
 
+        Ok(())
+    }
+
+    async fn sign_in_payload(
+        &self, 
+        attempt_result: CodeAttemptModel, 
+        id_option: Option<i64>, 
+        device: SessionDeviceModel,
+    ) -> Result<Payload, Status> {
         let payload = match attempt_result {
             CodeAttemptModel::Success => {
-                let user_service = self.service_factory.user();
-
-                let id_option = user_service
-                    .get_id_phone(request_data.phone)
-                    .await
-                    .consume_error(&self.logger)?;
-
                 if let Some(id) = id_option {
                     let token_service = self.service_factory.token();
 
                     let (refresh_token, refresh_expires_at) = token_service
-                        .create_refresh(id)
+                        .create_refresh(id, device)
                         .await
                         .consume_error(&self.logger)?;
 
                     let (access_token, access_expires_at) = token_service
-                        .create_access(id)
+                        .create_access(id, token_service.full_scopes(id))
                         .consume_error(&self.logger)?;
 
                     Payload::Success(Success {
@@ -145,6 +136,87 @@ impl Auth for AuthGrpcService {
                 Retry {},
             ),
         };
+
+        Ok(payload)
+    }
+}
+
+#[tonic::async_trait]
+impl Auth for AuthGrpcService {
+    async fn send_code_phone(&self, request: Request<SendCodePhoneRequest>) -> Result<Response<SendCodePhoneResponse>, Status> {
+        let request_data = request.get_ref();
+
+        self.limit_phone("send_code_phone", &request.session_ip(&self.proxies), request_data.phone).await?;
+        
+        let service = self.service_factory.code();
+        
+        let result = service
+            .send_phone(request_data.phone)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(
+            Response::new(
+                SendCodePhoneResponse { 
+                    result: Some(result.into())
+                }
+            )
+        )
+    }
+
+    async fn send_code_email(&self, request: Request<SendCodeEmailRequest>) -> Result<Response<SendCodeEmailResponse>, Status> {
+        let request_data = request.get_ref();
+        let email = request_data.email.trim().to_lowercase();
+
+        self.limit_email("send_code_email", &request.session_ip(&self.proxies), &email).await?;
+        
+        let service = self.service_factory.code();
+        
+        let result = service
+            .send_email(&email)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(
+            Response::new(
+                SendCodeEmailResponse { 
+                    result: Some(result.into())
+                }
+            )
+        )
+    }
+
+
+    async fn sign_in_phone(&self, request: Request<SignInPhoneRequest>) -> Result<Response<SignInPhoneResponse>, Status> {
+        let request_data = request.get_ref();
+
+        self.limit_phone("sign_in_phone", &request.session_ip(&self.proxies), request_data.phone).await?;
+        
+        let code_service = self.service_factory.code();
+
+        let attempt_result = code_service.attempt_phone(
+            request_data.phone, 
+            request_data.code,
+        ).await.consume_error(&self.logger)?;
+
+        let id_option = match attempt_result {
+            CodeAttemptModel::Success => self.service_factory.user()
+                .get_id_phone(request_data.phone)
+                .await
+                .consume_error(&self.logger)?,
+            _ => None,
+        };
+
+        // Signing in keeps the hash of existing users matchable by their contacts
+        if let Some(id) = id_option {
+            self.service_factory
+                .contact()
+                .register_phone(id, request_data.phone)
+                .await
+                .consume_error(&self.logger)?;
+        }
+
+        let payload = self.sign_in_payload(attempt_result, id_option, request.session_device(&self.proxies)).await?;
         
         Ok(
             Response::new(
@@ -155,41 +227,198 @@ impl Auth for AuthGrpcService {
         )
     }
 
+    async fn sign_in_email(&self, request: Request<SignInEmailRequest>) -> Result<Response<SignInEmailResponse>, Status> {
+        let request_data = request.get_ref();
+        let email = request_data.email.trim().to_lowercase();
+
+        self.limit_email("sign_in_email", &request.session_ip(&self.proxies), &email).await?;
+        
+        let code_service = self.service_factory.code();
+
+        let attempt_result = code_service.attempt_email(
+            &email, 
+            request_data.code,
+        ).await.consume_error(&self.logger)?;
+
+        let id_option = match attempt_result {
+            CodeAttemptModel::Success => self.service_factory.user()
+                .get_id_email(&email)
+                .await
+                .consume_error(&self.logger)?,
+            _ => None,
+        };
+
+        let payload = self.sign_in_payload(attempt_result, id_option, request.session_device(&self.proxies)).await?;
+        
+        Ok(
+            Response::new(
+                SignInEmailResponse { 
+                    result: Some(SignInResultResource { payload: Some(payload) }),
+                }
+            )
+        )
+    }
+
     async fn create_generic_access_token(
         &self, 
         request: Request<CreateGenericAccessTokenRequest>
     ) -> Result<Response<CreateGenericAccessTokenResponse>, Status> {
-        if let Some(auth_metadata) = request.metadata().get("authorization") {
-            if let Ok(str) = auth_metadata.to_str() {
-                if let Some(token) = str.strip_prefix("refresh ") {
-                    let token_service = self.service_factory.token();
-                    
-                    if let Some(user_id) = token_service.find_refresh(token).await.consume_error(&self.logger)? {
-                        let (token, expires_at) = token_service.create_access(user_id).consume_error(&self.logger)?;
-
-                        Ok(
-                            Response::new(
-                                CreateGenericAccessTokenResponse { 
-                                    token,
-                                    expires_at,
-                                }
-                            )
-                        )
-                    }
-                    else {
-                        Err(Status::unauthenticated("Token does not exists"))
-                    }
+        let request_data = request.get_ref();
+
+        let unknown = request_data.scopes
+            .iter()
+            .find(|scope| !scopes::ALL.contains(&scope.as_str()) && !scopes::ADMIN.contains(&scope.as_str()));
+
+        if let Some(scope) = unknown {
+            return Err(Status::invalid_argument(format!("Unknown scope {}", scope)));
+        }
+
+        let refresh_token = request.refresh_token()?;
+
+        let token_service = self.service_factory.token();
+
+        let (user_id, refresh_token, refresh_expires_at) = match token_service.use_refresh(refresh_token, request.session_ip(&self.proxies)).await.consume_error(&self.logger)? {
+            RefreshStateModel::Valid(user_id) => (user_id, String::new(), 0),
+            RefreshStateModel::Rotated(user_id, token, expires_at) => (user_id, token, expires_at),
+            RefreshStateModel::Absent => return Err(Status::unauthenticated("Token does not exists")),
+            RefreshStateModel::Reused => return Err(Status::unauthenticated("Token was already used")),
+        };
+
+        // Token without requested scopes is not restricted, scopes beyond the user grant are dropped
+        let granted = token_service.full_scopes(user_id);
+        let scopes = if request_data.scopes.is_empty() {
+            granted
+        }
+        else {
+            request_data.scopes
+                .iter()
+                .filter(|scope| granted.contains(scope))
+                .cloned()
+                .collect()
+        };
+
+        let (token, expires_at) = token_service.create_access(user_id, scopes).consume_error(&self.logger)?;
+
+        Ok(
+            Response::new(
+                CreateGenericAccessTokenResponse { 
+                    token,
+                    expires_at,
+                    refresh_token,
+                    refresh_expires_at,
                 }
-                else {
-                    Err(Status::unauthenticated("Invalid token type"))
+            )
+        )
+    }
+
+    async fn logout(&self, request: Request<LogoutRequest>) -> Result<Response<LogoutResponse>, Status> {
+        let refresh_token = request.refresh_token()?;
+
+        let token_service = self.service_factory.token();
+
+        if !token_service.revoke_refresh(refresh_token).await.consume_error(&self.logger)? {
+            return Err(Status::unauthenticated("Token does not exists"));
+        }
+
+        Ok(Response::new(LogoutResponse {}))
+    }
+
+    async fn logout_all(&self, request: Request<LogoutAllRequest>) -> Result<Response<LogoutAllResponse>, Status> {
+        let refresh_token = request.refresh_token()?;
+
+        let token_service = self.service_factory.token();
+
+        if !token_service.revoke_all_refresh(refresh_token).await.consume_error(&self.logger)? {
+            return Err(Status::unauthenticated("Token does not exists"));
+        }
+
+        Ok(Response::new(LogoutAllResponse {}))
+    }
+
+    async fn list_sessions(&self, request: Request<ListSessionsRequest>) -> Result<Response<ListSessionsResponse>, Status> {
+        let token_service = self.service_factory.token();
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::SESSIONS_READ]).await?;
+
+        let sessions = token_service.list_sessions(principal.user_id).await.consume_error(&self.logger)?;
+
+        Ok(Response::new(ListSessionsResponse {
+            sessions: sessions.into_iter().map(|model| model.into()).collect(),
+        }))
+    }
+
+    async fn revoke_session(&self, request: Request<RevokeSessionRequest>) -> Result<Response<RevokeSessionResponse>, Status> {
+        let token_service = self.service_factory.token();
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::SESSIONS_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        if !token_service.revoke_session(principal.user_id, &request_data.id).await.consume_error(&self.logger)? {
+            return Err(Status::not_found("Session not found"));
+        }
+
+        Ok(Response::new(RevokeSessionResponse {}))
+    }
+
+    async fn list_keys(&self, _: Request<ListKeysRequest>) -> Result<Response<ListKeysResponse>, Status> {
+        let token_service = self.service_factory.token();
+
+        Ok(Response::new(ListKeysResponse {
+            keys: token_service.list_keys().into_iter().map(|model| model.into()).collect(),
+        }))
+    }
+}
+
+impl From<CodeSendModel> for SendCodeResultResource {
+    fn from(model: CodeSendModel) -> Self {
+        let payload = match model {
+            CodeSendModel::Success(timeout, valid) => send_code_result_resource::Payload::Success(
+                send_code_result_resource::Success {
+                    timeout_until: timeout,
+                    valid_until: valid,
                 }
-            }
-            else {
-                Err(Status::unauthenticated("Invalid authorization header format"))
-            }
+            ),
+            CodeSendModel::Timeout(timeout) => send_code_result_resource::Payload::Timeout(
+                send_code_result_resource::Timeout {
+                    timeout_until: timeout,
+                },
+            ),
+            CodeSendModel::Fail => send_code_result_resource::Payload::Fail(
+                send_code_result_resource::Fail {
+                }
+            ),
+            CodeSendModel::Retry => send_code_result_resource::Payload::Retry(
+                send_code_result_resource::Retry {
+                }
+            )
+        };
+
+        Self {
+            payload: Some(payload),
         }
-        else {
-            Err(Status::unauthenticated("No authorization was present"))
+    }
+}
+
+impl From<SessionModel> for SessionResource {
+    fn from(model: SessionModel) -> Self {
+        Self {
+            id: model.id,
+            device_name: model.device_name,
+            user_agent: model.user_agent,
+            ip: model.ip,
+            created_at: model.created_at,
+            last_used_at: model.last_used_at,
+        }
+    }
+}
+
+impl From<PublicKeyModel> for KeyResource {
+    fn from(model: PublicKeyModel) -> Self {
+        Self {
+            kid: model.kid,
+            kty: String::from("EC"),
+            crv: String::from("P-384"),
+            alg: String::from("ES384"),
+            x: model.x,
+            y: model.y,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,179 @@
+pub mod api_contacts {
+    tonic::include_proto!("api_core.contacts");
+}
+
+pub use api_contacts::contacts_server::ContactsServer;
+use tonic::{Request, Response, Status};
+
+use std::sync::Arc;
+
+use crate::{domain::{ServiceFactory, contacts::{ContactModel, ContactSendModel, ContactMatchModel}, tokens::scopes, rate_limits::RateLimitModel}, logging::Logger};
+
+use self::api_contacts::{
+    contacts_server::Contacts, 
+    send_response, 
+    SendRequest, 
+    SendResponse, 
+    AcceptRequest, 
+    AcceptResponse, 
+    RemoveRequest, 
+    RemoveResponse, 
+    ListRequest, 
+    ListResponse, 
+    MatchPhonesRequest, 
+    MatchPhonesResponse, 
+    ContactResource, 
+    MatchResource,
+};
+
+use super::extensions::{StatusResult, AuthorizedRequest, rate_limited};
+
+const MAX_MATCH_HASHES: usize = 256;
+const HASH_LENGTH: usize = 32;
+
+#[derive(Debug)]
+pub struct ContactsGrpcService {
+    logger: Arc<Logger>,
+    service_factory: Arc<ServiceFactory>,
+}
+
+impl ContactsGrpcService {
+    pub fn new(
+        logger: Arc<Logger>,
+        service_factory: Arc<ServiceFactory>,
+    ) -> Self {
+        Self {
+            logger,
+            service_factory,
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Contacts for ContactsGrpcService {
+    async fn send(&self, request: Request<SendRequest>) -> Result<Response<SendResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::CONTACTS_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        if request_data.user_id == principal.user_id {
+            return Err(Status::invalid_argument("Contact must be another user"));
+        }
+
+        let result = self.service_factory
+            .contact()
+            .send(principal.user_id, request_data.user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        let payload = match result {
+            ContactSendModel::Sent => send_response::Payload::Sent(send_response::Sent {}),
+            ContactSendModel::Accepted => send_response::Payload::Accepted(send_response::Accepted {}),
+            ContactSendModel::Exists => send_response::Payload::Exists(send_response::Exists {}),
+            ContactSendModel::Absent => send_response::Payload::Absent(send_response::Absent {}),
+            ContactSendModel::Retry => send_response::Payload::Retry(send_response::Retry {}),
+        };
+
+        Ok(Response::new(SendResponse {
+            payload: Some(payload),
+        }))
+    }
+
+    async fn accept(&self, request: Request<AcceptRequest>) -> Result<Response<AcceptResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::CONTACTS_WRITE]).await?;
+
+        let accepted = self.service_factory
+            .contact()
+            .accept(principal.user_id, request.get_ref().user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(Response::new(AcceptResponse {
+            accepted,
+        }))
+    }
+
+    async fn remove(&self, request: Request<RemoveRequest>) -> Result<Response<RemoveResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::CONTACTS_WRITE]).await?;
+
+        let removed = self.service_factory
+            .contact()
+            .remove(principal.user_id, request.get_ref().user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(Response::new(RemoveResponse {
+            removed,
+        }))
+    }
+
+    async fn list(&self, request: Request<ListRequest>) -> Result<Response<ListResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::CONTACTS_READ]).await?;
+        let request_data = request.get_ref();
+
+        if request_data.limit <= 0 || request_data.limit > 64 {
+            return Err(Status::invalid_argument("Limit must in [1:64]"));
+        }
+
+        let contacts = self.service_factory
+            .contact()
+            .list(principal.user_id, request_data.last_user_id, request_data.limit)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(Response::new(ListResponse {
+            contacts: contacts.into_iter().map(|model| model.into()).collect(),
+        }))
+    }
+
+    async fn match_phones(&self, request: Request<MatchPhonesRequest>) -> Result<Response<MatchPhonesResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::CONTACTS_READ]).await?;
+        let request_data = request.get_ref();
+
+        if request_data.hashes.is_empty() || request_data.hashes.len() > MAX_MATCH_HASHES {
+            return Err(Status::invalid_argument(format!("Hashes count must be in [1:{}]", MAX_MATCH_HASHES)));
+        }
+
+        if request_data.hashes.iter().any(|hash| hash.len() != HASH_LENGTH) {
+            return Err(Status::invalid_argument("Hashes must be sha256 digests"));
+        }
+
+        let limit = self.service_factory
+            .rate_limit()
+            .take_lookups("match_phones", principal.user_id, request_data.hashes.len() as i64)
+            .await
+            .consume_error(&self.logger)?;
+
+        if let RateLimitModel::Limited(retry_after) = limit {
+            return Err(rate_limited(retry_after));
+        }
+
+        let matches = self.service_factory
+            .contact()
+            .match_phones(principal.user_id, &request_data.hashes)
+            .await
+            .consume_error(&self.logger)?;
+
+        Ok(Response::new(MatchPhonesResponse {
+            matches: matches.into_iter().map(|model| model.into()).collect(),
+        }))
+    }
+}
+
+impl From<ContactModel> for ContactResource {
+    fn from(model: ContactModel) -> Self {
+        Self {
+            user_id: model.contact_id,
+            state: i16::from(model.state) as i32,
+            updated_at: model.updated_at,
+        }
+    }
+}
+
+impl From<ContactMatchModel> for MatchResource {
+    fn from(model: ContactMatchModel) -> Self {
+        Self {
+            hash: model.hash,
+            user_id: model.user_id,
+        }
+    }
+}
\ No newline at end of file
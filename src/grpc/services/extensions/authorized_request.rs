@@ -1,27 +1,45 @@
-use tonic::{Request, Status};
+use tonic::{Request, Status, async_trait};
 
-use crate::{domain::tokens::{TokenService, AccessTokenModel}, logging::Logger};
+use crate::{domain::{ServiceFactory, tokens::PrincipalModel}, logging::Logger};
 
 use super::StatusResult;
 
+#[async_trait]
 pub trait AuthorizedRequest {
-    fn authorize(&self, logger: &Logger, token_service: &TokenService) -> Result<AccessTokenModel, Status>;
+    async fn authorize(&self, logger: &Logger, service_factory: &ServiceFactory, scopes: &[&str]) -> Result<PrincipalModel, Status>;
 }
 
-impl<T> AuthorizedRequest for Request<T> {
-    fn authorize(&self, logger: &Logger, token_service: &TokenService) -> Result<AccessTokenModel, Status> {
-        match self.metadata().get("authorization") {
+#[async_trait]
+impl<T: Sync> AuthorizedRequest for Request<T> {
+    async fn authorize(&self, logger: &Logger, service_factory: &ServiceFactory, scopes: &[&str]) -> Result<PrincipalModel, Status> {
+        let authorization = match self.metadata().get("authorization") {
             Some(metadata) => match metadata.to_str() {
-                Ok(str) => match str.strip_prefix("access ") {
-                    Some(token) => match token_service.decode_access(token).consume_error(&logger)? {
-                        Some(model) => Ok(model),
-                        None => Err(Status::unauthenticated("Invalid access token")),
-                    },
-                    None => Err(Status::unauthenticated("Invalid token type")),
-                },
-                Err(_) => Err(Status::unauthenticated("Invalid authorization header format")),
+                Ok(str) => str,
+                Err(_) => return Err(Status::unauthenticated("Invalid authorization header format")),
             },
-            None => Err(Status::unauthenticated("No authorization was present")),
+            None => return Err(Status::unauthenticated("No authorization was present")),
+        };
+
+        let principal: PrincipalModel = if let Some(token) = authorization.strip_prefix("access ") {
+            match service_factory.token().decode_access(token).await.consume_error(logger)? {
+                Some(model) => model.into(),
+                None => return Err(Status::unauthenticated("Invalid access token")),
+            }
+        }
+        else if let Some(key) = authorization.strip_prefix("key ") {
+            match service_factory.api_key().authenticate(key).await.consume_error(logger)? {
+                Some(model) => model.into(),
+                None => return Err(Status::unauthenticated("Invalid api key")),
+            }
         }
+        else {
+            return Err(Status::unauthenticated("Invalid token type"));
+        };
+
+        if !principal.allows(scopes) {
+            return Err(Status::permission_denied("Principal lacks required scope"));
+        }
+
+        Ok(principal)
     }
 }
\ No newline at end of file
@@ -0,0 +1,34 @@
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use tonic::Status;
+
+use super::super::api_common::DecimalResource;
+
+pub fn parse_decimal(value: &str, max_scale: i64) -> Result<BigDecimal, Status> {
+    if value.is_empty() || value.len() > 64 {
+        return Err(Status::invalid_argument("Decimal must consist of [1:64] chars"));
+    }
+
+    if value.chars().any(|c| !c.is_ascii_digit() && c != '.' && c != '-') {
+        return Err(Status::invalid_argument("Decimal must contain only digits, minus sign and decimal point"));
+    }
+
+    let decimal = BigDecimal::from_str(value)
+        .map_err(|_| Status::invalid_argument("Decimal can not be parsed"))?;
+
+    let (_, scale) = decimal.normalized().as_bigint_and_exponent();
+    if scale > max_scale {
+        return Err(Status::invalid_argument(format!("Decimal must have {} fractional digits at max", max_scale)));
+    }
+
+    Ok(decimal)
+}
+
+impl From<BigDecimal> for DecimalResource {
+    fn from(value: BigDecimal) -> Self {
+        Self {
+            value: value.to_string(),
+        }
+    }
+}
\ No newline at end of file
@@ -1,5 +1,13 @@
 mod status_result;
 mod authorized_request;
+mod refresh_request;
+mod session_request;
+mod decimal;
+mod rate_limited;
 
 pub use status_result::StatusResult;
-pub use authorized_request::AuthorizedRequest;
\ No newline at end of file
+pub use authorized_request::AuthorizedRequest;
+pub use refresh_request::RefreshRequest;
+pub use session_request::SessionRequest;
+pub use decimal::parse_decimal;
+pub use rate_limited::rate_limited;
\ No newline at end of file
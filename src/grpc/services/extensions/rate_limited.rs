@@ -0,0 +1,11 @@
+use tonic::{Status, metadata::MetadataValue};
+
+pub fn rate_limited(retry_after: i64) -> Status {
+    let mut status = Status::resource_exhausted("Too many requests");
+
+    // Seconds as in the http header, rounded up to never retry early
+    let seconds = (retry_after.max(0) + 999) / 1000;
+    status.metadata_mut().insert("retry-after", MetadataValue::from(seconds));
+
+    status
+}
\ No newline at end of file
@@ -0,0 +1,20 @@
+use tonic::{Request, Status};
+
+pub trait RefreshRequest {
+    fn refresh_token(&self) -> Result<&str, Status>;
+}
+
+impl<T> RefreshRequest for Request<T> {
+    fn refresh_token(&self) -> Result<&str, Status> {
+        match self.metadata().get("authorization") {
+            Some(metadata) => match metadata.to_str() {
+                Ok(str) => match str.strip_prefix("refresh ") {
+                    Some(token) => Ok(token),
+                    None => Err(Status::unauthenticated("Invalid token type")),
+                },
+                Err(_) => Err(Status::unauthenticated("Invalid authorization header format")),
+            },
+            None => Err(Status::unauthenticated("No authorization was present")),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::net::IpAddr;
+
+use tonic::Request;
+
+use crate::{grpc::ProxiesConfig, domain::tokens::SessionDeviceModel};
+
+const MAX_DEVICE_NAME: usize = 64;
+const MAX_USER_AGENT: usize = 256;
+
+pub trait SessionRequest {
+    fn session_ip(&self, proxies: &ProxiesConfig) -> String;
+    fn session_device(&self, proxies: &ProxiesConfig) -> SessionDeviceModel;
+}
+
+impl<T> SessionRequest for Request<T> {
+    fn session_ip(&self, proxies: &ProxiesConfig) -> String {
+        let peer = match self.remote_addr() {
+            Some(addr) => addr.ip(),
+            None => return String::new(),
+        };
+
+        if !proxies.is_trusted(&peer) {
+            return peer.to_string();
+        }
+
+        let forwarded = self.metadata()
+            .get("x-forwarded-for")
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        // Each proxy appends its peer, so the client is the last hop not added by a trusted proxy
+        let mut client = peer;
+        for hop in forwarded.rsplit(',') {
+            match hop.trim().parse::<IpAddr>() {
+                Ok(ip) => {
+                    client = ip;
+
+                    if !proxies.is_trusted(&ip) {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+
+        client.to_string()
+    }
+
+    fn session_device(&self, proxies: &ProxiesConfig) -> SessionDeviceModel {
+        SessionDeviceModel {
+            device_name: metadata_string(self, "x-device-name", MAX_DEVICE_NAME),
+            user_agent: metadata_string(self, "user-agent", MAX_USER_AGENT),
+            ip: self.session_ip(proxies),
+        }
+    }
+}
+
+fn metadata_string<T>(request: &Request<T>, key: &str, max: usize) -> String {
+    request.metadata()
+        .get(key)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.chars().take(max).collect())
+        .unwrap_or_default()
+}
\ No newline at end of file
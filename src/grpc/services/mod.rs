@@ -1,12 +1,22 @@
+pub mod api_common {
+    tonic::include_proto!("api_core.common");
+}
+
 mod extensions;
 mod auth_grpc_service;
+mod admin_grpc_service;
 mod users_grpc_service;
 mod registries_grpc_service;
 mod profile_grpc_service;
 mod transactions_grpc_service;
+mod contacts_grpc_service;
+#[cfg(test)]
+mod tests;
 
 pub use auth_grpc_service::{AuthGrpcService, AuthServer};
+pub use admin_grpc_service::{AdminGrpcService, AdminServer};
 pub use users_grpc_service::{UsersGrpcService, UsersServer};
 pub use registries_grpc_service::{RegistriesGrpcService, RegistriesServer};
 pub use profile_grpc_service::{ProfileGrpcService, ProfileServer};
-pub use transactions_grpc_service::{TransactionsGrpcService, TransactionsServer};
\ No newline at end of file
+pub use transactions_grpc_service::{TransactionsGrpcService, TransactionsServer};
+pub use contacts_grpc_service::{ContactsGrpcService, ContactsServer};
\ No newline at end of file
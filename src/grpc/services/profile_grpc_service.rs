@@ -7,13 +7,30 @@ use tonic::{Request, Response, Status};
 
 use std::sync::Arc;
 
-use crate::{domain::{ServiceFactory, registries::RegistryModel}, logging::Logger};
+use crate::{domain::{ServiceFactory, registries::RegistryModel, tokens::scopes, users::{LoginUpdateModel, EmailBindModel}, codes::CodeAttemptModel}, logging::Logger};
 
-use self::api_profile::{profile_server::Profile, ListRegistriesResponse, ListRegistriesRequest, RegistryResource};
+use self::api_profile::{
+    profile_server::Profile, 
+    ListRegistriesResponse, 
+    ListRegistriesRequest, 
+    RegistryResource, 
+    UpdateLoginRequest, 
+    UpdateLoginResponse, 
+    update_login_response, 
+    UpdateImageRequest, 
+    UpdateImageResponse, 
+    BindEmailRequest, 
+    BindEmailResponse, 
+    bind_email_response,
+    SetDiscoverableRequest, 
+    SetDiscoverableResponse,
+};
 
 use super::{extensions::{StatusResult, AuthorizedRequest}};
 
 
+const MAX_IMAGE: usize = 512;
+
 #[derive(Debug)]
 pub struct ProfileGrpcService {
     logger: Arc<Logger>,
@@ -40,12 +57,12 @@ impl Profile for ProfileGrpcService {
             return Err(Status::invalid_argument("Limit must in [1:64]"));
         }
 
-        let token = request.authorize(&self.logger, &self.service_factory.token())?;
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_READ]).await?;
 
         let registry_service = self.service_factory.registry();
 
         let registries = registry_service.list_user_registries(
-            token.sub,
+            principal.user_id,
             request_data.last_updated_at,
             request_data.limit,
         ).await.consume_error(&self.logger)?;
@@ -54,6 +71,93 @@ impl Profile for ProfileGrpcService {
             registries: registries.into_iter().map(|model| RegistryResource::from(model)).collect()
         }))
     }
+
+    async fn update_login(&self, request: Request<UpdateLoginRequest>) -> Result<Response<UpdateLoginResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::PROFILE_WRITE]).await?;
+        let login = request.get_ref().login.trim().to_lowercase();
+
+        let result = self.service_factory
+            .user()
+            .update_login(principal.user_id, &login)
+            .await
+            .consume_error(&self.logger)?;
+
+        let payload = match result {
+            LoginUpdateModel::Updated => update_login_response::Payload::Updated(update_login_response::Updated {}),
+            LoginUpdateModel::Taken => update_login_response::Payload::Taken(update_login_response::Taken {}),
+            LoginUpdateModel::Retry => update_login_response::Payload::Retry(update_login_response::Retry {}),
+            LoginUpdateModel::Invalid => return Err(Status::invalid_argument("Login must be 3 to 32 lowercase letters, digits or underscores starting with a letter")),
+            LoginUpdateModel::Absent => return Err(Status::not_found("User does not exists")),
+        };
+
+        Ok(Response::new(UpdateLoginResponse { 
+            payload: Some(payload),
+        }))
+    }
+
+    async fn update_image(&self, request: Request<UpdateImageRequest>) -> Result<Response<UpdateImageResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::PROFILE_WRITE]).await?;
+        let image = request.get_ref().image.trim();
+
+        if image.len() > MAX_IMAGE {
+            return Err(Status::invalid_argument(format!("Image must consist of {} chars at max.", MAX_IMAGE)));
+        }
+
+        if !self.service_factory.user().update_image(principal.user_id, image).await.consume_error(&self.logger)? {
+            return Err(Status::not_found("User does not exists"));
+        }
+
+        Ok(Response::new(UpdateImageResponse {}))
+    }
+
+    async fn set_discoverable(&self, request: Request<SetDiscoverableRequest>) -> Result<Response<SetDiscoverableResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::PROFILE_WRITE]).await?;
+
+        if !self.service_factory.user().update_discoverable(principal.user_id, request.get_ref().discoverable).await.consume_error(&self.logger)? {
+            return Err(Status::not_found("User does not exists"));
+        }
+
+        Ok(Response::new(SetDiscoverableResponse {}))
+    }
+
+    async fn bind_email(&self, request: Request<BindEmailRequest>) -> Result<Response<BindEmailResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::PROFILE_WRITE]).await?;
+        let request_data = request.get_ref();
+        let email = request_data.email.trim().to_lowercase();
+
+        let user_service = self.service_factory.user();
+
+        // Checked before the attempt so that a taken email does not burn the code
+        if !user_service.is_email_available(principal.user_id, &email).await.consume_error(&self.logger)? {
+            return Ok(Response::new(BindEmailResponse { 
+                payload: Some(bind_email_response::Payload::Taken(bind_email_response::Taken {})),
+            }));
+        }
+
+        let attempt_result = self.service_factory
+            .code()
+            .attempt_email(&email, request_data.code)
+            .await
+            .consume_error(&self.logger)?;
+
+        let payload = match attempt_result {
+            CodeAttemptModel::Success => match user_service.bind_email(principal.user_id, &email).await.consume_error(&self.logger)? {
+                EmailBindModel::Bound => bind_email_response::Payload::Bound(bind_email_response::Bound {}),
+                EmailBindModel::Taken => bind_email_response::Payload::Taken(bind_email_response::Taken {}),
+                EmailBindModel::Retry => bind_email_response::Payload::Retry(bind_email_response::Retry {}),
+                EmailBindModel::Absent => return Err(Status::not_found("User does not exists")),
+            },
+            CodeAttemptModel::Absent => bind_email_response::Payload::Absent(bind_email_response::Absent {}),
+            CodeAttemptModel::Fail(attempts) => bind_email_response::Payload::Fail(
+                bind_email_response::Fail { attempts_left: attempts as i32 },
+            ),
+            CodeAttemptModel::Retry => bind_email_response::Payload::Retry(bind_email_response::Retry {}),
+        };
+
+        Ok(Response::new(BindEmailResponse { 
+            payload: Some(payload),
+        }))
+    }
 }
 
 impl From<RegistryModel> for RegistryResource {
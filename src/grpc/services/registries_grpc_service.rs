@@ -2,45 +2,204 @@ pub mod api_registries {
     tonic::include_proto!("api_core.registries");
 }
 
+use super::api_common as common;
+
 pub use api_registries::registries_server::RegistriesServer;
+use bigdecimal::{BigDecimal, Zero};
 use tonic::{Request, Response, Status};
 
 use std::sync::Arc;
 
-use crate::{domain::{ServiceFactory, registries::RegistryModel}, logging::Logger};
+use crate::{
+    domain::{
+        ServiceFactory, 
+        registries::{RegistryModel, RegistryPolicyModel, BalancePolicyModel, RegistryVariantModel}, 
+        registry_users::{RegistryUserModel, RegistryUserRoleModel, MemberRemovalModel}, 
+        transactions::TransactionCompletionModel,
+        tokens::scopes,
+    }, 
+    logging::Logger, 
+    grpc::CurrenciesConfig,
+};
+
+use self::api_registries::{
+    CreateDirectRequest, 
+    CreateGroupRequest, 
+    CreateResponse, 
+    registries_server::Registries, 
+    FindRequest, 
+    FindResponse, 
+    create_response::{Payload, Retry}, 
+    RegistryResource, 
+    SetPolicyRequest, 
+    FindPolicyRequest, 
+    PolicyResponse, 
+    PolicyResource, 
+    BalancePolicyVariantResource, 
+    AddMemberRequest, 
+    AddMemberResponse, 
+    RemoveMemberRequest, 
+    LeaveRequest, 
+    RemoveMemberResponse, 
+    ListMembersRequest, 
+    ListMembersResponse, 
+    MemberResource, 
+    RegistryUserRoleResource, 
+    add_member_response, 
+    remove_member_response,
+};
 
-use self::api_registries::{CreateDirectRequest, CreateResponse, registries_server::Registries, FindRequest, FindResponse, create_response::{Payload, Retry}, RegistryResource};
+use super::extensions::{AuthorizedRequest, StatusResult, parse_decimal};
 
-use super::extensions::{AuthorizedRequest, StatusResult};
+const MAX_GROUP_MEMBERS: usize = 64;
 
 #[derive(Debug)]
 pub struct RegistriesGrpcService {
     logger: Arc<Logger>,
     service_factory: Arc<ServiceFactory>,
+    currencies: Arc<CurrenciesConfig>,
 }
 
 impl RegistriesGrpcService {
     pub fn new(
         logger: Arc<Logger>,
         service_factory: Arc<ServiceFactory>,
+        currencies: Arc<CurrenciesConfig>,
     ) -> Self {
         Self {
             logger,
             service_factory,
+            currencies,
+        }
+    }
+
+    async fn find_group(&self, registry_id: i64) -> Result<RegistryModel, Status> {
+        let registry_option = self.service_factory.registry()
+            .find(registry_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        match registry_option {
+            Some(registry) if registry.variant == RegistryVariantModel::Group => Ok(registry),
+            Some(_) => Err(Status::failed_precondition("Registry is not a group")),
+            None => Err(Status::not_found("Registry not found")),
+        }
+    }
+
+    async fn require_owner(&self, registry: &RegistryModel, user_id: i64) -> Result<(), Status> {
+        let member_option = self.service_factory.registry()
+            .find_member(registry.id, user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        match member_option {
+            Some(member) if member.role == RegistryUserRoleModel::Owner => Ok(()),
+            Some(_) => Err(Status::permission_denied("Only group owner is allowed to manage the group")),
+            None => Err(Status::permission_denied("Access denied to registry")),
+        }
+    }
+
+    async fn require_group_contact(&self, owner_user_id: i64, user_id: i64) -> Result<(), Status> {
+        let allowed = self.service_factory
+            .contact()
+            .can_add_to_group(owner_user_id, user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        if !allowed {
+            return Err(Status::failed_precondition(format!("User {} is not an accepted contact", user_id)));
         }
+
+        Ok(())
+    }
+
+    async fn remove_member(&self, registry_id: i64, user_id: i64) -> Result<Response<RemoveMemberResponse>, Status> {
+        let registry_service = self.service_factory.registry();
+
+        let member = match registry_service.find_member(registry_id, user_id).await.consume_error(&self.logger)? {
+            Some(member) => member,
+            None => return Err(Status::not_found("User is not a member of the registry")),
+        };
+
+        // Leaving member is refused by send_basic, so nothing new involves it between settling and deleting
+        if member.role != RegistryUserRoleModel::Leaving {
+            let marked = registry_service
+                .update_role(registry_id, user_id, RegistryUserRoleModel::Leaving)
+                .await
+                .consume_error(&self.logger)?;
+
+            if !marked {
+                return Ok(Response::new(RemoveMemberResponse {
+                    payload: Some(remove_member_response::Payload::Retry(remove_member_response::Retry {})),
+                }));
+            }
+        }
+
+        // Pending transaction may still change balance of the member
+        let completion = self.service_factory.transaction()
+            .complete(registry_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        let mut removal = match completion {
+            TransactionCompletionModel::Conflict => MemberRemovalModel::Conflict,
+            _ => registry_service
+                .remove_member(registry_id, user_id)
+                .await
+                .consume_error(&self.logger)?,
+        };
+
+        // Member that stays is allowed to transact again, until then the removal has to be retried
+        if let MemberRemovalModel::Conflict | MemberRemovalModel::OutstandingBalance = removal {
+            let restored = registry_service
+                .update_role(registry_id, user_id, RegistryUserRoleModel::Member)
+                .await
+                .consume_error(&self.logger)?;
+
+            if !restored {
+                removal = MemberRemovalModel::Conflict;
+            }
+        }
+
+        let payload = match removal {
+            MemberRemovalModel::Removed => remove_member_response::Payload::Removed(remove_member_response::Removed {}),
+            MemberRemovalModel::Conflict => remove_member_response::Payload::Retry(remove_member_response::Retry {}),
+            MemberRemovalModel::OutstandingBalance => remove_member_response::Payload::OutstandingBalance(
+                remove_member_response::OutstandingBalance {},
+            ),
+            MemberRemovalModel::NotMember => return Err(Status::not_found("User is not a member of the registry")),
+        };
+
+        Ok(Response::new(RemoveMemberResponse {
+            payload: Some(payload),
+        }))
     }
 }
 
 #[tonic::async_trait]
 impl Registries for RegistriesGrpcService {
     async fn create_direct(&self, request: Request<CreateDirectRequest>) -> Result<Response<CreateResponse>, Status> {
-        let access_token = request.authorize(&self.logger, &self.service_factory.token())?;
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_WRITE]).await?;
         let request_data = request.get_ref();
 
+        if request_data.user_id == principal.user_id {
+            return Err(Status::invalid_argument("Direct registry must be created with another user"));
+        }
+
+        let allowed = self.service_factory
+            .contact()
+            .can_create_direct(principal.user_id, request_data.user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        if !allowed {
+            return Err(Status::failed_precondition("User is not an accepted contact"));
+        }
+
         let registry_service = self.service_factory.registry();
 
         let model_option = registry_service.create_direct(
-            access_token.sub, 
+            principal.user_id, 
             request_data.user_id, 
             request_data.name.clone(), 
             request_data.image.clone(),
@@ -58,11 +217,44 @@ impl Registries for RegistriesGrpcService {
         }))
     }
 
+    async fn create_group(&self, request: Request<CreateGroupRequest>) -> Result<Response<CreateResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        if request_data.user_ids.len() >= MAX_GROUP_MEMBERS {
+            return Err(Status::invalid_argument(format!("Group must consist of {} members at max.", MAX_GROUP_MEMBERS)));
+        }
+
+        for &user_id in request_data.user_ids.iter().filter(|&&user_id| user_id != principal.user_id) {
+            self.require_group_contact(principal.user_id, user_id).await?;
+        }
+
+        let registry_service = self.service_factory.registry();
+
+        let model_option = registry_service.create_group(
+            principal.user_id, 
+            &request_data.user_ids, 
+            request_data.name.clone(), 
+            request_data.image.clone(),
+        ).await.consume_error(&self.logger)?;
+
+        let payload = match model_option {
+            Some(model) => {
+                Payload::Registry(model.into())
+            }
+            None => Payload::Retry(Retry {})
+        };
+
+        Ok(Response::new(CreateResponse {
+            payload: Some(payload),
+        }))
+    }
+
     async fn find(&self, request: Request<FindRequest>) -> Result<Response<FindResponse>, Status> {
-        let access_token = request.authorize(&self.logger, &self.service_factory.token())?;
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_READ]).await?;
         let request_data = request.get_ref();
         let registry_id = request_data.id;
-        let user_id = access_token.sub;
+        let user_id = principal.user_id;
 
         let registry_service = self.service_factory.registry();
 
@@ -81,6 +273,195 @@ impl Registries for RegistriesGrpcService {
             Err(Status::not_found("Registry not found"))
         }
     }   
+
+    async fn set_policy(&self, request: Request<SetPolicyRequest>) -> Result<Response<PolicyResponse>, Status> {
+        let request_data = request.get_ref();
+
+        if request_data.currency.chars().any(|c| !c.is_ascii_alphabetic()) {
+            return Err(Status::invalid_argument("Currency must contain only alphabetic ascii chars"));
+        }
+
+        if request_data.currency.chars().count() > 8 {
+            return Err(Status::invalid_argument("Currency must consist of 8 chars at max."));
+        }
+
+        let balance = match BalancePolicyVariantResource::from_i32(request_data.variant) {
+            Some(BalancePolicyVariantResource::Unlimited) => BalancePolicyModel::Unlimited,
+            Some(BalancePolicyVariantResource::NonNegative) => BalancePolicyModel::NonNegative,
+            Some(BalancePolicyVariantResource::CreditLimit) => {
+                let limit = match request_data.credit_limit.as_ref() {
+                    Some(limit) => parse_decimal(&limit.value, self.currencies.max_scale(&request_data.currency))?,
+                    None => return Err(Status::invalid_argument("Credit limit must be specified")),
+                };
+
+                if limit < BigDecimal::zero() {
+                    return Err(Status::invalid_argument("Credit limit must not be negative"));
+                }
+
+                BalancePolicyModel::CreditLimit(limit)
+            },
+            _ => return Err(Status::invalid_argument("Invalid policy variant")),
+        };
+
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_WRITE]).await?;
+
+        let registry_service = self.service_factory.registry();
+
+        let registry = match registry_service.find(request_data.registry_id).await.consume_error(&self.logger)? {
+            Some(registry) => registry,
+            None => return Err(Status::not_found("Registry not found")),
+        };
+
+        if registry.variant == RegistryVariantModel::Group {
+            self.require_owner(&registry, principal.user_id).await?;
+        }
+        else if !registry_service.access(registry.id, &[principal.user_id]).await.consume_error(&self.logger)? {
+            return Err(Status::permission_denied("Access denied to registry"));
+        }
+        else {
+            // Either member of a direct registry may be the debtor, so neither can loosen the policy alone
+            let current = registry_service
+                .find_policy(registry.id, request_data.currency.clone())
+                .await
+                .consume_error(&self.logger)?;
+
+            if !balance.tightens(&current.balance) {
+                return Err(Status::permission_denied("Direct registry policy can only be tightened"));
+            }
+        }
+
+        let policy = registry_service.set_policy(
+            request_data.registry_id, 
+            request_data.currency.clone(), 
+            balance,
+        ).await.consume_error(&self.logger)?;
+
+        Ok(Response::new(PolicyResponse {
+            policy: Some(policy.into()),
+        }))
+    }
+
+    async fn find_policy(&self, request: Request<FindPolicyRequest>) -> Result<Response<PolicyResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_READ]).await?;
+        let request_data = request.get_ref();
+
+        let registry_service = self.service_factory.registry();
+
+        if !registry_service.access(request_data.registry_id, &[principal.user_id]).await.consume_error(&self.logger)? {
+            return Err(Status::permission_denied("Access denied to registry"));
+        }
+
+        let policy = registry_service.find_policy(
+            request_data.registry_id, 
+            request_data.currency.clone(),
+        ).await.consume_error(&self.logger)?;
+
+        Ok(Response::new(PolicyResponse {
+            policy: Some(policy.into()),
+        }))
+    }
+
+    async fn add_member(&self, request: Request<AddMemberRequest>) -> Result<Response<AddMemberResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        let registry = self.find_group(request_data.registry_id).await?;
+        self.require_owner(&registry, principal.user_id).await?;
+
+        let registry_service = self.service_factory.registry();
+
+        let members = registry_service.list_members(registry.id).await.consume_error(&self.logger)?;
+        if members.len() >= MAX_GROUP_MEMBERS {
+            return Err(Status::failed_precondition(format!("Group must consist of {} members at max.", MAX_GROUP_MEMBERS)));
+        }
+
+        if members.iter().any(|member| member.user_id == request_data.user_id) {
+            return Err(Status::already_exists("User is already a member of the registry"));
+        }
+
+        self.require_group_contact(principal.user_id, request_data.user_id).await?;
+
+        let retry = Response::new(AddMemberResponse {
+            payload: Some(add_member_response::Payload::Retry(add_member_response::Retry {})),
+        });
+
+        // Member joins after the last transaction, so it has to be settled first
+        let completion = self.service_factory.transaction()
+            .complete(registry.id)
+            .await
+            .consume_error(&self.logger)?;
+
+        if let TransactionCompletionModel::Conflict = completion {
+            return Ok(retry);
+        }
+
+        let registry = self.find_group(registry.id).await?;
+
+        let member_option = registry_service.add_member(
+            &registry, 
+            request_data.user_id,
+        ).await.consume_error(&self.logger)?;
+
+        match member_option {
+            Some(member) => Ok(Response::new(AddMemberResponse {
+                payload: Some(add_member_response::Payload::Member(member.into())),
+            })),
+            None => Ok(retry),
+        }
+    }
+
+    async fn remove_member(&self, request: Request<RemoveMemberRequest>) -> Result<Response<RemoveMemberResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        let registry = self.find_group(request_data.registry_id).await?;
+        self.require_owner(&registry, principal.user_id).await?;
+
+        if request_data.user_id == principal.user_id {
+            return Err(Status::failed_precondition("Owner cannot be removed from the group"));
+        }
+
+        RegistriesGrpcService::remove_member(self, registry.id, request_data.user_id).await
+    }
+
+    async fn leave(&self, request: Request<LeaveRequest>) -> Result<Response<RemoveMemberResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_WRITE]).await?;
+        let request_data = request.get_ref();
+
+        let registry = self.find_group(request_data.registry_id).await?;
+
+        let member_option = self.service_factory.registry()
+            .find_member(registry.id, principal.user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        match member_option {
+            Some(member) if member.role == RegistryUserRoleModel::Owner => {
+                return Err(Status::failed_precondition("Owner cannot leave the group"));
+            },
+            Some(_) => (),
+            None => return Err(Status::permission_denied("Access denied to registry")),
+        }
+
+        RegistriesGrpcService::remove_member(self, registry.id, principal.user_id).await
+    }
+
+    async fn list_members(&self, request: Request<ListMembersRequest>) -> Result<Response<ListMembersResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::REGISTRIES_READ]).await?;
+        let request_data = request.get_ref();
+
+        let registry_service = self.service_factory.registry();
+
+        if !registry_service.access(request_data.registry_id, &[principal.user_id]).await.consume_error(&self.logger)? {
+            return Err(Status::permission_denied("Access denied to registry"));
+        }
+
+        let members = registry_service.list_members(request_data.registry_id).await.consume_error(&self.logger)?;
+
+        Ok(Response::new(ListMembersResponse {
+            members: members.into_iter().map(|model| model.into()).collect(),
+        }))
+    }
 }
 
 impl From<RegistryModel> for RegistryResource {
@@ -96,4 +477,50 @@ impl From<RegistryModel> for RegistryResource {
             image: model.image,
         }
     }
+}
+
+impl From<RegistryPolicyModel> for PolicyResource {
+    fn from(model: RegistryPolicyModel) -> Self {
+        let (variant, credit_limit) = match model.balance {
+            BalancePolicyModel::Invalid => (BalancePolicyVariantResource::Invalid, None),
+            BalancePolicyModel::Unlimited => (BalancePolicyVariantResource::Unlimited, None),
+            BalancePolicyModel::NonNegative => (BalancePolicyVariantResource::NonNegative, None),
+            BalancePolicyModel::CreditLimit(limit) => (
+                BalancePolicyVariantResource::CreditLimit, 
+                Some(limit.into()),
+            ),
+        };
+
+        Self {
+            registry_id: model.registry_id,
+            currency: model.currency,
+            updated_at: model.updated_at,
+            variant: variant as i32,
+            credit_limit,
+        }
+    }
+}
+
+impl From<RegistryUserModel> for MemberResource {
+    fn from(model: RegistryUserModel) -> Self {
+        let role = match model.role {
+            RegistryUserRoleModel::Invalid => RegistryUserRoleResource::Invalid,
+            RegistryUserRoleModel::Owner => RegistryUserRoleResource::Owner,
+            RegistryUserRoleModel::Member => RegistryUserRoleResource::Member,
+            RegistryUserRoleModel::Leaving => RegistryUserRoleResource::Leaving,
+        };
+
+        Self {
+            registry_id: model.registry_id,
+            user_id: model.user_id,
+            updated_at: model.updated_at,
+            current_pack: model.current_pack,
+            current_sequence: model.current_sequence as i32,
+            role: role as i32,
+            balance: model.balance
+                .into_iter()
+                .map(|(currency, value)| (currency, value.into()))
+                .collect(),
+        }
+    }
 }
\ No newline at end of file
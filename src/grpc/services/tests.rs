@@ -0,0 +1,443 @@
+use std::{sync::Arc, collections::HashMap, fs, net::SocketAddr, str::FromStr};
+
+use bigdecimal::BigDecimal;
+use tokio::net::TcpListener;
+use tonic::{Request, transport::Channel, metadata::MetadataValue};
+use uuid::Uuid;
+
+use crate::{
+    delivery::CodeSenderConfig,
+    domain::{
+        ServiceFactory, ServicesConfig,
+        codes::CodesConfig,
+        tokens::{TokensConfig, TokenKeyConfig},
+        registries::{RegistriesConfig, BalancePolicyConfig},
+        contacts::ContactsConfig,
+        rate_limits::{RateLimitConfig, RateLimitBackendConfig, RateBucketConfig},
+        transactions::TransactionModel,
+    },
+    grpc::{GrpcServer, ServerConfig, CurrenciesConfig, ProxiesConfig},
+    logging::Logger,
+    storage::{RepositoryFactory, MemoryContext, transactions::TransactionDto},
+};
+
+use super::{
+    api_common::DecimalResource,
+    auth_grpc_service::api_auth::{
+        auth_client::AuthClient,
+        SendCodePhoneRequest,
+        SendCodeEmailRequest,
+        SignInEmailRequest,
+        LogoutAllRequest,
+        ListSessionsRequest,
+        SignInPhoneRequest,
+        sign_in_result_resource,
+    },
+    registries_grpc_service::api_registries::{
+        registries_client::RegistriesClient,
+        CreateDirectRequest,
+        ListMembersRequest,
+        create_response,
+    },
+    transactions_grpc_service::api_transactions::{
+        transactions_client::TransactionsClient,
+        SendBasicRequest,
+        ListTransactionsRequest,
+        VerifyRegistryRequest,
+        TransactionResource,
+        send_response,
+        chain_report_resource,
+    },
+    users_grpc_service::api_users::{
+        users_client::UsersClient,
+        FindIdRequest,
+    },
+    profile_grpc_service::api_profile::{
+        profile_client::ProfileClient,
+        ListRegistriesRequest,
+    },
+};
+
+const CURRENCY: &str = "USD";
+
+struct TestServer {
+    address: SocketAddr,
+    codes_path: String,
+}
+
+struct TestUser {
+    id: i64,
+    access_token: String,
+    refresh_token: String,
+}
+
+impl TestServer {
+    async fn start() -> Self {
+        Self::start_with(|_| ()).await
+    }
+
+    async fn start_with(configure: impl FnOnce(&mut ServicesConfig)) -> Self {
+        let codes_path = std::env::temp_dir()
+            .join(format!("recoining-codes-{}.txt", Uuid::new_v4()))
+            .to_string_lossy()
+            .into_owned();
+
+        let repository_factory = RepositoryFactory::memory(&Arc::new(MemoryContext::new()));
+        let logger = Arc::new(Logger::new());
+        let mut config = services_config(&codes_path);
+        configure(&mut config);
+
+        let service_factory = Arc::new(ServiceFactory::new(config, repository_factory, &logger).unwrap());
+
+        let server_config = ServerConfig {
+            host: String::from("127.0.0.1:0"),
+            currencies: CurrenciesConfig {
+                default_max_scale: 2,
+                max_scales: HashMap::new(),
+            },
+            proxies: ProxiesConfig::default(),
+        };
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let grpc_server = GrpcServer::new(&server_config, &logger, &service_factory);
+        tokio::spawn(async move {
+            grpc_server.serve_with_listener(listener).await.unwrap();
+        });
+
+        Self {
+            address,
+            codes_path,
+        }
+    }
+
+    async fn channel(&self) -> Channel {
+        Channel::from_shared(format!("http://{}", self.address))
+            .unwrap()
+            .connect()
+            .await
+            .unwrap()
+    }
+
+    // The file sender appends "<destination> <code>" lines
+    fn last_code(&self, phone: i64) -> i64 {
+        let destination = phone.to_string();
+
+        fs::read_to_string(&self.codes_path)
+            .unwrap()
+            .lines()
+            .rev()
+            .filter_map(|line| line.split_once(' '))
+            .find(|(line_destination, _)| *line_destination == destination)
+            .map(|(_, code)| code.parse().unwrap())
+            .unwrap()
+    }
+
+    async fn sign_in(&self, phone: i64) -> TestUser {
+        let mut auth = AuthClient::new(self.channel().await);
+
+        auth.send_code_phone(SendCodePhoneRequest { phone }).await.unwrap();
+
+        let response = auth.sign_in_phone(SignInPhoneRequest {
+            phone,
+            code: self.last_code(phone),
+        }).await.unwrap().into_inner();
+
+        match response.result.and_then(|result| result.payload) {
+            Some(sign_in_result_resource::Payload::Success(success)) => TestUser {
+                id: success.user_id,
+                access_token: success.access_token,
+                refresh_token: success.refresh_token,
+            },
+            _ => panic!("Sign in failed for {}", phone),
+        }
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.codes_path);
+    }
+}
+
+impl TestUser {
+    fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        request.metadata_mut().insert(
+            "authorization",
+            MetadataValue::try_from(format!("access {}", self.access_token)).unwrap(),
+        );
+        request
+    }
+}
+
+fn services_config(codes_path: &str) -> ServicesConfig {
+    let bucket = RateBucketConfig {
+        capacity: 1000,
+        refill_interval: 1000,
+    };
+
+    ServicesConfig {
+        codes: CodesConfig {
+            attempts_phone: 3,
+            max_phone: 10000,
+            timeout_phone: 60000,
+            expiration_phone: 300000,
+            attempts_email: 3,
+            max_email: 10000,
+            timeout_email: 60000,
+            expiration_email: 300000,
+            sender_phone: CodeSenderConfig::File {
+                path: String::from(codes_path),
+            },
+            sender_email: CodeSenderConfig::Log,
+            rate_limit: RateLimitConfig {
+                backend: RateLimitBackendConfig::Memory,
+                ip: bucket.clone(),
+                phone: bucket.clone(),
+                email: bucket.clone(),
+                lookup: bucket,
+            },
+        },
+        tokens: TokensConfig {
+            keys: vec![TokenKeyConfig {
+                kid: String::from("test"),
+                private_key_path: Some(String::from(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/tokens/test_private.pem"))),
+                public_key_path: String::from(concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/tokens/test_public.pem")),
+            }],
+            active_key: String::from("test"),
+            key_reload_interval: 60000,
+            audience: String::from("test"),
+            admin_users: Vec::new(),
+            refresh_lifetime: 3600000,
+            access_lifetime: 600000,
+            refresh_rotation: true,
+        },
+        registries: RegistriesConfig {
+            default_balance_policy: BalancePolicyConfig::Unlimited,
+        },
+        contacts: ContactsConfig {
+            require_for_direct: false,
+            require_for_group: false,
+            hash_secret: String::from("test"),
+        },
+    }
+}
+
+fn decimal(value: &str) -> Option<DecimalResource> {
+    Some(DecimalResource {
+        value: String::from(value),
+    })
+}
+
+fn transaction_model(resource: &TransactionResource) -> TransactionModel {
+    TransactionModel::from(TransactionDto {
+        registry_id: resource.registry_id,
+        pack: resource.pack,
+        created_at: resource.created_at,
+        source_user_id: resource.source_user_id,
+        target_user_id: resource.target_user_id,
+        sequence: resource.sequence as i16,
+        variant: resource.variant as i16,
+        amount: BigDecimal::from_str(&resource.amount.as_ref().unwrap().value).unwrap(),
+        currency: resource.currency.clone(),
+        label: resource.label.clone(),
+        description: resource.description.clone(),
+        hash_version: resource.hash_version as i16,
+        hash: resource.hash.clone(),
+    })
+}
+
+#[tokio::test]
+async fn direct_registry_transactions_keep_balances_and_chain() {
+    let server = TestServer::start().await;
+    let alice = server.sign_in(79990000001).await;
+    let bob = server.sign_in(79990000002).await;
+
+    let mut registries = RegistriesClient::new(server.channel().await);
+    let mut transactions = TransactionsClient::new(server.channel().await);
+    let mut profile = ProfileClient::new(server.channel().await);
+    let mut users = UsersClient::new(server.channel().await);
+
+    let created = registries.create_direct(alice.request(CreateDirectRequest {
+        user_id: bob.id,
+        name: String::from("Dinner"),
+        image: String::new(),
+    })).await.unwrap().into_inner();
+
+    let registry_id = match created.payload {
+        Some(create_response::Payload::Registry(registry)) => registry.id,
+        _ => panic!("Direct registry was not created"),
+    };
+
+    let transfers = [
+        (&alice, &bob, "10.50"),
+        (&bob, &alice, "3.25"),
+        (&alice, &bob, "1"),
+    ];
+
+    for (source, target, amount) in transfers {
+        let sent = transactions.send_basic(source.request(SendBasicRequest {
+            registry_id,
+            user_id: target.id,
+            currency: String::from(CURRENCY),
+            label: String::new(),
+            description: format!("{} to {}", source.id, target.id),
+            amount: decimal(amount),
+        })).await.unwrap().into_inner();
+
+        assert!(matches!(sent.payload, Some(send_response::Payload::Success(_))));
+    }
+
+    let members = registries.list_members(bob.request(ListMembersRequest {
+        registry_id,
+    })).await.unwrap().into_inner().members;
+
+    let balances: HashMap<i64, BigDecimal> = members
+        .iter()
+        .map(|member| (member.user_id, BigDecimal::from_str(&member.balance[CURRENCY].value).unwrap()))
+        .collect();
+
+    assert_eq!(balances[&alice.id], BigDecimal::from_str("-8.25").unwrap());
+    assert_eq!(balances[&bob.id], BigDecimal::from_str("8.25").unwrap());
+
+    for user in [&alice, &bob] {
+        let found = users.find_id(user.request(FindIdRequest {
+            id: user.id,
+        })).await.unwrap().into_inner().user.unwrap();
+
+        assert_eq!(BigDecimal::from_str(&found.balance[CURRENCY].value).unwrap(), balances[&user.id]);
+    }
+
+    let mut listed = transactions.list_transactions(alice.request(ListTransactionsRequest {
+        registry_id,
+        last_pack: 0,
+        last_sequence: 0,
+        limit: 10,
+    })).await.unwrap().into_inner().transactions;
+    listed.reverse();
+
+    // Pages follow the exclusive cursor without repeating the last item
+    let mut paged = Vec::new();
+    let mut cursor = (0, 0);
+    loop {
+        let page = transactions.list_transactions(alice.request(ListTransactionsRequest {
+            registry_id,
+            last_pack: cursor.0,
+            last_sequence: cursor.1,
+            limit: 2,
+        })).await.unwrap().into_inner();
+
+        paged.extend(page.transactions.into_iter().map(|resource| resource.sequence));
+
+        match page.next {
+            Some(next) => cursor = (next.pack, next.sequence),
+            None => break,
+        }
+    }
+    paged.reverse();
+
+    assert_eq!(paged, listed.iter().map(|resource| resource.sequence).collect::<Vec<i32>>());
+
+    assert_eq!(listed.len(), transfers.len());
+
+    let mut previous = Vec::new();
+    for (index, resource) in listed.iter().enumerate() {
+        assert_eq!(resource.sequence, index as i32);
+        assert_eq!(transaction_model(resource).hash(&previous), resource.hash);
+        previous = resource.hash.clone();
+    }
+
+    let report = transactions.verify_registry(bob.request(VerifyRegistryRequest {
+        registry_id,
+        from_pack: 0,
+        from_sequence: 0,
+        to_pack: 0,
+        to_sequence: (transfers.len() - 1) as i32,
+    })).await.unwrap().into_inner().report.unwrap();
+
+    assert_eq!(report.checked, transfers.len() as i64);
+    assert!(matches!(report.payload, Some(chain_report_resource::Payload::Valid(_))));
+
+    for user in [&alice, &bob] {
+        let listed_registries = profile.list_registries(user.request(ListRegistriesRequest {
+            last_updated_at: i64::MAX,
+            limit: 10,
+        })).await.unwrap().into_inner().registries;
+
+        assert_eq!(listed_registries.iter().map(|registry| registry.id).collect::<Vec<i64>>(), vec![registry_id]);
+    }
+}
+
+#[tokio::test]
+async fn spoofed_forwarded_for_shares_peer_ip_bucket() {
+    let server = TestServer::start_with(|config| {
+        config.codes.rate_limit.ip = RateBucketConfig {
+            capacity: 2,
+            refill_interval: 3600000,
+        };
+    }).await;
+    let mut auth = AuthClient::new(server.channel().await);
+
+    // Peer is not a trusted proxy, so every forwarded address must land in its bucket
+    let mut codes = Vec::new();
+    for index in 0..3 {
+        let mut request = Request::new(SendCodePhoneRequest { phone: 79990000101 + index });
+        request.metadata_mut().insert(
+            "x-forwarded-for",
+            MetadataValue::try_from(format!("10.0.0.{}", index)).unwrap(),
+        );
+
+        codes.push(auth.send_code_phone(request).await.map(|_| ()).map_err(|status| status.code()));
+    }
+
+    assert_eq!(codes, vec![Ok(()), Ok(()), Err(tonic::Code::ResourceExhausted)]);
+}
+
+#[tokio::test]
+async fn email_destination_bucket_limits_sends_and_attempts() {
+    let server = TestServer::start_with(|config| {
+        config.codes.rate_limit.email = RateBucketConfig {
+            capacity: 1,
+            refill_interval: 3600000,
+        };
+    }).await;
+    let mut auth = AuthClient::new(server.channel().await);
+
+    // Address is normalized before it is charged, so case and spaces do not open a new bucket
+    let sent = auth.send_code_email(SendCodeEmailRequest { email: String::from("alice@example.com") }).await;
+    assert!(sent.is_ok());
+
+    let resent = auth.send_code_email(SendCodeEmailRequest { email: String::from(" Alice@Example.com") }).await;
+    assert_eq!(resent.map(|_| ()).map_err(|status| status.code()), Err(tonic::Code::ResourceExhausted));
+
+    let signed_in = auth.sign_in_email(SignInEmailRequest { email: String::from("alice@example.com"), code: 0 }).await;
+    assert!(signed_in.is_ok());
+
+    let guessed = auth.sign_in_email(SignInEmailRequest { email: String::from("alice@example.com"), code: 0 }).await;
+    assert_eq!(guessed.map(|_| ()).map_err(|status| status.code()), Err(tonic::Code::ResourceExhausted));
+}
+
+#[tokio::test]
+async fn logout_all_revokes_issued_access_tokens() {
+    let server = TestServer::start().await;
+    let mut auth = AuthClient::new(server.channel().await);
+
+    let alice = server.sign_in(79990000201).await;
+    assert!(auth.list_sessions(alice.request(ListSessionsRequest {})).await.is_ok());
+
+    let mut logout = Request::new(LogoutAllRequest {});
+    logout.metadata_mut().insert(
+        "authorization",
+        MetadataValue::try_from(format!("refresh {}", alice.refresh_token)).unwrap(),
+    );
+    auth.logout_all(logout).await.unwrap();
+
+    // Access token is still unexpired, but it was issued before the logout
+    let listed = auth.list_sessions(alice.request(ListSessionsRequest {})).await;
+    assert_eq!(listed.map(|_| ()).map_err(|status| status.code()), Err(tonic::Code::Unauthenticated));
+
+    let bob = server.sign_in(79990000202).await;
+    assert!(auth.list_sessions(bob.request(ListSessionsRequest {})).await.is_ok());
+}
@@ -2,8 +2,10 @@ pub mod api_transactions {
     tonic::include_proto!("api_core.transactions");
 }
 
+use super::api_common as common;
+
 pub use api_transactions::transactions_server::TransactionsServer;
-use bigdecimal::{BigDecimal, FromPrimitive, ToPrimitive};
+use bigdecimal::{BigDecimal, Zero};
 use tonic::{Request, Response, Status};
 
 use std::sync::Arc;
@@ -11,36 +13,48 @@ use std::sync::Arc;
 use crate::{
     domain::{
         ServiceFactory, 
-        transactions::{TransactionModel, TransactionStateModel},
+        transactions::{TransactionModel, TransactionStateModel, ChainReportModel, ChainFaultModel},
+        tokens::scopes,
     }, 
-    logging::Logger,
+    logging::Logger, 
+    grpc::CurrenciesConfig,
 };
 
 use self::api_transactions::{
     transactions_server::Transactions, 
     SendBasicRequest, 
     SendResponse, 
-    send_response::{Payload, Retry, Pending, Success}, 
-    TransactionResource,
+    send_response::{Payload, Retry, Pending, Success, InsufficientFunds}, 
+    TransactionResource, 
+    ListTransactionsRequest, 
+    ListTransactionsResponse,
+    TransactionCursorResource, 
+    VerifyRegistryRequest, 
+    VerifyRegistryResponse, 
+    ChainReportResource, 
+    chain_report_resource, 
 };
 
-use super::{extensions::{StatusResult, AuthorizedRequest}};
+use super::{extensions::{StatusResult, AuthorizedRequest, parse_decimal}};
 
 
 #[derive(Debug)]
 pub struct TransactionsGrpcService {
     logger: Arc<Logger>,
     service_factory: Arc<ServiceFactory>,
+    currencies: Arc<CurrenciesConfig>,
 }
 
 impl TransactionsGrpcService {
     pub fn new(
         logger: Arc<Logger>,
         service_factory: Arc<ServiceFactory>,
+        currencies: Arc<CurrenciesConfig>,
     ) -> Self {
         Self {
             logger,
             service_factory,
+            currencies,
         }
     }
 }
@@ -49,15 +63,6 @@ impl TransactionsGrpcService {
 impl Transactions for TransactionsGrpcService {
     async fn send_basic(&self, request: Request<SendBasicRequest>) -> Result<Response<SendResponse>, Status> {
         let request_data = request.get_ref();
-        if request_data.amount <= 0.0 {
-            return Err(Status::invalid_argument("Amount must be grater than zero"));
-        }
-
-        let amount_option = BigDecimal::from_f64(request_data.amount);
-        if amount_option.is_none() {
-            return Err(Status::invalid_argument("Amount can not be converted to decimal value"));
-        }
-        let amount = amount_option.unwrap();
 
         if request_data.currency.chars().any(|c| !c.is_ascii_alphabetic()) {
             return Err(Status::invalid_argument("Currency must contain only alphabetic ascii chars"));
@@ -67,6 +72,15 @@ impl Transactions for TransactionsGrpcService {
             return Err(Status::invalid_argument("Currency must consist of 8 chars at max."));
         }
 
+        let amount = match request_data.amount.as_ref() {
+            Some(amount) => parse_decimal(&amount.value, self.currencies.max_scale(&request_data.currency))?,
+            None => return Err(Status::invalid_argument("Amount must be specified")),
+        };
+
+        if amount <= BigDecimal::zero() {
+            return Err(Status::invalid_argument("Amount must be grater than zero"));
+        }
+
         if request_data.label.chars().any(|c| !c.is_ascii_alphabetic()) {
             return Err(Status::invalid_argument("Label must contain only alphabetic ascii chars"));
         }
@@ -75,7 +89,7 @@ impl Transactions for TransactionsGrpcService {
             return Err(Status::invalid_argument("Label must consist of 16 chars at max."));
         }
 
-        let token = request.authorize(&self.logger, &self.service_factory.token())?;
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::TRANSACTIONS_SEND]).await?;
 
         let registry_service = self.service_factory.registry();
         let registry_option = registry_service.find(
@@ -89,7 +103,7 @@ impl Transactions for TransactionsGrpcService {
         let registry_user_service = self.service_factory.registry_user();
 
         let count = registry_user_service.count(registry.id, &[
-            token.sub,
+            principal.user_id,
             request_data.user_id,
         ]).await.consume_error(&self.logger)?;
         if count != 2 {
@@ -99,7 +113,7 @@ impl Transactions for TransactionsGrpcService {
 
         let result = transaction_service.send_basic(
             &registry, 
-            token.sub,
+            principal.user_id,
             request_data.user_id,
             amount, 
             request_data.currency.clone(), 
@@ -110,6 +124,10 @@ impl Transactions for TransactionsGrpcService {
         Ok(Response::new(SendResponse { 
             payload: Some(match result {
                 TransactionStateModel::Fail => Payload::Retry(Retry {}),
+                TransactionStateModel::InsufficientFunds => Payload::InsufficientFunds(InsufficientFunds {}),
+                TransactionStateModel::MemberLeaving => {
+                    return Err(Status::failed_precondition("One of the users is leaving the registry"));
+                },
                 TransactionStateModel::Pending(transaction) => Payload::Pending(Pending {
                     transaction: Some(transaction.into()),
                 }),
@@ -119,6 +137,86 @@ impl Transactions for TransactionsGrpcService {
             }),
         }))
     }
+
+    async fn list_transactions(&self, request: Request<ListTransactionsRequest>) -> Result<Response<ListTransactionsResponse>, Status> {
+        let request_data = request.get_ref();
+        if request_data.limit <= 0 || request_data.limit > 64 {
+            return Err(Status::invalid_argument("Limit must in [1:64]"));
+        }
+
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::TRANSACTIONS_READ]).await?;
+
+        let registry_service = self.service_factory.registry();
+
+        if !registry_service.access(request_data.registry_id, &[principal.user_id]).await.consume_error(&self.logger)? {
+            return Err(Status::permission_denied("Access denied to registry"));
+        }
+
+        let registry_option = registry_service.find(
+            request_data.registry_id,
+        ).await.consume_error(&self.logger)?;
+        if registry_option.is_none() {
+            return Err(Status::not_found("Registry not found"));
+        }
+        let registry = registry_option.unwrap();
+
+        let transaction_service = self.service_factory.transaction();
+
+        // Nothing precedes the genesis transaction, so its position stands for unset
+        let last = match (request_data.last_pack, request_data.last_sequence) {
+            (0, 0) => None,
+            (pack, sequence) => Some((pack, sequence.clamp(0, i16::MAX as i32) as i16)),
+        };
+
+        let transactions = transaction_service.list(
+            &registry,
+            last,
+            request_data.limit,
+        ).await.consume_error(&self.logger)?;
+
+        let next = transactions
+            .last()
+            .filter(|transaction| transactions.len() as i32 == request_data.limit && (transaction.pack, transaction.sequence) != (0, 0))
+            .map(|transaction| TransactionCursorResource {
+                pack: transaction.pack,
+                sequence: transaction.sequence as i32,
+            });
+
+        Ok(Response::new(ListTransactionsResponse {
+            transactions: transactions.into_iter().map(TransactionResource::from).collect(),
+            next,
+        }))
+    }
+
+    async fn verify_registry(&self, request: Request<VerifyRegistryRequest>) -> Result<Response<VerifyRegistryResponse>, Status> {
+        let request_data = request.get_ref();
+        if request_data.from_pack < 0 || request_data.from_sequence < 0 || request_data.from_sequence > i16::MAX as i32 {
+            return Err(Status::invalid_argument("From position is out of range"));
+        }
+
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::TRANSACTIONS_READ]).await?;
+
+        let registry_service = self.service_factory.registry();
+
+        if !registry_service.access(request_data.registry_id, &[principal.user_id]).await.consume_error(&self.logger)? {
+            return Err(Status::permission_denied("Access denied to registry"));
+        }
+
+        let transaction_service = self.service_factory.transaction();
+
+        let report_option = transaction_service.verify_chain(
+            request_data.registry_id,
+            (request_data.from_pack, request_data.from_sequence as i16),
+            (request_data.to_pack, request_data.to_sequence.clamp(-1, i16::MAX as i32) as i16),
+        ).await.consume_error(&self.logger)?;
+
+        match report_option {
+            Some(report) => Ok(Response::new(VerifyRegistryResponse {
+                report: Some(report.into()),
+            })),
+            None => Err(Status::not_found("Registry not found")),
+        }
+    }
 }
 
 impl From<TransactionModel> for TransactionResource {
@@ -131,11 +229,39 @@ impl From<TransactionModel> for TransactionResource {
             target_user_id: model.target_user_id,
             sequence: model.sequence as i32,
             variant: i16::from(model.variant) as i32,
-            amount: model.amount.to_f64().unwrap(),
+            amount: Some(model.amount.into()),
             currency: model.currency,
             label: model.label,
             description: model.description,
+            hash_version: model.hash_version as i32,
             hash: model.hash,
         }
     }
+}
+
+impl From<ChainReportModel> for ChainReportResource {
+    fn from(model: ChainReportModel) -> Self {
+        let payload = match model.fault {
+            None => chain_report_resource::Payload::Valid(
+                chain_report_resource::Valid {}
+            ),
+            Some(ChainFaultModel::BrokenLink(pack, sequence)) => chain_report_resource::Payload::BrokenLink(
+                chain_report_resource::BrokenLink { pack, sequence: sequence as i32 }
+            ),
+            Some(ChainFaultModel::MissingSequence(pack, sequence)) => chain_report_resource::Payload::MissingSequence(
+                chain_report_resource::MissingSequence { pack, sequence: sequence as i32 }
+            ),
+            Some(ChainFaultModel::PackGap(pack)) => chain_report_resource::Payload::PackGap(
+                chain_report_resource::PackGap { pack }
+            ),
+        };
+
+        Self {
+            registry_id: model.registry_id,
+            checked: model.checked,
+            last_pack: model.last_pack,
+            last_sequence: model.last_sequence as i32,
+            payload: Some(payload),
+        }
+    }
 }
\ No newline at end of file
@@ -2,17 +2,18 @@ pub mod api_users {
     tonic::include_proto!("api_core.users");
 }
 
+use super::api_common as common;
+
 pub use api_users::users_server::UsersServer;
-use bigdecimal::ToPrimitive;
 use tonic::{Request, Response, Status};
 
 use std::{sync::Arc, collections::HashMap};
 
-use crate::{domain::{ServiceFactory, users::UserModel}, logging::Logger};
+use crate::{domain::{ServiceFactory, users::{UserModel, UserRelationModel}, tokens::scopes, rate_limits::RateLimitModel}, logging::Logger};
 
-use self::api_users::{users_server::Users, FindIdRequest, FindResponse, FindPhoneRequest, FindEmailRequest, UserResource};
+use self::api_users::{users_server::Users, FindIdRequest, FindResponse, FindPhoneRequest, FindEmailRequest, FindLoginRequest, UserResource};
 
-use super::extensions::StatusResult;
+use super::extensions::{StatusResult, AuthorizedRequest, rate_limited};
 
 
 #[derive(Debug)]
@@ -31,62 +32,145 @@ impl UsersGrpcService {
             service_factory,
         }
     }
+
+    // Lookups are what enumeration uses, so they are limited per caller
+    async fn limit_lookup(&self, user_id: i64) -> Result<(), Status> {
+        let result = self.service_factory
+            .rate_limit()
+            .take_lookup("find_user", user_id)
+            .await
+            .consume_error(&self.logger)?;
+
+        if let RateLimitModel::Limited(retry_after) = result {
+            return Err(rate_limited(retry_after));
+        }
+
+        Ok(())
+    }
+
+    async fn relation(&self, user_id: i64, model: &UserModel) -> Result<UserRelationModel, Status> {
+        if model.id == user_id {
+            return Ok(UserRelationModel::Itself);
+        }
+
+        let shared = self.service_factory
+            .registry()
+            .shares_registry(user_id, model.id)
+            .await
+            .consume_error(&self.logger)?;
+
+        if shared {
+            Ok(UserRelationModel::Related)
+        }
+        else {
+            Ok(UserRelationModel::Stranger)
+        }
+    }
+
+    async fn respond(&self, user_id: i64, model_option: Option<UserModel>, lookup: bool) -> Result<Response<FindResponse>, Status> {
+        let user = match model_option {
+            Some(model) => {
+                let relation = self.relation(user_id, &model).await?;
+
+                // Hidden users are only found by those who already know them
+                if lookup && relation == UserRelationModel::Stranger && !model.discoverable {
+                    None
+                }
+                else {
+                    Some(map_user_resource(model, relation))
+                }
+            },
+            None => None,
+        };
+
+        Ok(Response::new(FindResponse { 
+            user,
+        }))
+    }
 }
 
 #[tonic::async_trait]
 impl Users for UsersGrpcService {
     async fn find_id(&self, request: Request<FindIdRequest>) -> Result<Response<FindResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::USERS_READ]).await?;
         let request_data = request.get_ref();
 
+        if request_data.id != principal.user_id {
+            self.limit_lookup(principal.user_id).await?;
+        }
+
         let user_service = self.service_factory.user();
 
         let model_option = user_service.find_id(request_data.id).await.consume_error(&self.logger)?;
 
-        Ok(Response::new(FindResponse { 
-            user: model_option.map(|model| UserResource::from(model))
-        }))
+        self.respond(principal.user_id, model_option, false).await
     }
     
     async fn find_phone(&self, request: Request<FindPhoneRequest>) -> Result<Response<FindResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::USERS_READ]).await?;
         let request_data = request.get_ref();
 
+        self.limit_lookup(principal.user_id).await?;
+
         let user_service = self.service_factory.user();
 
         let model_option = user_service.find_phone(request_data.phone).await.consume_error(&self.logger)?;
 
-        Ok(Response::new(FindResponse { 
-            user: model_option.map(|model| UserResource::from(model))
-        }))
+        self.respond(principal.user_id, model_option, true).await
     }
     
     async fn find_email(&self, request: Request<FindEmailRequest>) -> Result<Response<FindResponse>, Status> {
-        let request_data = request.get_ref();
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::USERS_READ]).await?;
+        let email = request.get_ref().email.trim().to_lowercase();
+
+        self.limit_lookup(principal.user_id).await?;
 
         let user_service = self.service_factory.user();
 
-        let model_option = user_service.find_email(&request_data.email).await.consume_error(&self.logger)?;
+        let model_option = user_service.find_email(&email).await.consume_error(&self.logger)?;
 
-        Ok(Response::new(FindResponse { 
-            user: model_option.map(|model| UserResource::from(model))
-        }))
+        self.respond(principal.user_id, model_option, true).await
+    }
+    
+    async fn find_login(&self, request: Request<FindLoginRequest>) -> Result<Response<FindResponse>, Status> {
+        let principal = request.authorize(&self.logger, &self.service_factory, &[scopes::USERS_READ]).await?;
+        let login = request.get_ref().login.trim().to_lowercase();
+
+        self.limit_lookup(principal.user_id).await?;
+
+        let user_service = self.service_factory.user();
+
+        let model_option = user_service.find_login(&login).await.consume_error(&self.logger)?;
+
+        self.respond(principal.user_id, model_option, true).await
     }
 }
 
-impl From<UserModel> for UserResource {
-    fn from(model: UserModel) -> Self {
-        let balance = HashMap::from_iter(
+// Strangers see the public profile, registry peers also see contacts, balance is private
+fn map_user_resource(model: UserModel, relation: UserRelationModel) -> UserResource {
+    let mut resource = UserResource {
+        id: model.id,
+        phone: 0,
+        email: String::new(),
+        login: model.login,
+        image: model.image,
+        balance: HashMap::new(),
+        discoverable: false,
+    };
+
+    if relation != UserRelationModel::Stranger {
+        resource.phone = model.phone;
+        resource.email = model.email;
+    }
+
+    if relation == UserRelationModel::Itself {
+        resource.discoverable = model.discoverable;
+        resource.balance = HashMap::from_iter(
             model.balance
                 .into_iter()
-                .map(|(key, value)| (key, value.to_f64().unwrap()))
+                .map(|(key, value)| (key, value.into()))
         );
-
-        Self {
-            id: model.id,
-            phone: model.phone,
-            email: model.email,
-            login: model.login,
-            image: model.image,
-            balance,
-        }
     }
+
+    resource
 }
\ No newline at end of file
@@ -1,4 +1,4 @@
-use std::error::Error;
+use std::{error::Error, time::{SystemTime, UNIX_EPOCH}};
 
 #[derive(Debug)]
 pub struct Logger {
@@ -11,6 +11,18 @@ impl Logger {
     }
 
     pub fn log_fail(&self, error: &Box<dyn Error>) {
-        eprintln!("{:?}", error);
+        eprintln!("{} FAIL {:?}", timestamp(), error);
     }
+
+    pub fn log_info(&self, message: &str) {
+        println!("{} INFO {}", timestamp(), message);
+    }
+}
+
+// Millis since epoch, lines of all instances can be merged by it
+fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or(0)
 }
\ No newline at end of file
@@ -4,17 +4,20 @@ mod grpc;
 mod storage;
 mod domain;
 mod migrations;
+mod delivery;
+mod workers;
 
 use std::sync::Arc;
 
 use config::Config;
-use storage::RepositoryFactory;
+use storage::{RepositoryFactory, StorageConfig};
 
 use crate::domain::ServiceFactory;
 use crate::grpc::GrpcServer;
 use crate::logging::Logger;
-use crate::storage::ScyllaContext;
+use crate::storage::{ScyllaContext, MemoryContext};
 use crate::migrations::migrate;
+use crate::workers::{ReconcilerWorker, KeyReloadWorker};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -22,22 +25,37 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = Config::new()?;
     println!(" DONE: {}", config.serialize());
 
-    print!("Connecting to database...");
-    let scylla_context = Arc::new(ScyllaContext::new(&config.scylla).await?);
-    println!(" DONE");
+    let repository_factory = match &config.storage {
+        StorageConfig::Scylla(scylla_config) => {
+            print!("Connecting to database...");
+            let scylla_context = Arc::new(ScyllaContext::new(scylla_config).await?);
+            println!(" DONE");
+
+            migrate(&scylla_context, &String::from("migrations")).await?;
+
+            print!("Initializing repositories...");
+            let repository_factory = RepositoryFactory::new(&scylla_context).await?;
+            println!(" DONE");
 
-    migrate(&scylla_context, &String::from("migrations")).await?;
+            repository_factory
+        },
+        StorageConfig::Memory => {
+            print!("Initializing memory repositories...");
+            let memory_context = Arc::new(MemoryContext::new());
+            let repository_factory = RepositoryFactory::memory(&memory_context);
+            println!(" DONE");
+
+            repository_factory
+        },
+    };
 
     print!("Initializing logger...");
     let logger = Arc::new(Logger::new());
     println!(" DONE");
 
-    print!("Initializing repositories...");
-    let repository_factory = RepositoryFactory::new(&scylla_context).await?;
-    println!(" DONE");
-
     print!("Initializing services...");
-    let service_factory = Arc::new(ServiceFactory::new(config.services, repository_factory)?);
+    let key_reload_interval = config.services.tokens.key_reload_interval as u64;
+    let service_factory = Arc::new(ServiceFactory::new(config.services, repository_factory, &logger)?);
     println!(" DONE");
 
     print!("Initializing grpc server...");
@@ -48,6 +66,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
     println!(" DONE");
 
+    print!("Starting reconciler...");
+    let reconciler = ReconcilerWorker::new(
+        config.reconciler,
+        Arc::clone(&logger),
+        Arc::clone(&service_factory),
+    );
+    tokio::spawn(reconciler.run());
+    println!(" DONE");
+
+    print!("Starting key reload...");
+    let key_reload = KeyReloadWorker::new(
+        key_reload_interval,
+        Arc::clone(&logger),
+        Arc::clone(&service_factory),
+    );
+    tokio::spawn(key_reload.run());
+    println!(" DONE");
+
     println!("Running server...");
     grpc_server.serve().await?;
     println!("Exiting...");
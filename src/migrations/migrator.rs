@@ -7,7 +7,10 @@ use crate::storage::ScyllaContext;
 pub async fn migrate(context: &ScyllaContext, migrations: &str) -> Result<(), Box<dyn std::error::Error>> {
     println!("Running migrations...");
 
-    for path in fs::read_dir(migrations).unwrap() {
+    let mut paths: Vec<_> = fs::read_dir(migrations).unwrap().collect();
+    paths.sort_by_key(|path| path.as_ref().map(|entry| entry.file_name()).ok());
+
+    for path in paths {
         if let Ok(entry) = path {
             let file_name = entry.file_name().into_string().unwrap();
             let id_end = file_name.find(|c: char| c != '_' && !c.is_digit(10)).expect("Migration file name must start with id");
@@ -0,0 +1,9 @@
+#[derive(Debug, Clone)]
+pub struct ApiKeyDto {
+    pub id: String,
+    pub secret_hash: Vec<u8>,
+    pub user_id: i64,
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub created_at: i64,
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use std::{error::Error, fmt};
+
+use tonic::async_trait;
+
+use super::ApiKeyDto;
+
+#[async_trait]
+pub trait ApiKeyRepository: fmt::Debug {
+    async fn create(&self, dto: &ApiKeyDto) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, id: &str) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self, id: &str) -> Result<Option<ApiKeyDto>, Box<dyn Error>>;
+}
\ No newline at end of file
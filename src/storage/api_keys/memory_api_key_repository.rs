@@ -0,0 +1,39 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{ApiKeyRepository, ApiKeyDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryApiKeyRepository {
+    api_keys: Mutex<HashMap<String, ApiKeyDto>>,
+}
+
+impl MemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for MemoryApiKeyRepository {
+    async fn create(&self, dto: &ApiKeyDto) -> Result<bool, Box<dyn Error>> {
+        let mut api_keys = self.api_keys.lock().unwrap();
+
+        if api_keys.contains_key(&dto.id) {
+            return Ok(false);
+        }
+
+        api_keys.insert(dto.id.clone(), dto.clone());
+
+        Ok(true)
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, Box<dyn Error>> {
+        Ok(self.api_keys.lock().unwrap().remove(id).is_some())
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<ApiKeyDto>, Box<dyn Error>> {
+        Ok(self.api_keys.lock().unwrap().get(id).cloned())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod api_key_dto;
+mod api_key_repository;
+mod scylla_api_key_repository;
+mod memory_api_key_repository;
+
+pub use api_key_dto::ApiKeyDto;
+pub use api_key_repository::ApiKeyRepository;
+pub use scylla_api_key_repository::ScyllaApiKeyRepository;
+pub use memory_api_key_repository::MemoryApiKeyRepository;
\ No newline at end of file
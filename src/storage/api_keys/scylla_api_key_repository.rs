@@ -0,0 +1,101 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, ApiKeyRepository, ApiKeyDto};
+
+#[derive(Debug)]
+pub struct ScyllaApiKeyRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaApiKeyRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.api_keys (
+                id,
+                secret_hash,
+                user_id,
+                name,
+                scopes,
+                created_at
+            ) values (?, ?, ?, ?, ?, ?)
+            if not exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.api_keys
+            where id = ?
+            if exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                id,
+                secret_hash,
+                user_id,
+                name,
+                scopes,
+                created_at
+            from {}.api_keys
+            where id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_delete,
+            statement_find,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for ScyllaApiKeyRepository {
+    async fn create(&self, dto: &ApiKeyDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_create, (
+            &dto.id,
+            &dto.secret_hash,
+            dto.user_id,
+            &dto.name,
+            &dto.scopes,
+            dto.created_at,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn delete(&self, id: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn find(&self, id: &str) -> Result<Option<ApiKeyDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            id,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(String, Vec<u8>, i64, Option<String>, Option<Vec<String>>, i64)>()?.map(|row| {
+            let (id, secret_hash, user_id, name, scopes, created_at) = row;
+            ApiKeyDto {
+                id,
+                secret_hash,
+                user_id,
+                name: name.unwrap_or_default(),
+                scopes: scopes.unwrap_or_default(),
+                created_at,
+            }
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,16 @@
+#[derive(Debug, Clone)]
+pub struct DirectRegistryDto {
+    pub low_user_id: i64,
+    pub high_user_id: i64,
+    pub registry_id: i64,
+}
+
+impl DirectRegistryDto {
+    pub fn new(user_id: i64, other_user_id: i64, registry_id: i64) -> Self {
+        Self {
+            low_user_id: user_id.min(other_user_id),
+            high_user_id: user_id.max(other_user_id),
+            registry_id,
+        }
+    }
+}
\ No newline at end of file
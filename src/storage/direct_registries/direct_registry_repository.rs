@@ -0,0 +1,11 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::DirectRegistryDto;
+
+#[async_trait]
+pub trait DirectRegistryRepository: fmt::Debug {
+    async fn create(&self, dto: &DirectRegistryDto) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self, low_user_id: i64, high_user_id: i64) -> Result<Option<DirectRegistryDto>, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{DirectRegistryRepository, DirectRegistryDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryDirectRegistryRepository {
+    direct_registries: Mutex<HashMap<(i64, i64), i64>>,
+}
+
+impl MemoryDirectRegistryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl DirectRegistryRepository for MemoryDirectRegistryRepository {
+    async fn create(&self, dto: &DirectRegistryDto) -> Result<bool, Box<dyn Error>> {
+        let mut direct_registries = self.direct_registries.lock().unwrap();
+        let key = (dto.low_user_id, dto.high_user_id);
+
+        if direct_registries.contains_key(&key) {
+            return Ok(false);
+        }
+
+        direct_registries.insert(key, dto.registry_id);
+
+        Ok(true)
+    }
+
+    async fn find(&self, low_user_id: i64, high_user_id: i64) -> Result<Option<DirectRegistryDto>, Box<dyn Error>> {
+        let mapped = self.direct_registries.lock().unwrap().get(&(low_user_id, high_user_id)).map(|registry_id| DirectRegistryDto {
+            low_user_id,
+            high_user_id,
+            registry_id: *registry_id,
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
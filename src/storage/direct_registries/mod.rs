@@ -0,0 +1,9 @@
+mod direct_registry_dto;
+mod direct_registry_repository;
+mod scylla_direct_registry_repository;
+mod memory_direct_registry_repository;
+
+pub use direct_registry_dto::DirectRegistryDto;
+pub use direct_registry_repository::DirectRegistryRepository;
+pub use scylla_direct_registry_repository::ScyllaDirectRegistryRepository;
+pub use memory_direct_registry_repository::MemoryDirectRegistryRepository;
\ No newline at end of file
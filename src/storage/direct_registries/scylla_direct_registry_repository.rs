@@ -0,0 +1,75 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, DirectRegistryRepository, DirectRegistryDto};
+
+#[derive(Debug)]
+pub struct ScyllaDirectRegistryRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaDirectRegistryRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.direct_registries (
+                low_user_id,
+                high_user_id,
+                registry_id
+            ) values (?, ?, ?)
+            if not exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                low_user_id,
+                high_user_id,
+                registry_id
+            from {}.direct_registries
+            where low_user_id = ?
+            and high_user_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_find,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl DirectRegistryRepository for ScyllaDirectRegistryRepository {
+    async fn create(&self, dto: &DirectRegistryDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_create, (
+            dto.low_user_id,
+            dto.high_user_id,
+            dto.registry_id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn find(&self, low_user_id: i64, high_user_id: i64) -> Result<Option<DirectRegistryDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            low_user_id,
+            high_user_id,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(i64, i64, i64)>()?.map(|row| {
+            let (low_user_id, high_user_id, registry_id) = row;
+            DirectRegistryDto {
+                low_user_id,
+                high_user_id,
+                registry_id,
+            }
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
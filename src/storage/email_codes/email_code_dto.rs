@@ -0,0 +1,8 @@
+#[derive(Debug, Clone)]
+pub struct EmailCodeDto {
+    pub email: String,
+    pub code: i64,
+    pub created_at: i64,
+    pub attempts: i16,
+    pub ttl: i32,
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::EmailCodeDto;
+
+#[async_trait]
+pub trait EmailCodeRepository: fmt::Debug {
+    async fn create(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>>;
+    async fn update_attempts(&self, dto: &EmailCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>>;
+    async fn update_created_at(&self, dto: &EmailCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self, email: &str) -> Result<Option<EmailCodeDto>, Box<dyn Error>>;
+}
\ No newline at end of file
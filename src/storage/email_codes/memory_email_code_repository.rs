@@ -0,0 +1,110 @@
+use std::{sync::{Arc, Mutex}, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use crate::storage::MemoryContext;
+
+use super::{EmailCodeRepository, EmailCodeDto};
+
+#[derive(Debug)]
+struct MemoryEmailCode {
+    code: i64,
+    created_at: i64,
+    attempts: i16,
+    expires_at: i64,
+}
+
+#[derive(Debug)]
+pub struct MemoryEmailCodeRepository {
+    memory_context: Arc<MemoryContext>,
+    codes: Mutex<HashMap<String, MemoryEmailCode>>,
+}
+
+impl MemoryEmailCodeRepository {
+    pub fn new(memory_context: Arc<MemoryContext>) -> Self {
+        Self {
+            memory_context,
+            codes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn live<'a>(&self, codes: &'a mut HashMap<String, MemoryEmailCode>, email: &str) -> Option<&'a mut MemoryEmailCode> {
+        if let Some(code) = codes.get(email) {
+            if !self.memory_context.is_alive(code.expires_at) {
+                codes.remove(email);
+            }
+        }
+
+        codes.get_mut(email)
+    }
+}
+
+#[async_trait]
+impl EmailCodeRepository for MemoryEmailCodeRepository {
+    async fn create(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        if self.live(&mut codes, &dto.email).is_some() {
+            return Ok(false);
+        }
+
+        codes.insert(dto.email.clone(), MemoryEmailCode {
+            code: dto.code,
+            created_at: dto.created_at,
+            attempts: dto.attempts,
+            expires_at: self.memory_context.expires_at(dto.ttl),
+        });
+
+        Ok(true)
+    }
+
+    async fn delete(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        match self.live(&mut codes, &dto.email) {
+            Some(code) if code.attempts == dto.attempts => {
+                codes.remove(&dto.email);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_attempts(&self, dto: &EmailCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        match self.live(&mut codes, &dto.email) {
+            Some(code) if code.attempts == dto.attempts => {
+                code.attempts = attempts;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_created_at(&self, dto: &EmailCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        match self.live(&mut codes, &dto.email) {
+            Some(code) if code.created_at == dto.created_at => {
+                code.created_at = created_at;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn find(&self, email: &str) -> Result<Option<EmailCodeDto>, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        let mapped = self.live(&mut codes, email).map(|code| EmailCodeDto {
+            email: String::from(email),
+            code: code.code,
+            created_at: code.created_at,
+            attempts: code.attempts,
+            ttl: self.memory_context.ttl(code.expires_at),
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod email_code_dto;
+mod email_code_repository;
+mod scylla_email_code_repository;
+mod memory_email_code_repository;
+
+pub use email_code_dto::EmailCodeDto;
+pub use email_code_repository::EmailCodeRepository;
+pub use scylla_email_code_repository::ScyllaEmailCodeRepository;
+pub use memory_email_code_repository::MemoryEmailCodeRepository;
\ No newline at end of file
@@ -0,0 +1,140 @@
+use std::{sync::Arc, error::Error};
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, EmailCodeRepository, EmailCodeDto};
+
+#[derive(Debug)]
+pub struct ScyllaEmailCodeRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_update_attempts: PreparedStatement,
+    statement_update_created_at: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaEmailCodeRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.email_codes (
+                email,
+                code,
+                created_at,
+                attempts
+            ) values (?, ?, ?, ?)
+            if not exists
+            using ttl ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.email_codes
+            where email = ?
+            if attempts = ?
+        ", &scylla_context.keyspace)).await?;
+
+        // Cells written with the remaining ttl expire together with the code
+        let statement_update_attempts = scylla_context.session.prepare(format!("
+            update {}.email_codes
+            using ttl ?
+            set attempts = ?
+            where email = ?
+            if attempts = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update_created_at = scylla_context.session.prepare(format!("
+            update {}.email_codes
+            using ttl ?
+            set created_at = ?
+            where email = ?
+            if created_at = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                email,
+                code,
+                created_at,
+                attempts,
+                ttl(code)
+            from {}.email_codes
+            where email = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_delete,
+            statement_update_attempts,
+            statement_update_created_at,
+            statement_find,    
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl EmailCodeRepository for ScyllaEmailCodeRepository {
+    async fn create(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_create, (
+            &dto.email, 
+            dto.code,
+            dto.created_at,
+            dto.attempts,
+            dto.ttl,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn delete(&self, dto: &EmailCodeDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            &dto.email,
+            dto.attempts,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_attempts(&self, dto: &EmailCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_attempts, (
+            dto.ttl,
+            attempts,
+            &dto.email,
+            dto.attempts,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_created_at(&self, dto: &EmailCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_created_at, (
+            dto.ttl,
+            created_at,
+            &dto.email,
+            dto.created_at,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn find(&self, email: &str) -> Result<Option<EmailCodeDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            email, 
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(String, i64, i64, i16, i32)>()?.map(|row| {
+            let (email, code, created_at, attempts, ttl) = row;
+            EmailCodeDto { 
+                email, 
+                code, 
+                attempts, 
+                created_at,
+                ttl,
+            }
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+use std::{sync::atomic::{AtomicI64, Ordering}, time::{SystemTime, UNIX_EPOCH}};
+
+#[derive(Debug, Default)]
+pub struct MemoryContext {
+    offset: AtomicI64,
+}
+
+impl MemoryContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn now(&self) -> i64 {
+        let since_the_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_millis() as i64;
+
+        since_the_epoch + self.offset.load(Ordering::SeqCst)
+    }
+
+    // Moves the clock forward so that ttl expiry can be observed without sleeping
+    #[cfg(test)]
+    pub fn advance(&self, millis: i64) {
+        self.offset.fetch_add(millis, Ordering::SeqCst);
+    }
+
+    pub fn expires_at(&self, ttl: i32) -> i64 {
+        self.now() + ttl as i64 * 1000
+    }
+
+    pub fn is_alive(&self, expires_at: i64) -> bool {
+        expires_at > self.now()
+    }
+
+    pub fn ttl(&self, expires_at: i64) -> i32 {
+        ((expires_at - self.now() + 999) / 1000).max(0) as i32
+    }
+}
\ No newline at end of file
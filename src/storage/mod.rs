@@ -1,15 +1,34 @@
 mod scylla_config;
 mod scylla_context;
+mod memory_context;
+mod storage_config;
 mod repository_factory;
 pub mod id_generator;
 pub mod phone_codes;
+pub mod email_codes;
 pub mod users;
 pub mod user_tokens;
 pub mod registries;
 pub mod registry_users;
 pub mod user_registries;
 pub mod transactions;
+pub mod registry_policies;
+pub mod api_keys;
+pub mod rate_limits;
+pub mod user_logins;
+pub mod user_emails;
+pub mod user_contacts;
+pub mod phone_hashes;
+pub mod direct_registries;
+pub mod pending_registries;
+pub mod user_revocations;
+#[cfg(test)]
+pub mod simulation;
+#[cfg(test)]
+mod tests;
 
 pub use scylla_config::ScyllaConfig;
 pub use scylla_context::ScyllaContext;
+pub use memory_context::MemoryContext;
+pub use storage_config::StorageConfig;
 pub use repository_factory::RepositoryFactory;
\ No newline at end of file
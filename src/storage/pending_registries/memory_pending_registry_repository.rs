@@ -0,0 +1,53 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap, ops::Bound};
+
+use tonic::async_trait;
+
+use super::{PendingRegistryRepository, PendingRegistryDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryPendingRegistryRepository {
+    pending_registries: Mutex<BTreeMap<i64, PendingRegistryDto>>,
+}
+
+impl MemoryPendingRegistryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PendingRegistryRepository for MemoryPendingRegistryRepository {
+    async fn create(&self, dto: &PendingRegistryDto) -> Result<(), Box<dyn Error>> {
+        self.pending_registries.lock().unwrap().insert(dto.registry_id, dto.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, dto: &PendingRegistryDto) -> Result<bool, Box<dyn Error>> {
+        let mut pending_registries = self.pending_registries.lock().unwrap();
+
+        match pending_registries.get(&dto.registry_id) {
+            Some(pending) if pending.pack == dto.pack && pending.sequence == dto.sequence => {
+                pending_registries.remove(&dto.registry_id);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    // Scylla scans in token order, here ids are the order, both are stable between pages
+    async fn scan(&self, last_registry_id: Option<i64>, limit: i32) -> Result<Vec<PendingRegistryDto>, Box<dyn Error>> {
+        let pending_registries = self.pending_registries.lock().unwrap();
+
+        let start = match last_registry_id {
+            Some(last_registry_id) => Bound::Excluded(last_registry_id),
+            None => Bound::Unbounded,
+        };
+
+        let mapped = pending_registries.range((start, Bound::Unbounded))
+            .take(limit.max(0) as usize)
+            .map(|(_, pending)| pending.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
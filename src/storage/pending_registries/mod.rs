@@ -0,0 +1,9 @@
+mod pending_registry_dto;
+mod pending_registry_repository;
+mod scylla_pending_registry_repository;
+mod memory_pending_registry_repository;
+
+pub use pending_registry_dto::PendingRegistryDto;
+pub use pending_registry_repository::PendingRegistryRepository;
+pub use scylla_pending_registry_repository::ScyllaPendingRegistryRepository;
+pub use memory_pending_registry_repository::MemoryPendingRegistryRepository;
\ No newline at end of file
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct PendingRegistryDto {
+    pub registry_id: i64,
+    pub pack: i64,
+    pub sequence: i16,
+    pub created_at: i64,
+}
\ No newline at end of file
@@ -0,0 +1,13 @@
+use std::{error::Error, fmt};
+
+use tonic::async_trait;
+
+use super::PendingRegistryDto;
+
+#[async_trait]
+pub trait PendingRegistryRepository: fmt::Debug {
+    async fn create(&self, dto: &PendingRegistryDto) -> Result<(), Box<dyn Error>>;
+    // Only the entry of the same transaction is deleted, a newer pending one stays indexed
+    async fn delete(&self, dto: &PendingRegistryDto) -> Result<bool, Box<dyn Error>>;
+    async fn scan(&self, last_registry_id: Option<i64>, limit: i32) -> Result<Vec<PendingRegistryDto>, Box<dyn Error>>;
+}
\ No newline at end of file
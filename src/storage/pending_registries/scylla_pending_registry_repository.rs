@@ -0,0 +1,120 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, PendingRegistryRepository, PendingRegistryDto};
+
+type RowType = (i64, i64, i16, i64);
+
+#[derive(Debug)]
+pub struct ScyllaPendingRegistryRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_scan_first: PreparedStatement,
+    statement_scan_next: PreparedStatement,
+}
+
+impl ScyllaPendingRegistryRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let select_base = format!("
+            select
+                registry_id,
+                pack,
+                sequence,
+                created_at
+            from {}.pending_registries
+        ", &scylla_context.keyspace);
+
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.pending_registries (
+                registry_id,
+                pack,
+                sequence,
+                created_at
+            ) values (?, ?, ?, ?)
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.pending_registries
+            where registry_id = ?
+            if pack = ?
+            and sequence = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_scan_first = scylla_context.session.prepare(format!("
+            {}
+            limit ?
+        ", &select_base)).await?;
+
+        let statement_scan_next = scylla_context.session.prepare(format!("
+            {}
+            where token(registry_id) > token(?)
+            limit ?
+        ", &select_base)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_delete,
+            statement_scan_first,
+            statement_scan_next,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl PendingRegistryRepository for ScyllaPendingRegistryRepository {
+    async fn create(&self, dto: &PendingRegistryDto) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_create, (
+            dto.registry_id,
+            dto.pack,
+            dto.sequence,
+            dto.created_at,
+        )).await?;
+
+        Ok(())
+    }
+
+    async fn delete(&self, dto: &PendingRegistryDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            dto.registry_id,
+            dto.pack,
+            dto.sequence,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn scan(&self, last_registry_id: Option<i64>, limit: i32) -> Result<Vec<PendingRegistryDto>, Box<dyn Error>> {
+        let result = match last_registry_id {
+            Some(registry_id) => self.scylla_context.session.execute(&self.statement_scan_next, (
+                registry_id,
+                limit,
+            )).await?,
+            None => self.scylla_context.session.execute(&self.statement_scan_first, (
+                limit,
+            )).await?,
+        };
+
+        let mut mapped = Vec::new();
+
+        if let Some(rows) = result.rows {
+            for row in rows.into_typed::<RowType>() {
+                let (registry_id, pack, sequence, created_at) = row?;
+
+                mapped.push(PendingRegistryDto {
+                    registry_id,
+                    pack,
+                    sequence,
+                    created_at,
+                });
+            }
+        }
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,110 @@
+use std::{sync::{Arc, Mutex}, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use crate::storage::MemoryContext;
+
+use super::{PhoneCodeRepository, PhoneCodeDto};
+
+#[derive(Debug)]
+struct MemoryPhoneCode {
+    code: i64,
+    created_at: i64,
+    attempts: i16,
+    expires_at: i64,
+}
+
+#[derive(Debug)]
+pub struct MemoryPhoneCodeRepository {
+    memory_context: Arc<MemoryContext>,
+    codes: Mutex<HashMap<i64, MemoryPhoneCode>>,
+}
+
+impl MemoryPhoneCodeRepository {
+    pub fn new(memory_context: Arc<MemoryContext>) -> Self {
+        Self {
+            memory_context,
+            codes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn live<'a>(&self, codes: &'a mut HashMap<i64, MemoryPhoneCode>, phone: i64) -> Option<&'a mut MemoryPhoneCode> {
+        if let Some(code) = codes.get(&phone) {
+            if !self.memory_context.is_alive(code.expires_at) {
+                codes.remove(&phone);
+            }
+        }
+
+        codes.get_mut(&phone)
+    }
+}
+
+#[async_trait]
+impl PhoneCodeRepository for MemoryPhoneCodeRepository {
+    async fn create(&self, dto: &PhoneCodeDto) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        if self.live(&mut codes, dto.phone).is_some() {
+            return Ok(false);
+        }
+
+        codes.insert(dto.phone, MemoryPhoneCode {
+            code: dto.code,
+            created_at: dto.created_at,
+            attempts: dto.attempts,
+            expires_at: self.memory_context.expires_at(dto.ttl),
+        });
+
+        Ok(true)
+    }
+
+    async fn delete(&self, dto: &PhoneCodeDto) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        match self.live(&mut codes, dto.phone) {
+            Some(code) if code.attempts == dto.attempts => {
+                codes.remove(&dto.phone);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_attempts(&self, dto: &PhoneCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        match self.live(&mut codes, dto.phone) {
+            Some(code) if code.attempts == dto.attempts => {
+                code.attempts = attempts;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_created_at(&self, dto: &PhoneCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        match self.live(&mut codes, dto.phone) {
+            Some(code) if code.created_at == dto.created_at => {
+                code.created_at = created_at;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn find(&self, phone: i64) -> Result<Option<PhoneCodeDto>, Box<dyn Error>> {
+        let mut codes = self.codes.lock().unwrap();
+
+        let mapped = self.live(&mut codes, phone).map(|code| PhoneCodeDto {
+            phone,
+            code: code.code,
+            created_at: code.created_at,
+            attempts: code.attempts,
+            ttl: self.memory_context.ttl(code.expires_at),
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -1,7 +1,9 @@
 mod phone_code_dto;
 mod phone_code_repository;
 mod scylla_phone_code_repository;
+mod memory_phone_code_repository;
 
 pub use phone_code_dto::PhoneCodeDto;
 pub use phone_code_repository::PhoneCodeRepository;
-pub use scylla_phone_code_repository::ScyllaPhoneCodeRepository;
\ No newline at end of file
+pub use scylla_phone_code_repository::ScyllaPhoneCodeRepository;
+pub use memory_phone_code_repository::MemoryPhoneCodeRepository;
\ No newline at end of file
@@ -8,5 +8,7 @@ use super::PhoneCodeDto;
 pub trait PhoneCodeRepository: fmt::Debug {
     async fn create(&self, dto: &PhoneCodeDto) -> Result<bool, Box<dyn Error>>;
     async fn delete(&self, dto: &PhoneCodeDto) -> Result<bool, Box<dyn Error>>;
+    async fn update_attempts(&self, dto: &PhoneCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>>;
+    async fn update_created_at(&self, dto: &PhoneCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>>;
     async fn find(&self, phone: i64) -> Result<Option<PhoneCodeDto>, Box<dyn Error>>;
 }
\ No newline at end of file
@@ -9,6 +9,8 @@ pub struct ScyllaPhoneCodeRepository {
     scylla_context: Arc<ScyllaContext>,
     statement_create: PreparedStatement,
     statement_delete: PreparedStatement,
+    statement_update_attempts: PreparedStatement,
+    statement_update_created_at: PreparedStatement,
     statement_find: PreparedStatement,
 }
 
@@ -31,6 +33,23 @@ impl ScyllaPhoneCodeRepository {
             if attempts = ?
         ", &scylla_context.keyspace)).await?;
 
+        // Cells written with the remaining ttl expire together with the code
+        let statement_update_attempts = scylla_context.session.prepare(format!("
+            update {}.phone_codes
+            using ttl ?
+            set attempts = ?
+            where phone = ?
+            if attempts = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update_created_at = scylla_context.session.prepare(format!("
+            update {}.phone_codes
+            using ttl ?
+            set created_at = ?
+            where phone = ?
+            if created_at = ?
+        ", &scylla_context.keyspace)).await?;
+
         let statement_find = scylla_context.session.prepare(format!("
             select
                 phone,
@@ -46,6 +65,8 @@ impl ScyllaPhoneCodeRepository {
             scylla_context,
             statement_create,
             statement_delete,
+            statement_update_attempts,
+            statement_update_created_at,
             statement_find,    
         };
 
@@ -76,6 +97,28 @@ impl PhoneCodeRepository for ScyllaPhoneCodeRepository {
         Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
     }
 
+    async fn update_attempts(&self, dto: &PhoneCodeDto, attempts: i16) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_attempts, (
+            dto.ttl,
+            attempts,
+            dto.phone,
+            dto.attempts,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_created_at(&self, dto: &PhoneCodeDto, created_at: i64) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_created_at, (
+            dto.ttl,
+            created_at,
+            dto.phone,
+            dto.created_at,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
     async fn find(&self, phone: i64) -> Result<Option<PhoneCodeDto>, Box<dyn Error>> {
         let result = self.scylla_context.session.execute(&self.statement_find, (
             phone, 
@@ -0,0 +1,43 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{PhoneHashRepository, PhoneHashDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryPhoneHashRepository {
+    phone_hashes: Mutex<HashMap<Vec<u8>, i64>>,
+}
+
+impl MemoryPhoneHashRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl PhoneHashRepository for MemoryPhoneHashRepository {
+    async fn create(&self, dto: &PhoneHashDto) -> Result<(), Box<dyn Error>> {
+        self.phone_hashes.lock().unwrap().insert(dto.hash.clone(), dto.user_id);
+
+        Ok(())
+    }
+
+    async fn list(&self, hashes: &[Vec<u8>]) -> Result<Vec<PhoneHashDto>, Box<dyn Error>> {
+        let phone_hashes = self.phone_hashes.lock().unwrap();
+
+        let mut mapped: Vec<PhoneHashDto> = Vec::new();
+        for hash in hashes {
+            if let Some(user_id) = phone_hashes.get(hash) {
+                if !mapped.iter().any(|dto| &dto.hash == hash) {
+                    mapped.push(PhoneHashDto {
+                        hash: hash.clone(),
+                        user_id: *user_id,
+                    });
+                }
+            }
+        }
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod phone_hash_dto;
+mod phone_hash_repository;
+mod scylla_phone_hash_repository;
+mod memory_phone_hash_repository;
+
+pub use phone_hash_dto::PhoneHashDto;
+pub use phone_hash_repository::PhoneHashRepository;
+pub use scylla_phone_hash_repository::ScyllaPhoneHashRepository;
+pub use memory_phone_hash_repository::MemoryPhoneHashRepository;
\ No newline at end of file
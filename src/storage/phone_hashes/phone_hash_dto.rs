@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct PhoneHashDto {
+    pub hash: Vec<u8>,
+    pub user_id: i64,
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::PhoneHashDto;
+
+#[async_trait]
+pub trait PhoneHashRepository: fmt::Debug {
+    async fn create(&self, dto: &PhoneHashDto) -> Result<(), Box<dyn Error>>;
+    async fn list(&self, hashes: &[Vec<u8>]) -> Result<Vec<PhoneHashDto>, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,72 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, PhoneHashRepository, PhoneHashDto};
+
+#[derive(Debug)]
+pub struct ScyllaPhoneHashRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_list: PreparedStatement,
+}
+
+impl ScyllaPhoneHashRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.phone_hashes (
+                hash,
+                user_id
+            ) values (?, ?)
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_list = scylla_context.session.prepare(format!("
+            select
+                hash,
+                user_id
+            from {}.phone_hashes
+            where hash in ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_list,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl PhoneHashRepository for ScyllaPhoneHashRepository {
+    async fn create(&self, dto: &PhoneHashDto) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_create, (
+            &dto.hash,
+            dto.user_id,
+        )).await?;
+
+        Ok(())
+    }
+
+    async fn list(&self, hashes: &[Vec<u8>]) -> Result<Vec<PhoneHashDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_list, (
+            hashes,
+        )).await?;
+
+        let mut mapped = Vec::new();
+
+        if let Some(rows) = result.rows {
+            for row in rows.into_typed::<(Vec<u8>, i64)>() {
+                let (hash, user_id) = row?;
+                mapped.push(PhoneHashDto {
+                    hash,
+                    user_id,
+                });
+            }
+        }
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
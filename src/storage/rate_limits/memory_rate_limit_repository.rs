@@ -0,0 +1,38 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{RateLimitRepository, RateLimitBucketDto};
+
+const MAX_BUCKETS: usize = 100_000;
+
+#[derive(Debug, Default)]
+pub struct MemoryRateLimitRepository {
+    buckets: Mutex<HashMap<String, RateLimitBucketDto>>,
+}
+
+impl MemoryRateLimitRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RateLimitRepository for MemoryRateLimitRepository {
+    async fn take(&self, key: &str, count: i64, capacity: i64, refill_interval: i64, now: i64) -> Result<Option<i64>, Box<dyn Error>> {
+        let mut buckets = self.buckets.lock().unwrap();
+
+        // Buckets that are full again carry no state and can be dropped
+        if buckets.len() >= MAX_BUCKETS {
+            buckets.retain(|_, bucket| bucket.tokens + (now - bucket.updated_at) / refill_interval < capacity);
+        }
+
+        let bucket = buckets
+            .entry(String::from(key))
+            .or_insert_with(|| RateLimitBucketDto::full(capacity, now));
+
+        bucket.refill(capacity, refill_interval, now);
+
+        Ok(bucket.take(count, refill_interval, now))
+    }
+}
\ No newline at end of file
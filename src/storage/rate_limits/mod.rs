@@ -0,0 +1,9 @@
+mod rate_limit_bucket_dto;
+mod rate_limit_repository;
+mod scylla_rate_limit_repository;
+mod memory_rate_limit_repository;
+
+pub use rate_limit_bucket_dto::RateLimitBucketDto;
+pub use rate_limit_repository::RateLimitRepository;
+pub use scylla_rate_limit_repository::ScyllaRateLimitRepository;
+pub use memory_rate_limit_repository::MemoryRateLimitRepository;
\ No newline at end of file
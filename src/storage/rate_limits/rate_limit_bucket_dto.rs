@@ -0,0 +1,38 @@
+#[derive(Debug, Clone)]
+pub struct RateLimitBucketDto {
+    pub tokens: i64,
+    pub updated_at: i64,
+}
+
+impl RateLimitBucketDto {
+    pub fn full(capacity: i64, now: i64) -> Self {
+        Self {
+            tokens: capacity,
+            updated_at: now,
+        }
+    }
+
+    // Adds tokens earned since the last update, a full bucket restarts its interval
+    pub fn refill(&mut self, capacity: i64, refill_interval: i64, now: i64) {
+        let refilled = (now - self.updated_at).max(0) / refill_interval;
+        if self.tokens + refilled >= capacity {
+            self.tokens = capacity;
+            self.updated_at = now;
+        }
+        else {
+            self.tokens += refilled;
+            self.updated_at += refilled * refill_interval;
+        }
+    }
+
+    // Takes the tokens, returns millis until enough of them are refilled when the bucket is short
+    pub fn take(&mut self, count: i64, refill_interval: i64, now: i64) -> Option<i64> {
+        if self.tokens < count {
+            return Some(self.updated_at + (count - self.tokens) * refill_interval - now);
+        }
+
+        self.tokens -= count;
+
+        None
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use std::{error::Error, fmt};
+
+use tonic::async_trait;
+
+#[async_trait]
+pub trait RateLimitRepository: fmt::Debug {
+    // Takes count tokens from the bucket, returns millis until they are available when it is short
+    async fn take(&self, key: &str, count: i64, capacity: i64, refill_interval: i64, now: i64) -> Result<Option<i64>, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,107 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, QueryResult};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, RateLimitRepository, RateLimitBucketDto};
+
+// Concurrent takes of one key race on the same row, the losers read it again
+const MAX_TAKE_ATTEMPTS: usize = 3;
+
+#[derive(Debug)]
+pub struct ScyllaRateLimitRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_find: PreparedStatement,
+    statement_insert: PreparedStatement,
+    statement_update: PreparedStatement,
+}
+
+impl ScyllaRateLimitRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_find = scylla_context.session.prepare(format!("
+            select tokens, updated_at
+            from {}.rate_limits
+            where key = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_insert = scylla_context.session.prepare(format!("
+            insert into {}.rate_limits (
+                key,
+                tokens,
+                updated_at
+            ) values (?, ?, ?)
+            if not exists
+            using ttl ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update = scylla_context.session.prepare(format!("
+            update {}.rate_limits
+            using ttl ?
+            set tokens = ?, updated_at = ?
+            where key = ?
+            if tokens = ? and updated_at = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_find,
+            statement_insert,
+            statement_update,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl RateLimitRepository for ScyllaRateLimitRepository {
+    async fn take(&self, key: &str, count: i64, capacity: i64, refill_interval: i64, now: i64) -> Result<Option<i64>, Box<dyn Error>> {
+        // Row expires once the bucket would be full again, so idle keys leave nothing behind
+        let ttl = ((capacity * refill_interval) / 1000 + 1) as i32;
+
+        for _ in 0..MAX_TAKE_ATTEMPTS {
+            let result = self.scylla_context.session.execute(&self.statement_find, (
+                key,
+            )).await?;
+
+            let found = result
+                .maybe_first_row_typed::<(i64, i64)>()?
+                .map(|(tokens, updated_at)| RateLimitBucketDto { tokens, updated_at });
+
+            let mut bucket = found.clone().unwrap_or_else(|| RateLimitBucketDto::full(capacity, now));
+            bucket.refill(capacity, refill_interval, now);
+
+            if let Some(retry_after) = bucket.take(count, refill_interval, now) {
+                return Ok(Some(retry_after));
+            }
+
+            let result = match found {
+                Some(source) => self.scylla_context.session.execute(&self.statement_update, (
+                    ttl,
+                    bucket.tokens,
+                    bucket.updated_at,
+                    key,
+                    source.tokens,
+                    source.updated_at,
+                )).await?,
+                None => self.scylla_context.session.execute(&self.statement_insert, (
+                    key,
+                    bucket.tokens,
+                    bucket.updated_at,
+                    ttl,
+                )).await?,
+            };
+
+            if applied(result)? {
+                return Ok(None);
+            }
+        }
+
+        // Key is hammered hard enough to keep losing the race, which is what the limit is for
+        Ok(Some(refill_interval))
+    }
+}
+
+fn applied(result: QueryResult) -> Result<bool, Box<dyn Error>> {
+    Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap};
+
+use tonic::async_trait;
+
+use super::{RegistryRepository, RegistryDto, RegistryTransactionUpdateDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryRegistryRepository {
+    registries: Mutex<BTreeMap<i64, RegistryDto>>,
+}
+
+impl MemoryRegistryRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RegistryRepository for MemoryRegistryRepository {
+    async fn create(&self, dto: &RegistryDto) -> Result<bool, Box<dyn Error>> {
+        let mut registries = self.registries.lock().unwrap();
+
+        if registries.contains_key(&dto.id) {
+            return Ok(false);
+        }
+
+        registries.insert(dto.id, dto.clone());
+
+        Ok(true)
+    }
+
+    async fn update_transaction(&self, update_dto: &RegistryTransactionUpdateDto) -> Result<bool, Box<dyn Error>> {
+        let mut registries = self.registries.lock().unwrap();
+
+        match registries.get_mut(&update_dto.id) {
+            Some(registry) if registry.current_pack == update_dto.source_pack
+                && registry.current_sequence == update_dto.source_sequence
+                && registry.updated_at == update_dto.source_updated_at => {
+                registry.current_pack = update_dto.target_pack;
+                registry.current_sequence = update_dto.target_sequence;
+                registry.updated_at = update_dto.target_updated_at;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<RegistryDto>, Box<dyn Error>> {
+        Ok(self.registries.lock().unwrap().get(&id).cloned())
+    }
+
+    async fn list(&self, ids: &[i64]) -> Result<Vec<RegistryDto>, Box<dyn Error>> {
+        let registries = self.registries.lock().unwrap();
+
+        let mut mapped: Vec<RegistryDto> = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(registry) = registries.get(id) {
+                if !mapped.iter().any(|dto| dto.id == *id) {
+                    mapped.push(registry.clone());
+                }
+            }
+        }
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
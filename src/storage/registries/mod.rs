@@ -2,8 +2,10 @@ mod registry_dto;
 mod registry_transaction_update_dto;
 mod registry_repository;
 mod scylla_registry_repository;
+mod memory_registry_repository;
 
 pub use registry_dto::RegistryDto;
 pub use registry_transaction_update_dto::RegistryTransactionUpdateDto;
 pub use registry_repository::RegistryRepository;
-pub use scylla_registry_repository::ScyllaRegistryRepository;
\ No newline at end of file
+pub use scylla_registry_repository::ScyllaRegistryRepository;
+pub use memory_registry_repository::MemoryRegistryRepository;
\ No newline at end of file
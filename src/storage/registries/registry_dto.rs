@@ -1,3 +1,4 @@
+#[derive(Debug, Clone)]
 pub struct RegistryDto {
     pub id: i64,
     pub created_at: i64,
@@ -1,5 +1,5 @@
 use std::{sync::Arc, error::Error};
-use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows};
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows, QueryResult};
 use tonic::async_trait;
 
 use super::{super::ScyllaContext, RegistryRepository, RegistryDto, RegistryTransactionUpdateDto};
@@ -124,18 +124,22 @@ impl RegistryRepository for ScyllaRegistryRepository {
             ids, 
         )).await?;
 
-        if let Some(rows) = result.rows {
-            let mut dtos = Vec::new();
+        map_registry_dtos(result)
+    }
+}
 
-            for row in rows.into_typed::<RowType>() {
-                dtos.push(RegistryDto::from(row?));
-            }
+fn map_registry_dtos(result: QueryResult) -> Result<Vec<RegistryDto>, Box<dyn Error>> {
+    if let Some(rows) = result.rows {
+        let mut dtos = Vec::new();
 
-            Ok(dtos)
-        }
-        else {
-            Ok(Vec::new())
+        for row in rows.into_typed::<RowType>() {
+            dtos.push(RegistryDto::from(row?));
         }
+
+        Ok(dtos)
+    }
+    else {
+        Ok(Vec::new())
     }
 }
 
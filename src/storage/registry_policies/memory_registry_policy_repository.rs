@@ -0,0 +1,29 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap};
+
+use tonic::async_trait;
+
+use super::{RegistryPolicyRepository, RegistryPolicyDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryRegistryPolicyRepository {
+    policies: Mutex<BTreeMap<(i64, String), RegistryPolicyDto>>,
+}
+
+impl MemoryRegistryPolicyRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl RegistryPolicyRepository for MemoryRegistryPolicyRepository {
+    async fn update(&self, dto: &RegistryPolicyDto) -> Result<(), Box<dyn Error>> {
+        self.policies.lock().unwrap().insert((dto.registry_id, dto.currency.clone()), dto.clone());
+
+        Ok(())
+    }
+
+    async fn find(&self, registry_id: i64, currency: &str) -> Result<Option<RegistryPolicyDto>, Box<dyn Error>> {
+        Ok(self.policies.lock().unwrap().get(&(registry_id, String::from(currency))).cloned())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod registry_policy_dto;
+mod registry_policy_repository;
+mod scylla_registry_policy_repository;
+mod memory_registry_policy_repository;
+
+pub use registry_policy_dto::RegistryPolicyDto;
+pub use registry_policy_repository::RegistryPolicyRepository;
+pub use scylla_registry_policy_repository::ScyllaRegistryPolicyRepository;
+pub use memory_registry_policy_repository::MemoryRegistryPolicyRepository;
\ No newline at end of file
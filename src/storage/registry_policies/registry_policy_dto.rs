@@ -0,0 +1,10 @@
+use bigdecimal::BigDecimal;
+
+#[derive(Debug, Clone)]
+pub struct RegistryPolicyDto {
+    pub registry_id: i64,
+    pub currency: String,
+    pub updated_at: i64,
+    pub variant: i16,
+    pub credit_limit: Option<BigDecimal>,
+}
\ No newline at end of file
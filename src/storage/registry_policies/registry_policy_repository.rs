@@ -0,0 +1,11 @@
+use std::{error::Error, fmt};
+
+use tonic::async_trait;
+
+use super::RegistryPolicyDto;
+
+#[async_trait]
+pub trait RegistryPolicyRepository: fmt::Debug {
+    async fn update(&self, dto: &RegistryPolicyDto) -> Result<(), Box<dyn Error>>;
+    async fn find(&self, registry_id: i64, currency: &str) -> Result<Option<RegistryPolicyDto>, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,82 @@
+use std::{sync::Arc, error::Error};
+use bigdecimal::BigDecimal;
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, RegistryPolicyRepository, RegistryPolicyDto};
+
+#[derive(Debug)]
+pub struct ScyllaRegistryPolicyRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_update: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaRegistryPolicyRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_update = scylla_context.session.prepare(format!("
+            insert into {}.registry_policies (
+                registry_id,
+                currency,
+                updated_at,
+                variant,
+                credit_limit
+            ) values (?, ?, ?, ?, ?)
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                registry_id,
+                currency,
+                updated_at,
+                variant,
+                credit_limit
+            from {}.registry_policies
+            where registry_id = ?
+            and currency = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_update,
+            statement_find,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl RegistryPolicyRepository for ScyllaRegistryPolicyRepository {
+    async fn update(&self, dto: &RegistryPolicyDto) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_update, (
+            dto.registry_id,
+            &dto.currency,
+            dto.updated_at,
+            dto.variant,
+            &dto.credit_limit,
+        )).await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, registry_id: i64, currency: &str) -> Result<Option<RegistryPolicyDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            registry_id,
+            currency,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(i64, String, i64, i16, Option<BigDecimal>)>()?.map(|row| {
+            let (registry_id, currency, updated_at, variant, credit_limit) = row;
+            RegistryPolicyDto {
+                registry_id,
+                currency,
+                updated_at,
+                variant,
+                credit_limit,
+            }
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
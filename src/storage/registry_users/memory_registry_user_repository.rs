@@ -0,0 +1,127 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap};
+
+use tonic::async_trait;
+
+use super::{RegistryUserRepository, RegistryUserDto, RegistryUserUpdateDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryRegistryUserRepository {
+    registry_users: Mutex<BTreeMap<(i64, i64), RegistryUserDto>>,
+}
+
+impl MemoryRegistryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Rows of one user across registries, the source of the user_registries view
+    pub fn list_user(&self, user_id: i64) -> Vec<RegistryUserDto> {
+        self.registry_users.lock().unwrap()
+            .values()
+            .filter(|dto| dto.user_id == user_id)
+            .cloned()
+            .collect()
+    }
+}
+
+#[async_trait]
+impl RegistryUserRepository for MemoryRegistryUserRepository {
+    async fn create(&self, dtos: &[RegistryUserDto]) -> Result<bool, Box<dyn Error>> {
+        let mut registry_users = self.registry_users.lock().unwrap();
+
+        if dtos.iter().any(|dto| registry_users.contains_key(&(dto.registry_id, dto.user_id))) {
+            return Ok(false);
+        }
+
+        for dto in dtos {
+            registry_users.insert((dto.registry_id, dto.user_id), dto.clone());
+        }
+
+        Ok(true)
+    }
+
+    // Like the conditional batch, every condition is checked before anything is applied
+    async fn update(&self, dtos: &[RegistryUserUpdateDto]) -> Result<bool, Box<dyn Error>> {
+        let mut registry_users = self.registry_users.lock().unwrap();
+
+        let applies = dtos.iter().all(|dto| match registry_users.get(&(dto.registry_id, dto.user_id)) {
+            Some(registry_user) => registry_user.current_pack == dto.source_pack
+                && registry_user.current_sequence == dto.source_sequence
+                && registry_user.balance.get(&dto.currency) == dto.source_value.as_ref(),
+            None => false,
+        });
+
+        if !applies {
+            return Ok(false);
+        }
+
+        for dto in dtos {
+            let registry_user = registry_users.get_mut(&(dto.registry_id, dto.user_id)).unwrap();
+
+            registry_user.updated_at = dto.updated_at;
+            registry_user.current_pack = dto.current_pack;
+            registry_user.current_sequence = dto.current_sequence;
+            registry_user.balance.insert(dto.currency.clone(), dto.target_value.clone());
+        }
+
+        Ok(true)
+    }
+
+    async fn update_role(&self, dto: &RegistryUserDto, role: i16) -> Result<bool, Box<dyn Error>> {
+        let mut registry_users = self.registry_users.lock().unwrap();
+
+        match registry_users.get_mut(&(dto.registry_id, dto.user_id)) {
+            Some(registry_user) if registry_user.current_pack == dto.current_pack
+                && registry_user.current_sequence == dto.current_sequence => {
+                registry_user.role = role;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete(&self, dto: &RegistryUserDto) -> Result<bool, Box<dyn Error>> {
+        let mut registry_users = self.registry_users.lock().unwrap();
+        let key = (dto.registry_id, dto.user_id);
+
+        match registry_users.get(&key) {
+            Some(registry_user) if registry_user.current_pack == dto.current_pack
+                && registry_user.current_sequence == dto.current_sequence => {
+                registry_users.remove(&key);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn list(&self, registry_id: i64, user_ids: &[i64]) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
+        let registry_users = self.registry_users.lock().unwrap();
+
+        let mapped = registry_users.range((registry_id, i64::MIN)..=(registry_id, i64::MAX))
+            .filter(|((_, user_id), _)| user_ids.contains(user_id))
+            .map(|(_, registry_user)| registry_user.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+
+    async fn list_all(&self, registry_id: i64) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
+        let registry_users = self.registry_users.lock().unwrap();
+
+        let mapped = registry_users.range((registry_id, i64::MIN)..=(registry_id, i64::MAX))
+            .map(|(_, registry_user)| registry_user.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+
+    async fn count(&self, registry_id: i64, user_ids: &[i64]) -> Result<i64, Box<dyn Error>> {
+        let registry_users = self.registry_users.lock().unwrap();
+
+        let count = registry_users.range((registry_id, i64::MIN)..=(registry_id, i64::MAX))
+            .filter(|((_, user_id), _)| user_ids.contains(user_id))
+            .count();
+
+        Ok(count as i64)
+    }
+}
\ No newline at end of file
@@ -2,8 +2,10 @@ mod registry_user_dto;
 mod registry_user_update_dto;
 mod registry_user_repository;
 mod scylla_registry_user_repository;
+mod memory_registry_user_repository;
 
 pub use registry_user_dto::RegistryUserDto;
 pub use registry_user_update_dto::RegistryUserUpdateDto;
 pub use registry_user_repository::RegistryUserRepository;
-pub use scylla_registry_user_repository::ScyllaRegistryUserRepository;
\ No newline at end of file
+pub use scylla_registry_user_repository::ScyllaRegistryUserRepository;
+pub use memory_registry_user_repository::MemoryRegistryUserRepository;
\ No newline at end of file
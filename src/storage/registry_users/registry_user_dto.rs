@@ -3,23 +3,30 @@ use std::collections::HashMap;
 use bigdecimal::BigDecimal;
 
 
+#[derive(Debug, Clone)]
 pub struct RegistryUserDto {
     pub registry_id: i64,
     pub user_id: i64,
     pub updated_at: i64,
     pub current_pack: i64,
     pub current_sequence: i16,
+    pub joined_pack: i64,
+    pub joined_sequence: i16,
+    pub role: i16,
     pub balance: HashMap<String, BigDecimal>,
 }
 
 impl RegistryUserDto {
-    pub fn new(timestamp: i64, registry_id: i64, user_id: i64) -> Self {
+    pub fn new(timestamp: i64, registry_id: i64, user_id: i64, role: i16) -> Self {
         Self {
             registry_id,
             user_id,
             updated_at: timestamp,
             current_pack: 0,
             current_sequence: -1,
+            joined_pack: 0,
+            joined_sequence: -1,
+            role,
             balance: HashMap::new(),
         }
     }
@@ -8,6 +8,9 @@ use super::{RegistryUserDto, RegistryUserUpdateDto};
 pub trait RegistryUserRepository: fmt::Debug {
     async fn create(&self, dtos: &[RegistryUserDto]) -> Result<bool, Box<dyn Error>>;
     async fn update(&self, dtos: &[RegistryUserUpdateDto]) -> Result<bool, Box<dyn Error>>;
+    async fn update_role(&self, dto: &RegistryUserDto, role: i16) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, dto: &RegistryUserDto) -> Result<bool, Box<dyn Error>>;
     async fn list(&self, registry_id: i64, user_ids: &[i64]) -> Result<Vec<RegistryUserDto>, Box<dyn Error>>;
+    async fn list_all(&self, registry_id: i64) -> Result<Vec<RegistryUserDto>, Box<dyn Error>>;
     async fn count(&self, registry_id: i64, user_ids: &[i64]) -> Result<i64, Box<dyn Error>>;
 }
\ No newline at end of file
@@ -1,16 +1,24 @@
 use std::{sync::Arc, error::Error, collections::HashMap};
 use bigdecimal::BigDecimal;
-use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, batch::{Batch, BatchType}, IntoTypedRows};
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, batch::{Batch, BatchType}, IntoTypedRows, QueryResult};
 use tonic::async_trait;
 
 use super::{super::ScyllaContext, RegistryUserRepository, RegistryUserDto, RegistryUserUpdateDto};
 
+type RowType = (i64, i64, i64, i64, i16, Option<i64>, Option<i16>, Option<i16>, Option<HashMap<String, BigDecimal>>);
+
+// Members created before roles were introduced are plain members
+const DEFAULT_ROLE: i16 = 2;
+
 #[derive(Debug)]
 pub struct ScyllaRegistryUserRepository {
     scylla_context: Arc<ScyllaContext>,
     statement_create: PreparedStatement,
     statement_update: PreparedStatement,
+    statement_update_role: PreparedStatement,
+    statement_delete: PreparedStatement,
     statement_list: PreparedStatement,
+    statement_list_all: PreparedStatement,
     statement_count: PreparedStatement,
 }
 
@@ -23,8 +31,11 @@ impl ScyllaRegistryUserRepository {
                 updated_at,
                 current_pack,
                 current_sequence,
+                joined_pack,
+                joined_sequence,
+                role,
                 balance
-            ) values (?, ?, ?, ?, ?, ?)
+            ) values (?, ?, ?, ?, ?, ?, ?, ?, ?)
             if not exists
         ", &scylla_context.keyspace)).await?;
 
@@ -37,21 +48,52 @@ impl ScyllaRegistryUserRepository {
                 balance[?] = ?
             where registry_id = ?
             and user_id = ?
-            if balance[?] = ?;
+            if balance[?] = ?
+            and current_pack = ?
+            and current_sequence = ?;
         ", &scylla_context.keyspace)).await?;
 
-        let statement_list = scylla_context.session.prepare(format!("
+        let statement_update_role = scylla_context.session.prepare(format!("
+            update {}.registry_users
+            set role = ?
+            where registry_id = ?
+            and user_id = ?
+            if current_pack = ?
+            and current_sequence = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.registry_users
+            where registry_id = ?
+            and user_id = ?
+            if current_pack = ?
+            and current_sequence = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let select_base = format!("
             select
                 registry_id,
                 user_id,
                 updated_at,
                 current_pack,
                 current_sequence,
+                joined_pack,
+                joined_sequence,
+                role,
                 balance
             from {}.registry_users
+        ", &scylla_context.keyspace);
+
+        let statement_list = scylla_context.session.prepare(format!("
+            {}
             where registry_id = ?
             and user_id in ?
-        ", &scylla_context.keyspace)).await?;
+        ", &select_base)).await?;
+
+        let statement_list_all = scylla_context.session.prepare(format!("
+            {}
+            where registry_id = ?
+        ", &select_base)).await?;
 
         let statement_count = scylla_context.session.prepare(format!("
             select count(1)
@@ -64,7 +106,10 @@ impl ScyllaRegistryUserRepository {
             scylla_context,
             statement_create,
             statement_update,
+            statement_update_role,
+            statement_delete,
             statement_list,    
+            statement_list_all,
             statement_count,
         };
 
@@ -86,6 +131,9 @@ impl RegistryUserRepository for ScyllaRegistryUserRepository {
                 dto.updated_at,
                 dto.current_pack,
                 dto.current_sequence,
+                dto.joined_pack,
+                dto.joined_sequence,
+                dto.role,
                 &dto.balance,
             ));
         }
@@ -111,6 +159,8 @@ impl RegistryUserRepository for ScyllaRegistryUserRepository {
                 dto.user_id,
                 &dto.currency,
                 &dto.source_value,
+                dto.source_pack,
+                dto.source_sequence,
             ));
         }
         
@@ -119,41 +169,44 @@ impl RegistryUserRepository for ScyllaRegistryUserRepository {
         Ok(result.first_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
     }
 
+    async fn update_role(&self, dto: &RegistryUserDto, role: i16) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_role, (
+            role,
+            dto.registry_id,
+            dto.user_id,
+            dto.current_pack,
+            dto.current_sequence,
+        )).await?;
+
+        Ok(result.first_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn delete(&self, dto: &RegistryUserDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            dto.registry_id,
+            dto.user_id,
+            dto.current_pack,
+            dto.current_sequence,
+        )).await?;
+
+        Ok(result.first_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
     async fn list(&self, registry_id: i64, user_ids: &[i64]) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
         let result = self.scylla_context.session.execute(&self.statement_list, (
             registry_id,
             user_ids, 
         )).await?;
 
-        if let Some(rows) = result.rows {
-            let mut mapped = Vec::new();
+        map_registry_user_dtos(result)
+    }
 
-            for row in rows.into_typed::<(i64, i64, i64, i64, i16, Option<HashMap<String, BigDecimal>>)>() {
-                let (
-                    registry_id,
-                    user_id,
-                    updated_at,
-                    current_pack,
-                    current_sequence,
-                    balance,
-                ) = row?; 
-
-                mapped.push(
-                    RegistryUserDto {
-                        registry_id,
-                        user_id,
-                        updated_at,
-                        current_pack,
-                        current_sequence,
-                        balance: balance.unwrap_or(HashMap::new()),
-                    }
-                );
-            }
-
-            return Ok(mapped);
-        }
+    async fn list_all(&self, registry_id: i64) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_list_all, (
+            registry_id,
+        )).await?;
 
-        Ok(Vec::new())
+        map_registry_user_dtos(result)
     }
 
     async fn count(&self, registry_id: i64, user_ids: &[i64]) -> Result<i64, Box<dyn Error>> {
@@ -165,4 +218,43 @@ impl RegistryUserRepository for ScyllaRegistryUserRepository {
         let (count, ) = result.single_row_typed::<(i64,)>()?;
         Ok(count)
     }
+}
+
+fn map_registry_user_dtos(result: QueryResult) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
+    if let Some(rows) = result.rows {
+        let mut mapped = Vec::new();
+
+        for row in rows.into_typed::<RowType>() {
+            let (
+                registry_id,
+                user_id,
+                updated_at,
+                current_pack,
+                current_sequence,
+                joined_pack,
+                joined_sequence,
+                role,
+                balance,
+            ) = row?; 
+
+            mapped.push(
+                RegistryUserDto {
+                    registry_id,
+                    user_id,
+                    updated_at,
+                    current_pack,
+                    current_sequence,
+                    // Members joined before joins were recorded are replayed from the start
+                    joined_pack: joined_pack.unwrap_or(0),
+                    joined_sequence: joined_sequence.unwrap_or(-1),
+                    role: role.unwrap_or(DEFAULT_ROLE),
+                    balance: balance.unwrap_or(HashMap::new()),
+                }
+            );
+        }
+
+        return Ok(mapped);
+    }
+
+    Ok(Vec::new())
 }
\ No newline at end of file
@@ -1,25 +1,48 @@
 use std::{sync::Arc, error::Error};
 
 use super::{
-    phone_codes::{PhoneCodeRepository, ScyllaPhoneCodeRepository}, 
+    phone_codes::{PhoneCodeRepository, ScyllaPhoneCodeRepository, MemoryPhoneCodeRepository}, 
+    email_codes::{EmailCodeRepository, ScyllaEmailCodeRepository, MemoryEmailCodeRepository}, 
     ScyllaContext, 
-    users::{UserRepository, ScyllaUserRepository}, 
-    user_tokens::{UserTokenRepository, ScyllaUserTokenRepository}, 
-    registries::{RegistryRepository, ScyllaRegistryRepository}, 
-    registry_users::{RegistryUserRepository, ScyllaRegistryUserRepository}, 
-    user_registries::{UserRegistryRepository, ScyllaUserRegistryRepository}, 
-    transactions::{TransactionRepository, ScyllaTransactionRepository}
+    MemoryContext,
+    users::{UserRepository, ScyllaUserRepository, MemoryUserRepository}, 
+    user_tokens::{UserTokenRepository, ScyllaUserTokenRepository, MemoryUserTokenRepository}, 
+    registries::{RegistryRepository, ScyllaRegistryRepository, MemoryRegistryRepository}, 
+    registry_users::{RegistryUserRepository, ScyllaRegistryUserRepository, MemoryRegistryUserRepository}, 
+    user_registries::{UserRegistryRepository, ScyllaUserRegistryRepository, MemoryUserRegistryRepository}, 
+    transactions::{TransactionRepository, ScyllaTransactionRepository, MemoryTransactionRepository}, 
+    registry_policies::{RegistryPolicyRepository, ScyllaRegistryPolicyRepository, MemoryRegistryPolicyRepository},
+    api_keys::{ApiKeyRepository, ScyllaApiKeyRepository, MemoryApiKeyRepository},
+    rate_limits::{RateLimitRepository, ScyllaRateLimitRepository, MemoryRateLimitRepository},
+    user_logins::{UserLoginRepository, ScyllaUserLoginRepository, MemoryUserLoginRepository},
+    user_emails::{UserEmailRepository, ScyllaUserEmailRepository, MemoryUserEmailRepository},
+    user_contacts::{UserContactRepository, ScyllaUserContactRepository, MemoryUserContactRepository},
+    phone_hashes::{PhoneHashRepository, ScyllaPhoneHashRepository, MemoryPhoneHashRepository},
+    direct_registries::{DirectRegistryRepository, ScyllaDirectRegistryRepository, MemoryDirectRegistryRepository},
+    pending_registries::{PendingRegistryRepository, ScyllaPendingRegistryRepository, MemoryPendingRegistryRepository},
+    user_revocations::{UserRevocationRepository, ScyllaUserRevocationRepository, MemoryUserRevocationRepository},
 };
 
 #[derive(Debug)]
 pub struct RepositoryFactory {
     phone_code_repository: Arc<dyn PhoneCodeRepository + Sync + Send>,
+    email_code_repository: Arc<dyn EmailCodeRepository + Sync + Send>,
     user_repository: Arc<dyn UserRepository + Sync + Send>,
     user_token_repository: Arc<dyn UserTokenRepository + Sync + Send>,
     registry_repository: Arc<dyn RegistryRepository + Sync + Send>,
     registry_user_repository: Arc<dyn RegistryUserRepository + Sync + Send>,
     user_registry_repository: Arc<dyn UserRegistryRepository + Sync + Send>,
     transaction_repository: Arc<dyn TransactionRepository + Sync + Send>,
+    registry_policy_repository: Arc<dyn RegistryPolicyRepository + Sync + Send>,
+    api_key_repository: Arc<dyn ApiKeyRepository + Sync + Send>,
+    rate_limit_repository: Arc<dyn RateLimitRepository + Sync + Send>,
+    user_login_repository: Arc<dyn UserLoginRepository + Sync + Send>,
+    user_email_repository: Arc<dyn UserEmailRepository + Sync + Send>,
+    user_contact_repository: Arc<dyn UserContactRepository + Sync + Send>,
+    phone_hash_repository: Arc<dyn PhoneHashRepository + Sync + Send>,
+    direct_registry_repository: Arc<dyn DirectRegistryRepository + Sync + Send>,
+    pending_registry_repository: Arc<dyn PendingRegistryRepository + Sync + Send>,
+    user_revocation_repository: Arc<dyn UserRevocationRepository + Sync + Send>,
 }
 
 impl RepositoryFactory {
@@ -28,6 +51,9 @@ impl RepositoryFactory {
             phone_code_repository: Arc::new(
                 ScyllaPhoneCodeRepository::new(Arc::clone(scylla_context)).await?
             ),
+            email_code_repository: Arc::new(
+                ScyllaEmailCodeRepository::new(Arc::clone(scylla_context)).await?
+            ),
             user_repository: Arc::new(
                 ScyllaUserRepository::new(Arc::clone(scylla_context)).await?
             ),
@@ -46,13 +72,80 @@ impl RepositoryFactory {
             transaction_repository: Arc::new(
                 ScyllaTransactionRepository::new(Arc::clone(scylla_context)).await?
             ),
+            registry_policy_repository: Arc::new(
+                ScyllaRegistryPolicyRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            api_key_repository: Arc::new(
+                ScyllaApiKeyRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            rate_limit_repository: Arc::new(
+                ScyllaRateLimitRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            user_login_repository: Arc::new(
+                ScyllaUserLoginRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            user_email_repository: Arc::new(
+                ScyllaUserEmailRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            user_contact_repository: Arc::new(
+                ScyllaUserContactRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            phone_hash_repository: Arc::new(
+                ScyllaPhoneHashRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            direct_registry_repository: Arc::new(
+                ScyllaDirectRegistryRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            pending_registry_repository: Arc::new(
+                ScyllaPendingRegistryRepository::new(Arc::clone(scylla_context)).await?
+            ),
+            user_revocation_repository: Arc::new(
+                ScyllaUserRevocationRepository::new(Arc::clone(scylla_context)).await?
+            ),
         })
     }
 
+    pub fn memory(memory_context: &Arc<MemoryContext>) -> Self {
+        let registry_user_repository = Arc::new(MemoryRegistryUserRepository::new());
+
+        Self {
+            phone_code_repository: Arc::new(
+                MemoryPhoneCodeRepository::new(Arc::clone(memory_context))
+            ),
+            email_code_repository: Arc::new(
+                MemoryEmailCodeRepository::new(Arc::clone(memory_context))
+            ),
+            user_repository: Arc::new(MemoryUserRepository::new()),
+            user_token_repository: Arc::new(
+                MemoryUserTokenRepository::new(Arc::clone(memory_context))
+            ),
+            registry_repository: Arc::new(MemoryRegistryRepository::new()),
+            user_registry_repository: Arc::new(
+                MemoryUserRegistryRepository::new(Arc::clone(&registry_user_repository))
+            ),
+            registry_user_repository,
+            transaction_repository: Arc::new(MemoryTransactionRepository::new()),
+            registry_policy_repository: Arc::new(MemoryRegistryPolicyRepository::new()),
+            api_key_repository: Arc::new(MemoryApiKeyRepository::new()),
+            rate_limit_repository: Arc::new(MemoryRateLimitRepository::new()),
+            user_login_repository: Arc::new(MemoryUserLoginRepository::new()),
+            user_email_repository: Arc::new(MemoryUserEmailRepository::new()),
+            user_contact_repository: Arc::new(MemoryUserContactRepository::new()),
+            phone_hash_repository: Arc::new(MemoryPhoneHashRepository::new()),
+            direct_registry_repository: Arc::new(MemoryDirectRegistryRepository::new()),
+            pending_registry_repository: Arc::new(MemoryPendingRegistryRepository::new()),
+            user_revocation_repository: Arc::new(MemoryUserRevocationRepository::new()),
+        }
+    }
+
     pub fn phone_code(&self) -> Arc<dyn PhoneCodeRepository + Sync + Send> {
         Arc::clone(&self.phone_code_repository)
     }
 
+    pub fn email_code(&self) -> Arc<dyn EmailCodeRepository + Sync + Send> {
+        Arc::clone(&self.email_code_repository)
+    }
+
     pub fn user(&self) -> Arc<dyn UserRepository + Sync + Send> {
         Arc::clone(&self.user_repository)
     }
@@ -76,4 +169,44 @@ impl RepositoryFactory {
     pub fn transaction(&self) -> Arc<dyn TransactionRepository + Sync + Send> {
         Arc::clone(&self.transaction_repository)
     }
+
+    pub fn registry_policy(&self) -> Arc<dyn RegistryPolicyRepository + Sync + Send> {
+        Arc::clone(&self.registry_policy_repository)
+    }
+
+    pub fn api_key(&self) -> Arc<dyn ApiKeyRepository + Sync + Send> {
+        Arc::clone(&self.api_key_repository)
+    }
+
+    pub fn rate_limit(&self) -> Arc<dyn RateLimitRepository + Sync + Send> {
+        Arc::clone(&self.rate_limit_repository)
+    }
+
+    pub fn user_login(&self) -> Arc<dyn UserLoginRepository + Sync + Send> {
+        Arc::clone(&self.user_login_repository)
+    }
+
+    pub fn user_email(&self) -> Arc<dyn UserEmailRepository + Sync + Send> {
+        Arc::clone(&self.user_email_repository)
+    }
+
+    pub fn user_contact(&self) -> Arc<dyn UserContactRepository + Sync + Send> {
+        Arc::clone(&self.user_contact_repository)
+    }
+
+    pub fn phone_hash(&self) -> Arc<dyn PhoneHashRepository + Sync + Send> {
+        Arc::clone(&self.phone_hash_repository)
+    }
+
+    pub fn direct_registry(&self) -> Arc<dyn DirectRegistryRepository + Sync + Send> {
+        Arc::clone(&self.direct_registry_repository)
+    }
+
+    pub fn pending_registry(&self) -> Arc<dyn PendingRegistryRepository + Sync + Send> {
+        Arc::clone(&self.pending_registry_repository)
+    }
+
+    pub fn user_revocation(&self) -> Arc<dyn UserRevocationRepository + Sync + Send> {
+        Arc::clone(&self.user_revocation_repository)
+    }
 }
\ No newline at end of file
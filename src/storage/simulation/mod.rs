@@ -0,0 +1,11 @@
+mod simulation_scheduler;
+mod simulated_transaction_repository;
+mod simulated_registry_repository;
+mod simulated_registry_user_repository;
+mod simulated_user_repository;
+
+pub use simulation_scheduler::SimulationScheduler;
+pub use simulated_transaction_repository::SimulatedTransactionRepository;
+pub use simulated_registry_repository::SimulatedRegistryRepository;
+pub use simulated_registry_user_repository::SimulatedRegistryUserRepository;
+pub use simulated_user_repository::SimulatedUserRepository;
\ No newline at end of file
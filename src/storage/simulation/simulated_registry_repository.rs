@@ -0,0 +1,57 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::registries::{RegistryRepository, RegistryDto, RegistryTransactionUpdateDto};
+
+use super::SimulationScheduler;
+
+#[derive(Debug)]
+pub struct SimulatedRegistryRepository {
+    scheduler: Arc<SimulationScheduler>,
+    inner: Arc<dyn RegistryRepository + Sync + Send>,
+}
+
+impl SimulatedRegistryRepository {
+    pub fn new(scheduler: Arc<SimulationScheduler>, inner: Arc<dyn RegistryRepository + Sync + Send>) -> Self {
+        Self {
+            scheduler,
+            inner,
+        }
+    }
+}
+
+#[async_trait]
+impl RegistryRepository for SimulatedRegistryRepository {
+    async fn create(&self, dto: &RegistryDto) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.create(dto).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_transaction(&self, update_dto: &RegistryTransactionUpdateDto) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_transaction(update_dto).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn find(&self, id: i64) -> Result<Option<RegistryDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.find(id).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn list(&self, ids: &[i64]) -> Result<Vec<RegistryDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.list(ids).await?;
+        fault.after()?;
+        Ok(result)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,81 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::registry_users::{RegistryUserRepository, RegistryUserDto, RegistryUserUpdateDto};
+
+use super::SimulationScheduler;
+
+#[derive(Debug)]
+pub struct SimulatedRegistryUserRepository {
+    scheduler: Arc<SimulationScheduler>,
+    inner: Arc<dyn RegistryUserRepository + Sync + Send>,
+}
+
+impl SimulatedRegistryUserRepository {
+    pub fn new(scheduler: Arc<SimulationScheduler>, inner: Arc<dyn RegistryUserRepository + Sync + Send>) -> Self {
+        Self {
+            scheduler,
+            inner,
+        }
+    }
+}
+
+#[async_trait]
+impl RegistryUserRepository for SimulatedRegistryUserRepository {
+    async fn create(&self, dtos: &[RegistryUserDto]) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.create(dtos).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update(&self, dtos: &[RegistryUserUpdateDto]) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update(dtos).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_role(&self, dto: &RegistryUserDto, role: i16) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_role(dto, role).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn delete(&self, dto: &RegistryUserDto) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.delete(dto).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn list(&self, registry_id: i64, user_ids: &[i64]) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.list(registry_id, user_ids).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn list_all(&self, registry_id: i64) -> Result<Vec<RegistryUserDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.list_all(registry_id).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn count(&self, registry_id: i64, user_ids: &[i64]) -> Result<i64, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.count(registry_id, user_ids).await?;
+        fault.after()?;
+        Ok(result)
+    }
+}
\ No newline at end of file
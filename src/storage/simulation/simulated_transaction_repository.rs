@@ -0,0 +1,65 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::transactions::{TransactionRepository, TransactionDto};
+
+use super::SimulationScheduler;
+
+#[derive(Debug)]
+pub struct SimulatedTransactionRepository {
+    scheduler: Arc<SimulationScheduler>,
+    inner: Arc<dyn TransactionRepository + Sync + Send>,
+}
+
+impl SimulatedTransactionRepository {
+    pub fn new(scheduler: Arc<SimulationScheduler>, inner: Arc<dyn TransactionRepository + Sync + Send>) -> Self {
+        Self {
+            scheduler,
+            inner,
+        }
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for SimulatedTransactionRepository {
+    async fn create(&self, dto: &TransactionDto) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.create(dto).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn find(&self, registry_id: i64, pack: i64, sequence: i16) -> Result<Option<TransactionDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.find(registry_id, pack, sequence).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn find_last(&self, registry_id: i64, pack: i64) -> Result<Option<TransactionDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.find_last(registry_id, pack).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn list(&self, registry_id: i64, pack: i64, last_sequence: i16, limit: i32) -> Result<Vec<TransactionDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.list(registry_id, pack, last_sequence, limit).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn list_forward(&self, registry_id: i64, pack: i64, first_sequence: i16, limit: i32) -> Result<Vec<TransactionDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.list_forward(registry_id, pack, first_sequence, limit).await?;
+        fault.after()?;
+        Ok(result)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,105 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::users::{UserRepository, UserDto, UserBalanceUpdateDto};
+
+use super::SimulationScheduler;
+
+#[derive(Debug)]
+pub struct SimulatedUserRepository {
+    scheduler: Arc<SimulationScheduler>,
+    inner: Arc<dyn UserRepository + Sync + Send>,
+}
+
+impl SimulatedUserRepository {
+    pub fn new(scheduler: Arc<SimulationScheduler>, inner: Arc<dyn UserRepository + Sync + Send>) -> Self {
+        Self {
+            scheduler,
+            inner,
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for SimulatedUserRepository {
+    async fn create(&self, dto: &UserDto) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.create(dto).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn find_id(&self, id: i64) -> Result<Option<UserDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.find_id(id).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn find_phone(&self, phone: i64) -> Result<Option<UserDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.find_phone(phone).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn find_email(&self, email: &String) -> Result<Option<UserDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.find_email(email).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_login(&self, id: i64, source_login: &str, login: &str) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_login(id, source_login, login).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_email(&self, id: i64, source_email: &str, email: &str) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_email(id, source_email, email).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_image(&self, id: i64, image: &str) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_image(id, image).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_discoverable(&self, id: i64, discoverable: bool) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_discoverable(id, discoverable).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn update_balance(&self, dto: &UserBalanceUpdateDto) -> Result<bool, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.update_balance(dto).await?;
+        fault.after()?;
+        Ok(result)
+    }
+
+    async fn scan(&self, last_id: Option<i64>, limit: i32) -> Result<Vec<UserDto>, Box<dyn Error>> {
+        let fault = self.scheduler.step().await;
+        fault.before()?;
+        let result = self.inner.scan(last_id, limit).await?;
+        fault.after()?;
+        Ok(result)
+    }
+}
\ No newline at end of file
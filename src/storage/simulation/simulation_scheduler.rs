@@ -0,0 +1,127 @@
+use std::{sync::{Arc, Mutex}, error::Error, future::Future};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tokio::{sync::oneshot, task::JoinHandle};
+
+tokio::task_local! {
+    static ACTOR: usize;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SimulationFault {
+    None,
+    // The call fails before it reaches storage
+    Before,
+    // The call is applied but its result is lost, like a write timeout
+    After,
+}
+
+impl SimulationFault {
+    pub fn before(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::Before => Err("Simulated failure before the call".into()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn after(self) -> Result<(), Box<dyn Error>> {
+        match self {
+            Self::After => Err("Simulated failure after the call".into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SimulationState {
+    rng: StdRng,
+    fault_rate: f64,
+    running: usize,
+    waiting: Vec<(usize, oneshot::Sender<SimulationFault>)>,
+}
+
+// Every repository call of an actor waits here until the scheduler picks it, so
+// a seed fully determines the interleaving and the injected faults
+#[derive(Debug)]
+pub struct SimulationScheduler {
+    state: Mutex<SimulationState>,
+}
+
+struct RunningGuard(Arc<SimulationScheduler>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().running -= 1;
+    }
+}
+
+impl SimulationScheduler {
+    pub fn new(seed: u64, fault_rate: f64) -> Self {
+        Self {
+            state: Mutex::new(SimulationState {
+                rng: StdRng::seed_from_u64(seed),
+                fault_rate,
+                running: 0,
+                waiting: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn spawn<F>(self: &Arc<Self>, actor: usize, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.state.lock().unwrap().running += 1;
+        let guard = RunningGuard(Arc::clone(self));
+
+        tokio::spawn(ACTOR.scope(actor, async move {
+            let _guard = guard;
+            future.await
+        }))
+    }
+
+    // Calls made outside of an actor, like setup and checks, are not gated
+    pub async fn step(&self) -> SimulationFault {
+        let actor = match ACTOR.try_with(|actor| *actor) {
+            Ok(actor) => actor,
+            Err(_) => return SimulationFault::None,
+        };
+
+        let (sender, receiver) = oneshot::channel();
+        self.state.lock().unwrap().waiting.push((actor, sender));
+
+        receiver.await.unwrap_or(SimulationFault::None)
+    }
+
+    // Releases one waiting call at a time, only once every running actor is waiting
+    pub async fn run(&self) {
+        loop {
+            tokio::task::yield_now().await;
+
+            let mut state = self.state.lock().unwrap();
+            if state.running == 0 {
+                return;
+            }
+
+            if state.waiting.len() < state.running {
+                continue;
+            }
+
+            state.waiting.sort_by_key(|(actor, _)| *actor);
+
+            let waiting = state.waiting.len();
+            let index = state.rng.gen_range(0, waiting);
+            let fault_rate = state.fault_rate;
+            let fault = if state.rng.gen_bool(fault_rate) {
+                if state.rng.gen() { SimulationFault::Before } else { SimulationFault::After }
+            }
+            else {
+                SimulationFault::None
+            };
+
+            let (_, sender) = state.waiting.remove(index);
+            let _ = sender.send(fault);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use serde::{Serialize, Deserialize};
+
+use super::ScyllaConfig;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageConfig {
+    Scylla(ScyllaConfig),
+    Memory,
+}
\ No newline at end of file
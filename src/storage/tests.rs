@@ -0,0 +1,272 @@
+use std::{sync::Arc, collections::HashMap};
+
+use bigdecimal::BigDecimal;
+
+use super::{
+    MemoryContext,
+    phone_codes::{PhoneCodeRepository, PhoneCodeDto, MemoryPhoneCodeRepository},
+    user_tokens::{UserTokenRepository, UserTokenDto, MemoryUserTokenRepository},
+    registries::{RegistryRepository, RegistryDto, RegistryTransactionUpdateDto, MemoryRegistryRepository},
+    registry_users::{RegistryUserRepository, RegistryUserDto, RegistryUserUpdateDto, MemoryRegistryUserRepository},
+    user_registries::{UserRegistryRepository, MemoryUserRegistryRepository},
+    transactions::{TransactionRepository, TransactionDto, MemoryTransactionRepository},
+    pending_registries::{PendingRegistryRepository, PendingRegistryDto, MemoryPendingRegistryRepository},
+    users::{UserRepository, UserDto, MemoryUserRepository},
+    user_revocations::{UserRevocationRepository, UserRevocationDto, MemoryUserRevocationRepository},
+};
+
+const PHONE: i64 = 79990001122;
+const REGISTRY_ID: i64 = 100;
+
+fn phone_code(attempts: i16) -> PhoneCodeDto {
+    PhoneCodeDto {
+        phone: PHONE,
+        code: 1234,
+        created_at: 0,
+        attempts,
+        ttl: 60,
+    }
+}
+
+fn registry(id: i64) -> RegistryDto {
+    RegistryDto {
+        id,
+        created_at: 0,
+        updated_at: 0,
+        current_pack: 0,
+        current_sequence: -1,
+        variant: 1,
+        name: String::new(),
+        image: String::new(),
+    }
+}
+
+fn balance_update(user_id: i64, source_sequence: i16, source_value: Option<i64>, target_value: i64) -> RegistryUserUpdateDto {
+    RegistryUserUpdateDto {
+        registry_id: REGISTRY_ID,
+        user_id,
+        updated_at: 10,
+        source_pack: 0,
+        source_sequence,
+        current_pack: 0,
+        current_sequence: source_sequence + 1,
+        currency: String::from("USD"),
+        source_value: source_value.map(BigDecimal::from),
+        target_value: BigDecimal::from(target_value),
+    }
+}
+
+fn pending_registry(registry_id: i64, sequence: i16) -> PendingRegistryDto {
+    PendingRegistryDto {
+        registry_id,
+        pack: 0,
+        sequence,
+        created_at: 0,
+    }
+}
+
+fn transaction(sequence: i16) -> TransactionDto {
+    TransactionDto {
+        registry_id: REGISTRY_ID,
+        pack: 0,
+        created_at: 0,
+        source_user_id: 1,
+        target_user_id: 2,
+        sequence,
+        variant: 1,
+        amount: BigDecimal::from(1),
+        currency: String::from("USD"),
+        label: String::new(),
+        description: String::new(),
+        hash_version: 1,
+        hash: Vec::new(),
+    }
+}
+
+#[tokio::test]
+async fn phone_code_create_is_if_not_exists() {
+    let repository = MemoryPhoneCodeRepository::new(Arc::new(MemoryContext::new()));
+
+    assert!(repository.create(&phone_code(0)).await.unwrap());
+    assert!(!repository.create(&phone_code(0)).await.unwrap());
+}
+
+#[tokio::test]
+async fn phone_code_updates_are_conditional() {
+    let repository = MemoryPhoneCodeRepository::new(Arc::new(MemoryContext::new()));
+    repository.create(&phone_code(0)).await.unwrap();
+
+    assert!(repository.update_attempts(&phone_code(0), 1).await.unwrap());
+    assert!(!repository.update_attempts(&phone_code(0), 1).await.unwrap());
+    assert!(!repository.delete(&phone_code(0)).await.unwrap());
+    assert!(repository.delete(&phone_code(1)).await.unwrap());
+    assert!(repository.find(PHONE).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn phone_code_expires_after_ttl() {
+    let memory_context = Arc::new(MemoryContext::new());
+    let repository = MemoryPhoneCodeRepository::new(Arc::clone(&memory_context));
+    repository.create(&phone_code(0)).await.unwrap();
+
+    memory_context.advance(30_000);
+    assert_eq!(repository.find(PHONE).await.unwrap().unwrap().ttl, 30);
+
+    memory_context.advance(30_000);
+    assert!(repository.find(PHONE).await.unwrap().is_none());
+    assert!(!repository.update_attempts(&phone_code(0), 1).await.unwrap());
+    assert!(repository.create(&phone_code(0)).await.unwrap());
+}
+
+#[tokio::test]
+async fn user_token_is_used_once() {
+    let memory_context = Arc::new(MemoryContext::new());
+    let repository = MemoryUserTokenRepository::new(Arc::clone(&memory_context));
+    let dto = UserTokenDto::new(0, 1, String::new(), String::new(), String::new(), 60);
+    repository.create(&dto).await.unwrap();
+
+    assert!(repository.use_token(&dto).await.unwrap());
+    assert!(!repository.use_token(&dto).await.unwrap());
+
+    memory_context.advance(60_000);
+    assert!(repository.list(1).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn user_revocation_keeps_latest() {
+    let repository = MemoryUserRevocationRepository::new();
+    let revocation = |revoked_before| UserRevocationDto { user_id: 1, revoked_before, ttl: 60 };
+
+    repository.update(&revocation(200)).await.unwrap();
+    repository.update(&revocation(100)).await.unwrap();
+
+    assert_eq!(repository.find(1).await.unwrap(), Some(200));
+    assert_eq!(repository.find(2).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn user_login_update_checks_current_login() {
+    let repository = MemoryUserRepository::new();
+    repository.create(&UserDto::from_phone(1, PHONE)).await.unwrap();
+
+    assert!(repository.update_login(1, "", "alice").await.unwrap());
+    assert!(!repository.update_login(1, "", "bob").await.unwrap());
+    assert!(!repository.update_login(2, "", "bob").await.unwrap());
+    assert_eq!(repository.find_id(1).await.unwrap().unwrap().login, "alice");
+}
+
+#[tokio::test]
+async fn registry_update_checks_current_position() {
+    let repository = MemoryRegistryRepository::new();
+    repository.create(&registry(REGISTRY_ID)).await.unwrap();
+
+    let update_dto = RegistryTransactionUpdateDto {
+        id: REGISTRY_ID,
+        source_pack: 0,
+        target_pack: 0,
+        source_updated_at: 0,
+        target_updated_at: 10,
+        source_sequence: -1,
+        target_sequence: 0,
+    };
+
+    assert!(repository.update_transaction(&update_dto).await.unwrap());
+    assert!(!repository.update_transaction(&update_dto).await.unwrap());
+    assert_eq!(repository.find(REGISTRY_ID).await.unwrap().unwrap().current_sequence, 0);
+}
+
+#[tokio::test]
+async fn pending_registry_scan_pages_by_id() {
+    let repository = MemoryPendingRegistryRepository::new();
+    for registry_id in [3, 1, 2] {
+        repository.create(&pending_registry(registry_id, 0)).await.unwrap();
+    }
+
+    let first: Vec<i64> = repository.scan(None, 2).await.unwrap().iter().map(|dto| dto.registry_id).collect();
+    let next: Vec<i64> = repository.scan(Some(2), 2).await.unwrap().iter().map(|dto| dto.registry_id).collect();
+
+    assert_eq!(first, vec![1, 2]);
+    assert_eq!(next, vec![3]);
+}
+
+#[tokio::test]
+async fn user_scan_pages_by_id() {
+    let repository = MemoryUserRepository::new();
+    for id in [3, 1, 2] {
+        repository.create(&UserDto::from_phone(id, PHONE + id)).await.unwrap();
+    }
+
+    let first: Vec<i64> = repository.scan(None, 2).await.unwrap().iter().map(|dto| dto.id).collect();
+    let next: Vec<i64> = repository.scan(Some(2), 2).await.unwrap().iter().map(|dto| dto.id).collect();
+
+    assert_eq!(first, vec![1, 2]);
+    assert_eq!(next, vec![3]);
+}
+
+#[tokio::test]
+async fn pending_registry_delete_keeps_newer_transaction() {
+    let repository = MemoryPendingRegistryRepository::new();
+    repository.create(&pending_registry(REGISTRY_ID, 0)).await.unwrap();
+    repository.create(&pending_registry(REGISTRY_ID, 1)).await.unwrap();
+
+    assert!(!repository.delete(&pending_registry(REGISTRY_ID, 0)).await.unwrap());
+    assert_eq!(repository.scan(None, 10).await.unwrap().len(), 1);
+
+    assert!(repository.delete(&pending_registry(REGISTRY_ID, 1)).await.unwrap());
+    assert!(repository.scan(None, 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn registry_user_batch_applies_all_or_nothing() {
+    let repository = MemoryRegistryUserRepository::new();
+    repository.create(&[
+        RegistryUserDto::new(0, REGISTRY_ID, 1, 1),
+        RegistryUserDto::new(0, REGISTRY_ID, 2, 2),
+    ]).await.unwrap();
+
+    assert!(!repository.create(&[RegistryUserDto::new(0, REGISTRY_ID, 2, 2)]).await.unwrap());
+
+    assert!(repository.update(&[balance_update(1, -1, None, -5), balance_update(2, -1, None, 5)]).await.unwrap());
+    assert!(!repository.update(&[balance_update(1, 0, Some(-5), -6), balance_update(2, 0, None, 6)]).await.unwrap());
+    assert!(!repository.update(&[balance_update(1, -1, Some(-5), -6), balance_update(2, -1, Some(5), 6)]).await.unwrap());
+    assert!(!repository.update(&[balance_update(3, -1, None, 1)]).await.unwrap());
+
+    let balances: HashMap<i64, BigDecimal> = repository.list_all(REGISTRY_ID).await.unwrap()
+        .into_iter()
+        .map(|dto| (dto.user_id, dto.balance["USD"].clone()))
+        .collect();
+
+    assert_eq!(balances[&1], BigDecimal::from(-5));
+    assert_eq!(balances[&2], BigDecimal::from(5));
+    assert_eq!(repository.count(REGISTRY_ID, &[1, 2, 3]).await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn user_registries_are_ordered_by_update() {
+    let registry_user_repository = Arc::new(MemoryRegistryUserRepository::new());
+    let repository = MemoryUserRegistryRepository::new(Arc::clone(&registry_user_repository));
+
+    for (registry_id, updated_at) in [(1, 10), (2, 30), (3, 20)] {
+        registry_user_repository.create(&[RegistryUserDto::new(updated_at, registry_id, 1, 1)]).await.unwrap();
+    }
+
+    let listed: Vec<i64> = repository.list(1, 25, 10).await.unwrap().iter().map(|dto| dto.registry_id).collect();
+
+    assert_eq!(listed, vec![3, 1]);
+}
+
+#[tokio::test]
+async fn transactions_keep_clustering_order() {
+    let repository = MemoryTransactionRepository::new();
+    for sequence in 0..5 {
+        assert!(repository.create(&transaction(sequence)).await.unwrap());
+    }
+    assert!(!repository.create(&transaction(2)).await.unwrap());
+
+    let backward: Vec<i16> = repository.list(REGISTRY_ID, 0, 3, 2).await.unwrap().iter().map(|dto| dto.sequence).collect();
+    let forward: Vec<i16> = repository.list_forward(REGISTRY_ID, 0, 3, 5).await.unwrap().iter().map(|dto| dto.sequence).collect();
+
+    assert_eq!(backward, vec![3, 2]);
+    assert_eq!(forward, vec![3, 4]);
+    assert_eq!(repository.find_last(REGISTRY_ID, 0).await.unwrap().unwrap().sequence, 4);
+}
\ No newline at end of file
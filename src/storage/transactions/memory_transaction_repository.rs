@@ -0,0 +1,69 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap};
+
+use tonic::async_trait;
+
+use super::{TransactionRepository, TransactionDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryTransactionRepository {
+    transactions: Mutex<BTreeMap<(i64, i64, i16), TransactionDto>>,
+}
+
+impl MemoryTransactionRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl TransactionRepository for MemoryTransactionRepository {
+    async fn create(&self, dto: &TransactionDto) -> Result<bool, Box<dyn Error>> {
+        let mut transactions = self.transactions.lock().unwrap();
+        let key = (dto.registry_id, dto.pack, dto.sequence);
+
+        if transactions.contains_key(&key) {
+            return Ok(false);
+        }
+
+        transactions.insert(key, dto.clone());
+
+        Ok(true)
+    }
+
+    async fn find(&self, registry_id: i64, pack: i64, sequence: i16) -> Result<Option<TransactionDto>, Box<dyn Error>> {
+        Ok(self.transactions.lock().unwrap().get(&(registry_id, pack, sequence)).cloned())
+    }
+
+    async fn find_last(&self, registry_id: i64, pack: i64) -> Result<Option<TransactionDto>, Box<dyn Error>> {
+        let transactions = self.transactions.lock().unwrap();
+
+        let mapped = transactions.range((registry_id, pack, i16::MIN)..=(registry_id, pack, i16::MAX))
+            .next_back()
+            .map(|(_, transaction)| transaction.clone());
+
+        Ok(mapped)
+    }
+
+    async fn list(&self, registry_id: i64, pack: i64, last_sequence: i16, limit: i32) -> Result<Vec<TransactionDto>, Box<dyn Error>> {
+        let transactions = self.transactions.lock().unwrap();
+
+        let mapped = transactions.range((registry_id, pack, i16::MIN)..=(registry_id, pack, last_sequence))
+            .rev()
+            .take(limit.max(0) as usize)
+            .map(|(_, transaction)| transaction.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+
+    async fn list_forward(&self, registry_id: i64, pack: i64, first_sequence: i16, limit: i32) -> Result<Vec<TransactionDto>, Box<dyn Error>> {
+        let transactions = self.transactions.lock().unwrap();
+
+        let mapped = transactions.range((registry_id, pack, first_sequence)..=(registry_id, pack, i16::MAX))
+            .take(limit.max(0) as usize)
+            .map(|(_, transaction)| transaction.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -1,7 +1,9 @@
 mod transaction_dto;
 mod transaction_repository;
 mod scylla_transaction_repository;
+mod memory_transaction_repository;
 
 pub use transaction_dto::TransactionDto;
 pub use transaction_repository::TransactionRepository;
-pub use scylla_transaction_repository::ScyllaTransactionRepository;
\ No newline at end of file
+pub use scylla_transaction_repository::ScyllaTransactionRepository;
+pub use memory_transaction_repository::MemoryTransactionRepository;
\ No newline at end of file
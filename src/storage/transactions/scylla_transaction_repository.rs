@@ -9,8 +9,10 @@ use super::{super::ScyllaContext, TransactionRepository, TransactionDto};
 pub struct ScyllaTransactionRepository {
     scylla_context: Arc<ScyllaContext>,
     statement_create: PreparedStatement,
+    statement_find: PreparedStatement,
     statement_find_last: PreparedStatement,
     statement_list: PreparedStatement,
+    statement_list_forward: PreparedStatement,
 }
 
 impl ScyllaTransactionRepository {
@@ -28,6 +30,7 @@ impl ScyllaTransactionRepository {
                 currency,
                 label,
                 description,
+                hash_version,
                 hash
             from {}.transactions
         ", &scylla_context.keyspace);
@@ -45,11 +48,19 @@ impl ScyllaTransactionRepository {
                 currency,
                 label,
                 description,
+                hash_version,
                 hash
-            ) values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ) values (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             if not exists
         ", &scylla_context.keyspace)).await?;
 
+        let statement_find = scylla_context.session.prepare(format!("
+            {}
+            where registry_id = ?
+            and pack = ?
+            and sequence = ?
+        ", &select_base)).await?;
+
         let statement_find_last = scylla_context.session.prepare(format!("
             {}
             where registry_id = ?
@@ -67,11 +78,22 @@ impl ScyllaTransactionRepository {
             limit ?
         ", &select_base)).await?;
 
+        let statement_list_forward = scylla_context.session.prepare(format!("
+            {}
+            where registry_id = ?
+            and pack = ?
+            and sequence >= ?
+            order by sequence asc
+            limit ?
+        ", &select_base)).await?;
+
         let result = Self {
             scylla_context,
             statement_create,
+            statement_find,
             statement_find_last,
             statement_list,    
+            statement_list_forward,
         };
 
         Ok(result)
@@ -93,12 +115,23 @@ impl TransactionRepository for ScyllaTransactionRepository {
             &dto.currency,
             &dto.label,
             &dto.description,
+            dto.hash_version,
             &dto.hash,
         )).await?;
 
         Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
     }
 
+    async fn find(&self, registry_id: i64, pack: i64, sequence: i16) -> Result<Option<TransactionDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            registry_id, 
+            pack,
+            sequence,
+        )).await?;
+
+        Ok(result.maybe_first_row_typed::<RowType>()?.map(|row| row.into()))
+    }
+
     async fn find_last(&self, registry_id: i64, pack: i64) -> Result<Option<TransactionDto>, Box<dyn Error>> {
         let result = self.scylla_context.session.execute(&self.statement_find_last, (
             registry_id, 
@@ -128,6 +161,27 @@ impl TransactionRepository for ScyllaTransactionRepository {
 
         Ok(Vec::new())
     }
+
+    async fn list_forward(&self, registry_id: i64, pack: i64, first_sequence: i16, limit: i32) -> Result<Vec<TransactionDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_list_forward, (
+            registry_id, 
+            pack,
+            first_sequence,
+            limit,
+        )).await?;
+
+        if let Some(rows) = result.rows {
+            let mut mapped = Vec::new();
+
+            for row in rows.into_typed::<RowType>() {
+                mapped.push(row?.into());
+            }
+
+            return Ok(mapped);
+        }
+
+        Ok(Vec::new())
+    }
 }
 
 type RowType = (
@@ -142,6 +196,7 @@ type RowType = (
     String,
     String,
     String,
+    Option<i16>,
     Vec<u8>,
 );
 
@@ -159,6 +214,7 @@ impl From<RowType> for TransactionDto {
             currency, 
             label, 
             description, 
+            hash_version,
             hash,
         ) = row; 
 
@@ -174,6 +230,8 @@ impl From<RowType> for TransactionDto {
             currency, 
             label, 
             description, 
+            // Rows written before hash versioning use the legacy format
+            hash_version: hash_version.unwrap_or(1),
             hash,
         }
     }
@@ -1,5 +1,6 @@
 use bigdecimal::BigDecimal;
 
+#[derive(Debug, Clone)]
 pub struct TransactionDto {
     pub registry_id: i64,
     pub pack: i64,
@@ -12,5 +13,6 @@ pub struct TransactionDto {
     pub currency: String,
     pub label: String,
     pub description: String,
+    pub hash_version: i16,
     pub hash: Vec<u8>,
 }
\ No newline at end of file
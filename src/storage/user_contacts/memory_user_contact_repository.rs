@@ -0,0 +1,63 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap, ops::Bound};
+
+use tonic::async_trait;
+
+use super::{UserContactRepository, UserContactDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryUserContactRepository {
+    contacts: Mutex<BTreeMap<(i64, i64), UserContactDto>>,
+}
+
+impl MemoryUserContactRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserContactRepository for MemoryUserContactRepository {
+    async fn create(&self, dto: &UserContactDto) -> Result<bool, Box<dyn Error>> {
+        let mut contacts = self.contacts.lock().unwrap();
+
+        if contacts.contains_key(&(dto.user_id, dto.contact_id)) {
+            return Ok(false);
+        }
+
+        contacts.insert((dto.user_id, dto.contact_id), dto.clone());
+
+        Ok(true)
+    }
+
+    async fn update_state(&self, dto: &UserContactDto, state: i16) -> Result<bool, Box<dyn Error>> {
+        let mut contacts = self.contacts.lock().unwrap();
+
+        match contacts.get_mut(&(dto.user_id, dto.contact_id)) {
+            Some(contact) if contact.state == dto.state => {
+                contact.state = state;
+                contact.updated_at = dto.updated_at;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn delete(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        Ok(self.contacts.lock().unwrap().remove(&(user_id, contact_id)).is_some())
+    }
+
+    async fn find(&self, user_id: i64, contact_id: i64) -> Result<Option<UserContactDto>, Box<dyn Error>> {
+        Ok(self.contacts.lock().unwrap().get(&(user_id, contact_id)).cloned())
+    }
+
+    async fn list(&self, user_id: i64, last_contact_id: i64, limit: i32) -> Result<Vec<UserContactDto>, Box<dyn Error>> {
+        let contacts = self.contacts.lock().unwrap();
+
+        let mapped = contacts.range((Bound::Excluded((user_id, last_contact_id)), Bound::Included((user_id, i64::MAX))))
+            .take(limit.max(0) as usize)
+            .map(|(_, contact)| contact.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod user_contact_dto;
+mod user_contact_repository;
+mod scylla_user_contact_repository;
+mod memory_user_contact_repository;
+
+pub use user_contact_dto::UserContactDto;
+pub use user_contact_repository::UserContactRepository;
+pub use scylla_user_contact_repository::ScyllaUserContactRepository;
+pub use memory_user_contact_repository::MemoryUserContactRepository;
\ No newline at end of file
@@ -0,0 +1,158 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, UserContactRepository, UserContactDto};
+
+#[derive(Debug)]
+pub struct ScyllaUserContactRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_update_state: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_find: PreparedStatement,
+    statement_list: PreparedStatement,
+}
+
+impl ScyllaUserContactRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.user_contacts (
+                user_id,
+                contact_id,
+                state,
+                updated_at
+            ) values (?, ?, ?, ?)
+            if not exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update_state = scylla_context.session.prepare(format!("
+            update {}.user_contacts
+            set state = ?,
+                updated_at = ?
+            where user_id = ?
+            and contact_id = ?
+            if state = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.user_contacts
+            where user_id = ?
+            and contact_id = ?
+            if exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                user_id,
+                contact_id,
+                state,
+                updated_at
+            from {}.user_contacts
+            where user_id = ?
+            and contact_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_list = scylla_context.session.prepare(format!("
+            select
+                user_id,
+                contact_id,
+                state,
+                updated_at
+            from {}.user_contacts
+            where user_id = ?
+            and contact_id > ?
+            limit ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_update_state,
+            statement_delete,
+            statement_find,
+            statement_list,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl UserContactRepository for ScyllaUserContactRepository {
+    async fn create(&self, dto: &UserContactDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_create, (
+            dto.user_id,
+            dto.contact_id,
+            dto.state,
+            dto.updated_at,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_state(&self, dto: &UserContactDto, state: i16) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_state, (
+            state,
+            dto.updated_at,
+            dto.user_id,
+            dto.contact_id,
+            dto.state,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn delete(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            user_id,
+            contact_id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn find(&self, user_id: i64, contact_id: i64) -> Result<Option<UserContactDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            user_id,
+            contact_id,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(i64, i64, i16, i64)>()?.map(|row| {
+            let (user_id, contact_id, state, updated_at) = row;
+            UserContactDto {
+                user_id,
+                contact_id,
+                state,
+                updated_at,
+            }
+        });
+
+        Ok(mapped)
+    }
+
+    async fn list(&self, user_id: i64, last_contact_id: i64, limit: i32) -> Result<Vec<UserContactDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_list, (
+            user_id,
+            last_contact_id,
+            limit,
+        )).await?;
+
+        let mut mapped = Vec::new();
+
+        if let Some(rows) = result.rows {
+            for row in rows.into_typed::<(i64, i64, i16, i64)>() {
+                let (user_id, contact_id, state, updated_at) = row?;
+                mapped.push(UserContactDto {
+                    user_id,
+                    contact_id,
+                    state,
+                    updated_at,
+                });
+            }
+        }
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
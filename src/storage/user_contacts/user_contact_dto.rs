@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct UserContactDto {
+    pub user_id: i64,
+    pub contact_id: i64,
+    pub state: i16,
+    pub updated_at: i64,
+}
\ No newline at end of file
@@ -0,0 +1,14 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::UserContactDto;
+
+#[async_trait]
+pub trait UserContactRepository: fmt::Debug {
+    async fn create(&self, dto: &UserContactDto) -> Result<bool, Box<dyn Error>>;
+    async fn update_state(&self, dto: &UserContactDto, state: i16) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, user_id: i64, contact_id: i64) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self, user_id: i64, contact_id: i64) -> Result<Option<UserContactDto>, Box<dyn Error>>;
+    async fn list(&self, user_id: i64, last_contact_id: i64, limit: i32) -> Result<Vec<UserContactDto>, Box<dyn Error>>;
+}
\ No newline at end of file
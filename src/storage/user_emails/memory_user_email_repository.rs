@@ -0,0 +1,52 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{UserEmailRepository, UserEmailDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryUserEmailRepository {
+    user_emails: Mutex<HashMap<String, i64>>,
+}
+
+impl MemoryUserEmailRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserEmailRepository for MemoryUserEmailRepository {
+    async fn create(&self, dto: &UserEmailDto) -> Result<bool, Box<dyn Error>> {
+        let mut user_emails = self.user_emails.lock().unwrap();
+
+        if user_emails.contains_key(&dto.email) {
+            return Ok(false);
+        }
+
+        user_emails.insert(dto.email.clone(), dto.user_id);
+
+        Ok(true)
+    }
+
+    async fn delete(&self, dto: &UserEmailDto) -> Result<bool, Box<dyn Error>> {
+        let mut user_emails = self.user_emails.lock().unwrap();
+
+        if user_emails.get(&dto.email) != Some(&dto.user_id) {
+            return Ok(false);
+        }
+
+        user_emails.remove(&dto.email);
+
+        Ok(true)
+    }
+
+    async fn find(&self, email: &str) -> Result<Option<UserEmailDto>, Box<dyn Error>> {
+        let mapped = self.user_emails.lock().unwrap().get(email).map(|user_id| UserEmailDto {
+            email: String::from(email),
+            user_id: *user_id,
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod user_email_dto;
+mod user_email_repository;
+mod scylla_user_email_repository;
+mod memory_user_email_repository;
+
+pub use user_email_dto::UserEmailDto;
+pub use user_email_repository::UserEmailRepository;
+pub use scylla_user_email_repository::ScyllaUserEmailRepository;
+pub use memory_user_email_repository::MemoryUserEmailRepository;
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, UserEmailRepository, UserEmailDto};
+
+#[derive(Debug)]
+pub struct ScyllaUserEmailRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaUserEmailRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.user_emails (
+                email,
+                user_id
+            ) values (?, ?)
+            if not exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.user_emails
+            where email = ?
+            if user_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                email,
+                user_id
+            from {}.user_emails
+            where email = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_delete,
+            statement_find,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl UserEmailRepository for ScyllaUserEmailRepository {
+    async fn create(&self, dto: &UserEmailDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_create, (
+            &dto.email,
+            dto.user_id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn delete(&self, dto: &UserEmailDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            &dto.email,
+            dto.user_id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn find(&self, email: &str) -> Result<Option<UserEmailDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            email,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(String, i64)>()?.map(|row| {
+            let (email, user_id) = row;
+            UserEmailDto {
+                email,
+                user_id,
+            }
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
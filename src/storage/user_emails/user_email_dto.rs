@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct UserEmailDto {
+    pub email: String,
+    pub user_id: i64,
+}
\ No newline at end of file
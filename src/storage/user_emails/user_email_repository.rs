@@ -0,0 +1,12 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::UserEmailDto;
+
+#[async_trait]
+pub trait UserEmailRepository: fmt::Debug {
+    async fn create(&self, dto: &UserEmailDto) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, dto: &UserEmailDto) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self, email: &str) -> Result<Option<UserEmailDto>, Box<dyn Error>>;
+}
\ No newline at end of file
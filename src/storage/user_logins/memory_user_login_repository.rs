@@ -0,0 +1,52 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{UserLoginRepository, UserLoginDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryUserLoginRepository {
+    user_logins: Mutex<HashMap<String, i64>>,
+}
+
+impl MemoryUserLoginRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserLoginRepository for MemoryUserLoginRepository {
+    async fn create(&self, dto: &UserLoginDto) -> Result<bool, Box<dyn Error>> {
+        let mut user_logins = self.user_logins.lock().unwrap();
+
+        if user_logins.contains_key(&dto.login) {
+            return Ok(false);
+        }
+
+        user_logins.insert(dto.login.clone(), dto.user_id);
+
+        Ok(true)
+    }
+
+    async fn delete(&self, dto: &UserLoginDto) -> Result<bool, Box<dyn Error>> {
+        let mut user_logins = self.user_logins.lock().unwrap();
+
+        if user_logins.get(&dto.login) != Some(&dto.user_id) {
+            return Ok(false);
+        }
+
+        user_logins.remove(&dto.login);
+
+        Ok(true)
+    }
+
+    async fn find(&self, login: &str) -> Result<Option<UserLoginDto>, Box<dyn Error>> {
+        let mapped = self.user_logins.lock().unwrap().get(login).map(|user_id| UserLoginDto {
+            login: String::from(login),
+            user_id: *user_id,
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
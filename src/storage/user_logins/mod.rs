@@ -0,0 +1,9 @@
+mod user_login_dto;
+mod user_login_repository;
+mod scylla_user_login_repository;
+mod memory_user_login_repository;
+
+pub use user_login_dto::UserLoginDto;
+pub use user_login_repository::UserLoginRepository;
+pub use scylla_user_login_repository::ScyllaUserLoginRepository;
+pub use memory_user_login_repository::MemoryUserLoginRepository;
\ No newline at end of file
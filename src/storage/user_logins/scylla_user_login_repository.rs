@@ -0,0 +1,86 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, UserLoginRepository, UserLoginDto};
+
+#[derive(Debug)]
+pub struct ScyllaUserLoginRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_create: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaUserLoginRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        let statement_create = scylla_context.session.prepare(format!("
+            insert into {}.user_logins (
+                login,
+                user_id
+            ) values (?, ?)
+            if not exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.user_logins
+            where login = ?
+            if user_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                login,
+                user_id
+            from {}.user_logins
+            where login = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_create,
+            statement_delete,
+            statement_find,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl UserLoginRepository for ScyllaUserLoginRepository {
+    async fn create(&self, dto: &UserLoginDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_create, (
+            &dto.login,
+            dto.user_id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn delete(&self, dto: &UserLoginDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_delete, (
+            &dto.login,
+            dto.user_id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn find(&self, login: &str) -> Result<Option<UserLoginDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            login,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(String, i64)>()?.map(|row| {
+            let (login, user_id) = row;
+            UserLoginDto {
+                login,
+                user_id,
+            }
+        });
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
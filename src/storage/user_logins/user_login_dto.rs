@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct UserLoginDto {
+    pub login: String,
+    pub user_id: i64,
+}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::UserLoginDto;
+
+#[async_trait]
+pub trait UserLoginRepository: fmt::Debug {
+    async fn create(&self, dto: &UserLoginDto) -> Result<bool, Box<dyn Error>>;
+    async fn delete(&self, dto: &UserLoginDto) -> Result<bool, Box<dyn Error>>;
+    async fn find(&self, login: &str) -> Result<Option<UserLoginDto>, Box<dyn Error>>;
+}
\ No newline at end of file
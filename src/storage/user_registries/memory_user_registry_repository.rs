@@ -0,0 +1,45 @@
+use std::{sync::Arc, error::Error};
+
+use tonic::async_trait;
+
+use crate::storage::registry_users::MemoryRegistryUserRepository;
+
+use super::{UserRegistryRepository, UserRegistryDto};
+
+// Materialized view over registry users, computed on every read
+#[derive(Debug)]
+pub struct MemoryUserRegistryRepository {
+    registry_user_repository: Arc<MemoryRegistryUserRepository>,
+}
+
+impl MemoryUserRegistryRepository {
+    pub fn new(registry_user_repository: Arc<MemoryRegistryUserRepository>) -> Self {
+        Self {
+            registry_user_repository,
+        }
+    }
+}
+
+#[async_trait]
+impl UserRegistryRepository for MemoryUserRegistryRepository {
+    async fn list(&self, user_id: i64, last_updated_at: i64, limit: i32) -> Result<Vec<UserRegistryDto>, Box<dyn Error>> {
+        let mut mapped: Vec<UserRegistryDto> = self.registry_user_repository.list_user(user_id)
+            .into_iter()
+            .filter(|registry_user| registry_user.updated_at <= last_updated_at)
+            .map(|registry_user| UserRegistryDto {
+                user_id: registry_user.user_id,
+                updated_at: registry_user.updated_at,
+                registry_id: registry_user.registry_id,
+            })
+            .collect();
+
+        mapped.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(a.registry_id.cmp(&b.registry_id)));
+        mapped.truncate(limit.max(0) as usize);
+
+        Ok(mapped)
+    }
+
+    async fn list_all(&self, user_id: i64) -> Result<Vec<UserRegistryDto>, Box<dyn Error>> {
+        self.list(user_id, i64::MAX, i32::MAX).await
+    }
+}
\ No newline at end of file
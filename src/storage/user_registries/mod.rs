@@ -1,7 +1,9 @@
 mod user_registry_dto;
 mod user_registry_repository;
 mod scylla_user_registry_repository;
+mod memory_user_registry_repository;
 
 pub use user_registry_dto::UserRegistryDto;
 pub use user_registry_repository::UserRegistryRepository;
-pub use scylla_user_registry_repository::ScyllaUserRegistryRepository;
\ No newline at end of file
+pub use scylla_user_registry_repository::ScyllaUserRegistryRepository;
+pub use memory_user_registry_repository::MemoryUserRegistryRepository;
\ No newline at end of file
@@ -1,5 +1,5 @@
 use std::{sync::Arc, error::Error};
-use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows};
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows, QueryResult};
 use tonic::async_trait;
 
 use crate::storage::ScyllaContext;
@@ -10,6 +10,7 @@ use super::{UserRegistryDto, UserRegistryRepository};
 pub struct ScyllaUserRegistryRepository {
     scylla_context: Arc<ScyllaContext>,
     statement_list: PreparedStatement,
+    statement_list_all: PreparedStatement,
 }
 
 impl ScyllaUserRegistryRepository {
@@ -26,9 +27,19 @@ impl ScyllaUserRegistryRepository {
             limit ?;
         ", &scylla_context.keyspace)).await?;
 
+        let statement_list_all = scylla_context.session.prepare(format!("
+            select
+                user_id,
+                updated_at,
+                registry_id
+            from {}.user_registries
+            where user_id = ?;
+        ", &scylla_context.keyspace)).await?;
+
         let result = Self {
             scylla_context,
             statement_list,    
+            statement_list_all,
         };
 
         Ok(result)
@@ -44,28 +55,40 @@ impl UserRegistryRepository for ScyllaUserRegistryRepository {
             limit, 
         )).await?;
 
-        if let Some(rows) = result.rows {
-            let mut mapped = Vec::new();
+        map_user_registry_dtos(result)
+    }
 
-            for row in rows.into_typed::<(i64, i64, i64)>() {
-                let (
-                    user_id,
-                    updated_at,
-                    registry_id,
-                ) = row?; 
+    async fn list_all(&self, user_id: i64) -> Result<Vec<UserRegistryDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_list_all, (
+            user_id,
+        )).await?;
 
-                let dto = UserRegistryDto {
-                    user_id,
-                    updated_at,
-                    registry_id,
-                };
+        map_user_registry_dtos(result)
+    }
+}
+
+fn map_user_registry_dtos(result: QueryResult) -> Result<Vec<UserRegistryDto>, Box<dyn Error>> {
+    if let Some(rows) = result.rows {
+        let mut mapped = Vec::new();
 
-                mapped.push(dto);
-            }
+        for row in rows.into_typed::<(i64, i64, i64)>() {
+            let (
+                user_id,
+                updated_at,
+                registry_id,
+            ) = row?; 
 
-            return Ok(mapped);
+            let dto = UserRegistryDto {
+                user_id,
+                updated_at,
+                registry_id,
+            };
+
+            mapped.push(dto);
         }
 
-        Ok(Vec::new())
+        return Ok(mapped);
     }
+
+    Ok(Vec::new())
 }
\ No newline at end of file
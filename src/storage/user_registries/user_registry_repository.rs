@@ -7,4 +7,5 @@ use super::UserRegistryDto;
 #[async_trait]
 pub trait UserRegistryRepository: fmt::Debug {
     async fn list(&self, user_id: i64, last_updated_at: i64, limit: i32) -> Result<Vec<UserRegistryDto>, Box<dyn Error>>;
+    async fn list_all(&self, user_id: i64) -> Result<Vec<UserRegistryDto>, Box<dyn Error>>;
 }
\ No newline at end of file
@@ -0,0 +1,32 @@
+use std::{sync::Mutex, error::Error, collections::HashMap};
+
+use tonic::async_trait;
+
+use super::{UserRevocationRepository, UserRevocationDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryUserRevocationRepository {
+    user_revocations: Mutex<HashMap<i64, i64>>,
+}
+
+impl MemoryUserRevocationRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl UserRevocationRepository for MemoryUserRevocationRepository {
+    async fn update(&self, dto: &UserRevocationDto) -> Result<(), Box<dyn Error>> {
+        let mut user_revocations = self.user_revocations.lock().unwrap();
+
+        let revoked_before = user_revocations.entry(dto.user_id).or_insert(dto.revoked_before);
+        *revoked_before = (*revoked_before).max(dto.revoked_before);
+
+        Ok(())
+    }
+
+    async fn find(&self, user_id: i64) -> Result<Option<i64>, Box<dyn Error>> {
+        Ok(self.user_revocations.lock().unwrap().get(&user_id).copied())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+mod user_revocation_dto;
+mod user_revocation_repository;
+mod scylla_user_revocation_repository;
+mod memory_user_revocation_repository;
+
+pub use user_revocation_dto::UserRevocationDto;
+pub use user_revocation_repository::UserRevocationRepository;
+pub use scylla_user_revocation_repository::ScyllaUserRevocationRepository;
+pub use memory_user_revocation_repository::MemoryUserRevocationRepository;
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::{sync::Arc, error::Error};
+
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use tonic::async_trait;
+
+use super::{super::ScyllaContext, UserRevocationRepository, UserRevocationDto};
+
+#[derive(Debug)]
+pub struct ScyllaUserRevocationRepository {
+    scylla_context: Arc<ScyllaContext>,
+    statement_update: PreparedStatement,
+    statement_find: PreparedStatement,
+}
+
+impl ScyllaUserRevocationRepository {
+    pub async fn new(scylla_context: Arc<ScyllaContext>) -> Result<Self, QueryError> {
+        // Write timestamp is the revocation time, so a late write of an older revocation loses
+        let statement_update = scylla_context.session.prepare(format!("
+            update {}.user_revocations
+            using ttl ? and timestamp ?
+            set revoked_before = ?
+            where user_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_find = scylla_context.session.prepare(format!("
+            select
+                revoked_before
+            from {}.user_revocations
+            where user_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let result = Self {
+            scylla_context,
+            statement_update,
+            statement_find,
+        };
+
+        Ok(result)
+    }
+}
+
+#[async_trait]
+impl UserRevocationRepository for ScyllaUserRevocationRepository {
+    async fn update(&self, dto: &UserRevocationDto) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_update, (
+            dto.ttl,
+            dto.revoked_before * 1000,
+            dto.revoked_before,
+            dto.user_id,
+        )).await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, user_id: i64) -> Result<Option<i64>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            user_id,
+        )).await?;
+
+        let mapped = result.maybe_first_row_typed::<(i64,)>()?.map(|row| row.0);
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct UserRevocationDto {
+    pub user_id: i64,
+    // Access tokens of the user issued before it are rejected
+    pub revoked_before: i64,
+    pub ttl: i32,
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use std::{fmt, error::Error};
+
+use tonic::async_trait;
+
+use super::UserRevocationDto;
+
+#[async_trait]
+pub trait UserRevocationRepository: fmt::Debug {
+    async fn update(&self, dto: &UserRevocationDto) -> Result<(), Box<dyn Error>>;
+    async fn find(&self, user_id: i64) -> Result<Option<i64>, Box<dyn Error>>;
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use std::{sync::{Arc, Mutex}, error::Error, collections::BTreeMap};
+
+use tonic::async_trait;
+
+use crate::storage::MemoryContext;
+
+use super::{UserTokenRepository, UserTokenDto};
+
+#[derive(Debug)]
+struct MemoryUserToken {
+    dto: UserTokenDto,
+    expires_at: i64,
+}
+
+#[derive(Debug)]
+pub struct MemoryUserTokenRepository {
+    memory_context: Arc<MemoryContext>,
+    tokens: Mutex<BTreeMap<(i64, String), MemoryUserToken>>,
+}
+
+impl MemoryUserTokenRepository {
+    pub fn new(memory_context: Arc<MemoryContext>) -> Self {
+        Self {
+            memory_context,
+            tokens: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn prune(&self, tokens: &mut BTreeMap<(i64, String), MemoryUserToken>) {
+        tokens.retain(|_, token| self.memory_context.is_alive(token.expires_at));
+    }
+
+    fn map(&self, token: &MemoryUserToken) -> UserTokenDto {
+        UserTokenDto {
+            ttl: self.memory_context.ttl(token.expires_at),
+            ..token.dto.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl UserTokenRepository for MemoryUserTokenRepository {
+    async fn create(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>> {
+        self.tokens.lock().unwrap().insert((dto.user_id, dto.id.clone()), MemoryUserToken {
+            dto: dto.clone(),
+            expires_at: self.memory_context.expires_at(dto.ttl),
+        });
+
+        Ok(())
+    }
+
+    async fn use_token(&self, dto: &UserTokenDto) -> Result<bool, Box<dyn Error>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        self.prune(&mut tokens);
+
+        match tokens.get_mut(&(dto.user_id, dto.id.clone())) {
+            Some(token) if !token.dto.used => {
+                token.dto.used = true;
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn touch(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        self.prune(&mut tokens);
+
+        if let Some(token) = tokens.get_mut(&(dto.user_id, dto.id.clone())) {
+            token.dto.ip = dto.ip.clone();
+            token.dto.last_used_at = dto.last_used_at;
+        }
+
+        Ok(())
+    }
+
+    async fn delete(&self, user_id: i64, ids: &[String]) -> Result<(), Box<dyn Error>> {
+        let mut tokens = self.tokens.lock().unwrap();
+
+        for id in ids {
+            tokens.remove(&(user_id, id.clone()));
+        }
+
+        Ok(())
+    }
+
+    async fn delete_all(&self, user_id: i64) -> Result<(), Box<dyn Error>> {
+        self.tokens.lock().unwrap().retain(|(token_user_id, _), _| *token_user_id != user_id);
+
+        Ok(())
+    }
+
+    async fn find(&self, user_id: i64, id: &str) -> Result<Option<UserTokenDto>, Box<dyn Error>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        self.prune(&mut tokens);
+
+        Ok(tokens.get(&(user_id, String::from(id))).map(|token| self.map(token)))
+    }
+
+    async fn list(&self, user_id: i64) -> Result<Vec<UserTokenDto>, Box<dyn Error>> {
+        let mut tokens = self.tokens.lock().unwrap();
+        self.prune(&mut tokens);
+
+        let mapped = tokens.range((user_id, String::new())..)
+            .take_while(|((token_user_id, _), _)| *token_user_id == user_id)
+            .map(|(_, token)| self.map(token))
+            .collect();
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
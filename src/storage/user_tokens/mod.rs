@@ -1,7 +1,9 @@
 mod user_token_dto;
 mod user_token_repository;
 mod scylla_user_token_repository;
+mod memory_user_token_repository;
 
 pub use user_token_dto::UserTokenDto;
 pub use user_token_repository::UserTokenRepository;
-pub use scylla_user_token_repository::ScyllaUserTokenRepository;
\ No newline at end of file
+pub use scylla_user_token_repository::ScyllaUserTokenRepository;
+pub use memory_user_token_repository::MemoryUserTokenRepository;
\ No newline at end of file
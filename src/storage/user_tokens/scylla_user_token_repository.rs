@@ -1,6 +1,6 @@
 use std::{sync::Arc, error::Error};
 
-use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError};
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, IntoTypedRows, QueryResult};
 use tonic::async_trait;
 
 use crate::storage::ScyllaContext;
@@ -11,7 +11,12 @@ use super::{UserTokenRepository, UserTokenDto};
 pub struct ScyllaUserTokenRepository {
     scylla_context: Arc<ScyllaContext>,
     statement_create: PreparedStatement,
-    statement_exists: PreparedStatement,
+    statement_use: PreparedStatement,
+    statement_touch: PreparedStatement,
+    statement_delete: PreparedStatement,
+    statement_delete_all: PreparedStatement,
+    statement_find: PreparedStatement,
+    statement_list: PreparedStatement,
 }
 
 impl ScyllaUserTokenRepository {
@@ -19,22 +24,83 @@ impl ScyllaUserTokenRepository {
         let statement_create = scylla_context.session.prepare(format!("
             insert into {}.user_tokens (
                 user_id,
-                id
-            ) values (?, ?)
+                id,
+                family,
+                used,
+                device_name,
+                user_agent,
+                ip,
+                created_at,
+                last_used_at
+            ) values (?, ?, ?, ?, ?, ?, ?, ?, ?)
             using ttl ?
         ", &scylla_context.keyspace)).await?;
 
-        let statement_exists = scylla_context.session.prepare(format!("
-            select count(1)
-            from {}.user_tokens
+        let statement_use = scylla_context.session.prepare(format!("
+            update {}.user_tokens
+            using ttl ?
+            set used = true
+            where user_id = ?
+            and id = ?
+            if used != true
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_touch = scylla_context.session.prepare(format!("
+            update {}.user_tokens
+            using ttl ?
+            set 
+                ip = ?,
+                last_used_at = ?
             where user_id = ?
             and id = ?
         ", &scylla_context.keyspace)).await?;
 
+        let statement_delete = scylla_context.session.prepare(format!("
+            delete from {}.user_tokens
+            where user_id = ?
+            and id in ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_delete_all = scylla_context.session.prepare(format!("
+            delete from {}.user_tokens
+            where user_id = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let select_base = format!("
+            select
+                user_id,
+                id,
+                family,
+                used,
+                device_name,
+                user_agent,
+                ip,
+                created_at,
+                last_used_at,
+                ttl(family)
+            from {}.user_tokens
+        ", &scylla_context.keyspace);
+
+        let statement_find = scylla_context.session.prepare(format!("
+            {}
+            where user_id = ?
+            and id = ?
+        ", &select_base)).await?;
+
+        let statement_list = scylla_context.session.prepare(format!("
+            {}
+            where user_id = ?
+        ", &select_base)).await?;
+
         let result = Self {
             scylla_context,
             statement_create,
-            statement_exists, 
+            statement_use,
+            statement_touch,
+            statement_delete,
+            statement_delete_all,
+            statement_find,
+            statement_list,
         };
 
         Ok(result)
@@ -43,24 +109,126 @@ impl ScyllaUserTokenRepository {
 
 #[async_trait]
 impl UserTokenRepository for ScyllaUserTokenRepository {
-    async fn create(&self, dto: &UserTokenDto, ttl: i32) -> Result<(), Box<dyn Error>> {
+    async fn create(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>> {
         self.scylla_context.session.execute(&self.statement_create, (
             dto.user_id, 
             &dto.id,
-            ttl,
+            &dto.family,
+            dto.used,
+            &dto.device_name,
+            &dto.user_agent,
+            &dto.ip,
+            dto.created_at,
+            dto.last_used_at,
+            dto.ttl,
         )).await?;
 
         Ok(())
     }
 
-    async fn exists(&self, dto: &UserTokenDto) -> Result<bool, Box<dyn Error>> {
-        let result = self.scylla_context.session.execute(&self.statement_exists, (
+    async fn use_token(&self, dto: &UserTokenDto) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_use, (
+            dto.ttl,
             dto.user_id,
-            &dto.id, 
+            &dto.id,
         )).await?;
 
-        let (count, ) = result.single_row_typed::<(i64,)>()?;
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
 
-        Ok(count > 0)
+    async fn touch(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_touch, (
+            dto.ttl,
+            &dto.ip,
+            dto.last_used_at,
+            dto.user_id,
+            &dto.id,
+        )).await?;
+
+        Ok(())
     }
-}
\ No newline at end of file
+
+    async fn delete(&self, user_id: i64, ids: &[String]) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_delete, (
+            user_id,
+            ids,
+        )).await?;
+
+        Ok(())
+    }
+
+    async fn delete_all(&self, user_id: i64) -> Result<(), Box<dyn Error>> {
+        self.scylla_context.session.execute(&self.statement_delete_all, (
+            user_id,
+        )).await?;
+
+        Ok(())
+    }
+
+    async fn find(&self, user_id: i64, id: &str) -> Result<Option<UserTokenDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_find, (
+            user_id,
+            id, 
+        )).await?;
+
+        Ok(map_user_token_dtos(result)?.pop())
+    }
+
+    async fn list(&self, user_id: i64) -> Result<Vec<UserTokenDto>, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_list, (
+            user_id,
+        )).await?;
+
+        map_user_token_dtos(result)
+    }
+}
+
+fn map_user_token_dtos(result: QueryResult) -> Result<Vec<UserTokenDto>, Box<dyn Error>> {
+    let mut mapped = Vec::new();
+
+    if let Some(rows) = result.rows {
+        for row in rows.into_typed::<RowType>() {
+            let (
+                user_id, 
+                id, 
+                family, 
+                used, 
+                device_name, 
+                user_agent, 
+                ip, 
+                created_at, 
+                last_used_at, 
+                ttl,
+            ) = row?;
+
+            // Tokens issued before rotation are roots of their own families
+            mapped.push(UserTokenDto {
+                user_id,
+                family: family.unwrap_or_else(|| id.clone()),
+                id,
+                used: used.unwrap_or(false),
+                device_name: device_name.unwrap_or_default(),
+                user_agent: user_agent.unwrap_or_default(),
+                ip: ip.unwrap_or_default(),
+                created_at: created_at.unwrap_or(0),
+                last_used_at: last_used_at.unwrap_or(0),
+                ttl: ttl.unwrap_or(0),
+            });
+        }
+    }
+
+    Ok(mapped)
+}
+
+type RowType = (
+    i64, 
+    String, 
+    Option<String>, 
+    Option<bool>, 
+    Option<String>, 
+    Option<String>, 
+    Option<String>, 
+    Option<i64>, 
+    Option<i64>, 
+    Option<i32>,
+);
\ No newline at end of file
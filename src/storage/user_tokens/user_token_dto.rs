@@ -1,15 +1,56 @@
 use uuid::Uuid;
 
+#[derive(Debug, Clone)]
 pub struct UserTokenDto {
     pub user_id: i64,
     pub id: String,
+    pub family: String,
+    pub used: bool,
+    pub device_name: String,
+    pub user_agent: String,
+    pub ip: String,
+    pub created_at: i64,
+    pub last_used_at: i64,
+    pub ttl: i32,
 }
 
 impl UserTokenDto {
-    pub fn new(user_id: i64) -> Self {
+    pub fn new(
+        timestamp: i64,
+        user_id: i64, 
+        device_name: String, 
+        user_agent: String, 
+        ip: String, 
+        ttl: i32,
+    ) -> Self {
+        let id = base64_url::encode(Uuid::new_v4().as_bytes());
+
         Self {
             user_id,
+            family: id.clone(),
+            id,
+            used: false,
+            device_name,
+            user_agent,
+            ip,
+            created_at: timestamp,
+            last_used_at: timestamp,
+            ttl,
+        }
+    }
+
+    pub fn rotate(&self, timestamp: i64, ip: String, ttl: i32) -> Self {
+        Self {
+            user_id: self.user_id,
             id: base64_url::encode(Uuid::new_v4().as_bytes()),
+            family: self.family.clone(),
+            used: false,
+            device_name: self.device_name.clone(),
+            user_agent: self.user_agent.clone(),
+            ip,
+            created_at: self.created_at,
+            last_used_at: timestamp,
+            ttl,
         }
     }
 }
\ No newline at end of file
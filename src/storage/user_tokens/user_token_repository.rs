@@ -6,6 +6,11 @@ use super::UserTokenDto;
 
 #[async_trait]
 pub trait UserTokenRepository: fmt::Debug {
-    async fn create(&self, dto: &UserTokenDto, ttl: i32) -> Result<(), Box<dyn Error>>;
-    async fn exists(&self, dto: &UserTokenDto) -> Result<bool, Box<dyn Error>>;
+    async fn create(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>>;
+    async fn use_token(&self, dto: &UserTokenDto) -> Result<bool, Box<dyn Error>>;
+    async fn touch(&self, dto: &UserTokenDto) -> Result<(), Box<dyn Error>>;
+    async fn delete(&self, user_id: i64, ids: &[String]) -> Result<(), Box<dyn Error>>;
+    async fn delete_all(&self, user_id: i64) -> Result<(), Box<dyn Error>>;
+    async fn find(&self, user_id: i64, id: &str) -> Result<Option<UserTokenDto>, Box<dyn Error>>;
+    async fn list(&self, user_id: i64) -> Result<Vec<UserTokenDto>, Box<dyn Error>>;
 }
\ No newline at end of file
@@ -0,0 +1,112 @@
+use std::{sync::Mutex, error::Error, collections::BTreeMap, ops::Bound};
+
+use tonic::async_trait;
+
+use super::{UserRepository, UserDto, UserBalanceUpdateDto};
+
+#[derive(Debug, Default)]
+pub struct MemoryUserRepository {
+    users: Mutex<BTreeMap<i64, UserDto>>,
+}
+
+impl MemoryUserRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update(&self, id: i64, apply: impl FnOnce(&mut UserDto)) -> bool {
+        match self.users.lock().unwrap().get_mut(&id) {
+            Some(user) => {
+                apply(user);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
+#[async_trait]
+impl UserRepository for MemoryUserRepository {
+    async fn create(&self, dto: &UserDto) -> Result<bool, Box<dyn Error>> {
+        let mut users = self.users.lock().unwrap();
+
+        if users.contains_key(&dto.id) {
+            return Ok(false);
+        }
+
+        users.insert(dto.id, dto.clone());
+
+        Ok(true)
+    }
+
+    async fn find_id(&self, id: i64) -> Result<Option<UserDto>, Box<dyn Error>> {
+        Ok(self.users.lock().unwrap().get(&id).cloned())
+    }
+
+    // Phone and email are secondary indexes, so lookups scan the whole table
+    async fn find_phone(&self, phone: i64) -> Result<Option<UserDto>, Box<dyn Error>> {
+        Ok(self.users.lock().unwrap().values().find(|user| user.phone == phone).cloned())
+    }
+
+    async fn find_email(&self, email: &String) -> Result<Option<UserDto>, Box<dyn Error>> {
+        Ok(self.users.lock().unwrap().values().find(|user| &user.email == email).cloned())
+    }
+
+    async fn update_login(&self, id: i64, source_login: &str, login: &str) -> Result<bool, Box<dyn Error>> {
+        match self.users.lock().unwrap().get_mut(&id) {
+            Some(user) if user.login == source_login => {
+                user.login = String::from(login);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_email(&self, id: i64, source_email: &str, email: &str) -> Result<bool, Box<dyn Error>> {
+        match self.users.lock().unwrap().get_mut(&id) {
+            Some(user) if user.email == source_email => {
+                user.email = String::from(email);
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn update_image(&self, id: i64, image: &str) -> Result<bool, Box<dyn Error>> {
+        Ok(self.update(id, |user| user.image = String::from(image)))
+    }
+
+    async fn update_discoverable(&self, id: i64, discoverable: bool) -> Result<bool, Box<dyn Error>> {
+        Ok(self.update(id, |user| user.discoverable = discoverable))
+    }
+
+    async fn update_balance(&self, dto: &UserBalanceUpdateDto) -> Result<bool, Box<dyn Error>> {
+        let mut users = self.users.lock().unwrap();
+
+        match users.get_mut(&dto.id) {
+            Some(user) if user.balance_revision == dto.source_revision => {
+                user.balance = dto.balance.clone();
+                user.balance_revision = dto.target_revision;
+                user.balance_positions = dto.balance_positions.clone();
+                Ok(true)
+            },
+            _ => Ok(false),
+        }
+    }
+
+    async fn scan(&self, last_id: Option<i64>, limit: i32) -> Result<Vec<UserDto>, Box<dyn Error>> {
+        let users = self.users.lock().unwrap();
+
+        let start = match last_id {
+            Some(last_id) => Bound::Excluded(last_id),
+            None => Bound::Unbounded,
+        };
+
+        let mapped = users.range((start, Bound::Unbounded))
+            .take(limit.max(0) as usize)
+            .map(|(_, user)| user.clone())
+            .collect();
+
+        Ok(mapped)
+    }
+}
\ No newline at end of file
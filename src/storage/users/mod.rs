@@ -1,7 +1,11 @@
 mod user_dto;
+mod user_balance_update_dto;
 mod user_repository;
 mod scylla_user_repository;
+mod memory_user_repository;
 
 pub use user_dto::UserDto;
+pub use user_balance_update_dto::UserBalanceUpdateDto;
 pub use user_repository::UserRepository;
-pub use scylla_user_repository::ScyllaUserRepository;
\ No newline at end of file
+pub use scylla_user_repository::ScyllaUserRepository;
+pub use memory_user_repository::MemoryUserRepository;
\ No newline at end of file
@@ -1,12 +1,26 @@
 use std::{sync::Arc, error::Error, collections::HashMap};
 
 use bigdecimal::BigDecimal;
-use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, QueryResult};
+use scylla::{prepared_statement::PreparedStatement, transport::errors::QueryError, QueryResult, IntoTypedRows};
 use tonic::async_trait;
 
 use crate::storage::ScyllaContext;
 
-use super::{UserDto, UserRepository};
+use super::{UserDto, UserRepository, UserBalanceUpdateDto};
+
+type RowType = (
+    i64, 
+    i64, 
+    String, 
+    String, 
+    String, 
+    Option<bool>,
+    Option<bool>,
+    Option<HashMap<String, BigDecimal>>,
+    Option<i64>,
+    Option<HashMap<i64, i64>>,
+    Option<HashMap<i64, i16>>,
+);
 
 #[derive(Debug)]
 pub struct ScyllaUserRepository {
@@ -15,6 +29,13 @@ pub struct ScyllaUserRepository {
     statement_find_id: PreparedStatement,
     statement_find_phone: PreparedStatement,
     statement_find_email: PreparedStatement,
+    statement_update_login: PreparedStatement,
+    statement_update_email: PreparedStatement,
+    statement_update_image: PreparedStatement,
+    statement_update_discoverable: PreparedStatement,
+    statement_update_balance: PreparedStatement,
+    statement_scan_first: PreparedStatement,
+    statement_scan_next: PreparedStatement,
 }
 
 impl ScyllaUserRepository {
@@ -26,8 +47,10 @@ impl ScyllaUserRepository {
                 email,
                 login,
                 image,
+                discoverable,
+                service_account,
                 balance
-            ) values (?, ?, ?, ?, ?, ?)
+            ) values (?, ?, ?, ?, ?, ?, ?, ?)
             if not exists
         ", &scylla_context.keyspace)).await?;
 
@@ -38,7 +61,12 @@ impl ScyllaUserRepository {
                 email,
                 login,
                 image,
-                balance
+                discoverable,
+                service_account,
+                balance,
+                balance_revision,
+                balance_packs,
+                balance_sequences
             from {}.users 
             where id = ?
         ", &scylla_context.keyspace)).await?;
@@ -50,7 +78,12 @@ impl ScyllaUserRepository {
                 email,
                 login,
                 image,
-                balance
+                discoverable,
+                service_account,
+                balance,
+                balance_revision,
+                balance_packs,
+                balance_sequences
             from {}.users 
             where phone = ?
         ", &scylla_context.keyspace)).await?;
@@ -62,17 +95,92 @@ impl ScyllaUserRepository {
                 email,
                 login,
                 image,
-                balance
+                discoverable,
+                service_account,
+                balance,
+                balance_revision,
+                balance_packs,
+                balance_sequences
             from {}.users 
             where email = ?
         ", &scylla_context.keyspace)).await?;
 
+        let statement_update_login = scylla_context.session.prepare(format!("
+            update {}.users
+            set login = ?
+            where id = ?
+            if login = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update_email = scylla_context.session.prepare(format!("
+            update {}.users
+            set email = ?
+            where id = ?
+            if email = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update_image = scylla_context.session.prepare(format!("
+            update {}.users
+            set image = ?
+            where id = ?
+            if exists
+        ", &scylla_context.keyspace)).await?;
+
+        let statement_update_discoverable = scylla_context.session.prepare(format!("
+            update {}.users
+            set discoverable = ?
+            where id = ?
+            if exists
+        ", &scylla_context.keyspace)).await?;
+
+        // Rows are never deleted, so a missing revision is only ever compared on existing users
+        let statement_update_balance = scylla_context.session.prepare(format!("
+            update {}.users
+            set balance = ?, balance_revision = ?, balance_packs = ?, balance_sequences = ?
+            where id = ?
+            if balance_revision = ?
+        ", &scylla_context.keyspace)).await?;
+
+        let select_base = format!("
+            select
+                id,
+                phone,
+                email,
+                login,
+                image,
+                discoverable,
+                service_account,
+                balance,
+                balance_revision,
+                balance_packs,
+                balance_sequences
+            from {}.users
+        ", &scylla_context.keyspace);
+
+        let statement_scan_first = scylla_context.session.prepare(format!("
+            {}
+            limit ?
+        ", &select_base)).await?;
+
+        let statement_scan_next = scylla_context.session.prepare(format!("
+            {}
+            where token(id) > token(?)
+            limit ?
+        ", &select_base)).await?;
+
         let result = Self {
             scylla_context,
             statement_insert,   
             statement_find_id,
             statement_find_phone,
             statement_find_email,
+            statement_update_login,
+            statement_update_email,
+            statement_update_image,
+            statement_update_discoverable,
+            statement_update_balance,
+            statement_scan_first,
+            statement_scan_next,
         };
 
         Ok(result)
@@ -88,6 +196,8 @@ impl UserRepository for ScyllaUserRepository {
             &dto.email,
             &dto.login,
             &dto.image,
+            dto.discoverable,
+            dto.service_account,
             &dto.balance,
         )).await?;
 
@@ -117,27 +227,110 @@ impl UserRepository for ScyllaUserRepository {
 
         map_user_dto(result)
     }
-}
 
-fn map_user_dto(result: QueryResult) -> Result<Option<UserDto>, Box<dyn Error>> {
-    let mapped = result.maybe_first_row_typed::<(
-        i64, 
-        i64, 
-        String, 
-        String, 
-        String, 
-        Option<HashMap<String, BigDecimal>>,
-    )>()?.map(|row| {
-        let (id, phone, email, login, image, balance) = row;
-        UserDto { 
-            id, 
-            phone, 
-            email, 
-            login, 
+    async fn update_login(&self, id: i64, source_login: &str, login: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_login, (
+            login,
+            id,
+            source_login,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_email(&self, id: i64, source_email: &str, email: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_email, (
+            email,
+            id,
+            source_email,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_image(&self, id: i64, image: &str) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_image, (
             image,
-            balance: balance.unwrap_or(HashMap::new()),
+            id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_discoverable(&self, id: i64, discoverable: bool) -> Result<bool, Box<dyn Error>> {
+        let result = self.scylla_context.session.execute(&self.statement_update_discoverable, (
+            discoverable,
+            id,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn update_balance(&self, dto: &UserBalanceUpdateDto) -> Result<bool, Box<dyn Error>> {
+        let source_revision = if dto.source_revision == 0 { None } else { Some(dto.source_revision) };
+
+        // Positions are kept as two maps of plain values, both written by the same conditional update
+        let packs: HashMap<i64, i64> = dto.balance_positions.iter().map(|(registry_id, (pack, _))| (*registry_id, *pack)).collect();
+        let sequences: HashMap<i64, i16> = dto.balance_positions.iter().map(|(registry_id, (_, sequence))| (*registry_id, *sequence)).collect();
+
+        let result = self.scylla_context.session.execute(&self.statement_update_balance, (
+            &dto.balance,
+            dto.target_revision,
+            &packs,
+            &sequences,
+            dto.id,
+            source_revision,
+        )).await?;
+
+        Ok(result.single_row()?.columns[0].as_ref().unwrap().as_boolean().unwrap())
+    }
+
+    async fn scan(&self, last_id: Option<i64>, limit: i32) -> Result<Vec<UserDto>, Box<dyn Error>> {
+        let result = match last_id {
+            Some(id) => self.scylla_context.session.execute(&self.statement_scan_next, (
+                id,
+                limit,
+            )).await?,
+            None => self.scylla_context.session.execute(&self.statement_scan_first, (
+                limit,
+            )).await?,
+        };
+
+        let mut mapped = Vec::new();
+
+        if let Some(rows) = result.rows {
+            for row in rows.into_typed::<RowType>() {
+                mapped.push(map_row(row?));
+            }
         }
-    });
 
-    Ok(mapped)
+        Ok(mapped)
+    }
+}
+
+fn map_user_dto(result: QueryResult) -> Result<Option<UserDto>, Box<dyn Error>> {
+    Ok(result.maybe_first_row_typed::<RowType>()?.map(map_row))
+}
+
+fn map_row(row: RowType) -> UserDto {
+    let (id, phone, email, login, image, discoverable, service_account, balance, balance_revision, packs, sequences) = row;
+    let sequences = sequences.unwrap_or_default();
+    UserDto { 
+        id, 
+        phone, 
+        email, 
+        login, 
+        image,
+        // Users created before the setting existed stay hidden until they opt in
+        discoverable: discoverable.unwrap_or(false),
+        service_account: service_account.unwrap_or(false),
+        balance: balance.unwrap_or(HashMap::new()),
+        balance_revision: balance_revision.unwrap_or(0),
+        // Users settled before positions were kept have none, their registries are recomputed on the next transaction
+        balance_positions: packs
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(registry_id, pack)| sequences.get(&registry_id).map(|sequence| (registry_id, (pack, *sequence))))
+            .collect(),
+    }
 }
\ No newline at end of file
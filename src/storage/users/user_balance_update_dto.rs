@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use bigdecimal::BigDecimal;
+
+pub struct UserBalanceUpdateDto {
+    pub id: i64,
+    pub source_revision: i64,
+    pub target_revision: i64,
+    pub balance: HashMap<String, BigDecimal>,
+    pub balance_positions: HashMap<i64, (i64, i16)>,
+}
\ No newline at end of file
@@ -2,13 +2,19 @@ use std::collections::HashMap;
 
 use bigdecimal::BigDecimal;
 
+#[derive(Debug, Clone)]
 pub struct UserDto {
     pub id: i64,
     pub phone: i64,
     pub email: String,
     pub login: String,
     pub image: String,
+    pub discoverable: bool,
+    pub service_account: bool,
     pub balance: HashMap<String, BigDecimal>,
+    pub balance_revision: i64,
+    // Registry transaction each registry is counted in the balance up to
+    pub balance_positions: HashMap<i64, (i64, i16)>,
 }
 
 impl UserDto {
@@ -19,7 +25,42 @@ impl UserDto {
             email: String::new(),
             login: String::new(),
             image: String::new(),
+            discoverable: false,
+            service_account: false,
             balance: HashMap::new(),
+            balance_revision: 0,
+            balance_positions: HashMap::new(),
+        }
+    }
+
+    pub fn from_email(id: i64, email: String) -> Self {
+        Self {
+            id,
+            phone: 0,
+            email,
+            login: String::new(),
+            image: String::new(),
+            discoverable: false,
+            service_account: false,
+            balance: HashMap::new(),
+            balance_revision: 0,
+            balance_positions: HashMap::new(),
+        }
+    }
+
+    // Service accounts have no phone or email, so nobody can sign in as them
+    pub fn service_account(id: i64) -> Self {
+        Self {
+            id,
+            phone: 0,
+            email: String::new(),
+            login: String::new(),
+            image: String::new(),
+            discoverable: false,
+            service_account: true,
+            balance: HashMap::new(),
+            balance_revision: 0,
+            balance_positions: HashMap::new(),
         }
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use std::{fmt, error::Error};
 
 use tonic::async_trait;
 
-use super::UserDto;
+use super::{UserDto, UserBalanceUpdateDto};
 
 
 #[async_trait]
@@ -14,4 +14,18 @@ pub trait UserRepository: fmt::Debug {
     async fn find_phone(&self, phone: i64) -> Result<Option<UserDto>, Box<dyn Error>>;
 
     async fn find_email(&self, email: &String) -> Result<Option<UserDto>, Box<dyn Error>>;
+
+    // Applies only while the user still has the source value, so concurrent updates can not both win
+    async fn update_login(&self, id: i64, source_login: &str, login: &str) -> Result<bool, Box<dyn Error>>;
+
+    async fn update_email(&self, id: i64, source_email: &str, email: &str) -> Result<bool, Box<dyn Error>>;
+
+    async fn update_image(&self, id: i64, image: &str) -> Result<bool, Box<dyn Error>>;
+
+    async fn update_discoverable(&self, id: i64, discoverable: bool) -> Result<bool, Box<dyn Error>>;
+
+    async fn update_balance(&self, dto: &UserBalanceUpdateDto) -> Result<bool, Box<dyn Error>>;
+
+    // Pages in token order, the last returned id continues the scan
+    async fn scan(&self, last_id: Option<i64>, limit: i32) -> Result<Vec<UserDto>, Box<dyn Error>>;
 }
\ No newline at end of file
@@ -0,0 +1,38 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::sleep;
+
+use crate::{domain::ServiceFactory, logging::Logger};
+
+pub struct KeyReloadWorker {
+    interval: u64,
+    logger: Arc<Logger>,
+    service_factory: Arc<ServiceFactory>,
+}
+
+impl KeyReloadWorker {
+    pub fn new(
+        interval: u64,
+        logger: Arc<Logger>,
+        service_factory: Arc<ServiceFactory>,
+    ) -> Self {
+        Self {
+            interval,
+            logger,
+            service_factory,
+        }
+    }
+
+    // Requests only read the loaded key set, file changes are picked up here
+    pub async fn run(self) {
+        loop {
+            sleep(Duration::from_millis(self.interval)).await;
+
+            match self.service_factory.token().reload_keys().await {
+                Ok(true) => self.logger.log_info("Token keys reloaded"),
+                Ok(false) => (),
+                Err(error) => self.logger.log_fail(&error),
+            }
+        }
+    }
+}
\ No newline at end of file
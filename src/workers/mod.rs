@@ -0,0 +1,7 @@
+mod reconciler_config;
+mod reconciler_worker;
+mod key_reload_worker;
+
+pub use reconciler_config::ReconcilerConfig;
+pub use reconciler_worker::ReconcilerWorker;
+pub use key_reload_worker::KeyReloadWorker;
\ No newline at end of file
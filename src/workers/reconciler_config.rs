@@ -0,0 +1,9 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReconcilerConfig {
+    pub interval: u64,
+    pub batch_size: i32,
+    pub attempts: i32,
+    pub backoff: u64,
+}
\ No newline at end of file
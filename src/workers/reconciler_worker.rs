@@ -0,0 +1,92 @@
+use std::{sync::Arc, error::Error, time::Duration};
+
+use tokio::time::sleep;
+
+use crate::{domain::{ServiceFactory, transactions::{TransactionCompletionModel, PendingRegistryModel}}, logging::Logger};
+
+use super::ReconcilerConfig;
+
+pub struct ReconcilerWorker {
+    config: ReconcilerConfig,
+    logger: Arc<Logger>,
+    service_factory: Arc<ServiceFactory>,
+}
+
+impl ReconcilerWorker {
+    pub fn new(
+        config: ReconcilerConfig,
+        logger: Arc<Logger>,
+        service_factory: Arc<ServiceFactory>,
+    ) -> Self {
+        Self {
+            config,
+            logger,
+            service_factory,
+        }
+    }
+
+    pub async fn run(self) {
+        loop {
+            if let Err(error) = self.sweep().await {
+                self.logger.log_fail(&error);
+            }
+
+            sleep(Duration::from_millis(self.config.interval)).await;
+        }
+    }
+
+    // Only registries indexed by a settlement that may have left the registry or a projection behind are visited
+    async fn sweep(&self) -> Result<(), Box<dyn Error>> {
+        let transaction_service = self.service_factory.transaction();
+
+        let mut last_registry_id = None;
+
+        loop {
+            let pending_registries = transaction_service.list_pending(last_registry_id, self.config.batch_size).await?;
+
+            for pending in pending_registries.iter() {
+                self.reconcile(pending).await;
+            }
+
+            if pending_registries.len() < self.config.batch_size as usize {
+                return Ok(());
+            }
+
+            last_registry_id = pending_registries.last().map(|pending| pending.registry_id);
+        }
+    }
+
+    async fn reconcile(&self, pending: &PendingRegistryModel) {
+        let transaction_service = self.service_factory.transaction();
+        let registry_id = pending.registry_id;
+
+        let mut backoff = self.config.backoff;
+
+        for attempt in 1..=self.config.attempts {
+            match transaction_service.complete_pending(pending).await {
+                Ok(TransactionCompletionModel::Consistent) => return,
+                Ok(TransactionCompletionModel::Completed(pack, sequence)) => {
+                    self.logger.log_info(&format!(
+                        "Reconciler completed transaction [{}:{}] of registry {} on attempt {}", 
+                        pack,
+                        sequence,
+                        registry_id,
+                        attempt,
+                    ));
+                    return;
+                },
+                Ok(TransactionCompletionModel::Conflict) => (),
+                Err(error) => self.logger.log_fail(&error),
+            }
+
+            sleep(Duration::from_millis(backoff)).await;
+            backoff = backoff.saturating_mul(2);
+        }
+
+        self.logger.log_info(&format!(
+            "Reconciler gave up on registry {} after {} attempts", 
+            registry_id,
+            self.config.attempts,
+        ));
+    }
+}
\ No newline at end of file